@@ -14,11 +14,51 @@ fn main() {
         println!("cargo:warning=[BUILD] Swift plugin compilation complete");
     }
 
+    emit_build_info();
+
     println!("cargo:warning=[BUILD] Running Tauri build...");
     tauri_build::build();
     println!("cargo:warning=[BUILD] Build process complete!");
 }
 
+/// Expose `GIT_HASH` and `BUILD_TIME` (Unix seconds) to the crate via `env!`.
+fn emit_build_info() {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|stdout| stdout.trim().to_string())
+            .filter(|stdout| !stdout.is_empty())
+    };
+
+    let git_hash = git(&["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=GIT_HASH={}", git_hash);
+
+    // SOURCE_DATE_EPOCH keeps reproducible builds reproducible
+    let build_time = env::var("SOURCE_DATE_EPOCH").unwrap_or_else(|_| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0)
+            .to_string()
+    });
+    println!("cargo:rustc-env=BUILD_TIME={}", build_time);
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Re-run when HEAD moves so the hash doesn't go stale
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = PathBuf::from(git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        println!(
+            "cargo:rerun-if-changed={}",
+            git_dir.join("refs/heads").display()
+        );
+    }
+}
+
 #[cfg(target_os = "macos")]
 fn compile_macos_sensing() {
     let manifest_dir =
@@ -125,9 +165,7 @@ fn compile_macos_sensing() {
     let bundle_target = resources_dir.join(bundle_name);
 
     if bundle_source.exists() {
-        println!(
-            "cargo:warning=[COPY] Copying resource bundle...\n"
-        );
+        println!("cargo:warning=[COPY] Copying resource bundle...\n");
         println!("cargo:warning=[COPY]   Source: {}", bundle_source.display());
         println!("cargo:warning=[COPY]   Target: {}", bundle_target.display());
 
@@ -38,7 +38,8 @@ let package = Package(
                 .linkedFramework("Cocoa"),
                 .linkedFramework("ScreenCaptureKit"),
                 .linkedFramework("Vision"),
-                .linkedFramework("AVFoundation")
+                .linkedFramework("AVFoundation"),
+                .linkedFramework("UserNotifications")
             ]
         )
     ]
@@ -18,10 +18,12 @@ extern void macos_sensing_swift_island_cleanup(void);
 extern void macos_sensing_swift_island_update_chime_preferences(bool enabled, const char *sound_id);
 extern void macos_sensing_swift_island_preview_chime(const char *sound_id);
 extern void macos_sensing_swift_island_set_visible(bool visible);
+extern void macos_sensing_swift_post_notification(const char *title, const char *body);
 extern void macos_sensing_swift_audio_start_monitoring(void);
 extern void macos_sensing_swift_audio_toggle_playback(void);
 extern void macos_sensing_swift_audio_next_track(void);
 extern void macos_sensing_swift_audio_previous_track(void);
+extern double macos_sensing_swift_get_idle_seconds(void);
 
 extern bool macos_sensing_swift_check_screen_recording_permission(void);
 extern bool macos_sensing_swift_request_screen_recording_permission(void);
@@ -92,6 +94,10 @@ void macos_sensing_island_set_visible(bool visible) {
     macos_sensing_swift_island_set_visible(visible);
 }
 
+void macos_sensing_post_notification(const char *title, const char *body) {
+    macos_sensing_swift_post_notification(title, body);
+}
+
 void macos_sensing_audio_start_monitoring(void) {
     macos_sensing_swift_audio_start_monitoring();
 }
@@ -108,6 +114,10 @@ void macos_sensing_audio_previous_track(void) {
     macos_sensing_swift_audio_previous_track();
 }
 
+double macos_sensing_get_idle_seconds(void) {
+    return macos_sensing_swift_get_idle_seconds();
+}
+
 bool macos_sensing_check_screen_recording_permission(void) {
     return macos_sensing_swift_check_screen_recording_permission();
 }
@@ -40,12 +40,18 @@ void macos_sensing_island_update_chime_preferences(bool enabled, const char *sou
 void macos_sensing_island_preview_chime(const char *sound_id);
 void macos_sensing_island_set_visible(bool visible);
 
+// User notifications
+void macos_sensing_post_notification(const char *title, const char *body);
+
 // Audio monitoring/control
 void macos_sensing_audio_start_monitoring(void);
 void macos_sensing_audio_toggle_playback(void);
 void macos_sensing_audio_next_track(void);
 void macos_sensing_audio_previous_track(void);
 
+// User activity
+double macos_sensing_get_idle_seconds(void);
+
 // Permission checking
 bool macos_sensing_check_screen_recording_permission(void);
 bool macos_sensing_request_screen_recording_permission(void);
@@ -28,6 +28,30 @@ public final class AppIconProvider {
         return "data:image/png;base64,\(base64String)"
     }
 
+    /// Get the localized display name of an app
+    /// - Parameter bundleId: The bundle identifier of the app (e.g., "com.apple.Safari")
+    /// - Returns: Name like "Safari", or nil if the app is not installed
+    public func getDisplayName(forBundleId bundleId: String) -> String? {
+        guard let appURL = NSWorkspace.shared.urlForApplication(withBundleIdentifier: bundleId) else {
+            return nil
+        }
+
+        if let bundle = Bundle(url: appURL) {
+            let localized = bundle.localizedInfoDictionary ?? [:]
+            let info = bundle.infoDictionary ?? [:]
+            for key in ["CFBundleDisplayName", "CFBundleName"] {
+                if let name = (localized[key] ?? info[key]) as? String, !name.isEmpty {
+                    return name
+                }
+            }
+        }
+
+        // Fall back to the bundle's file name as Finder shows it, without ".app"
+        let fileName = FileManager.default.displayName(atPath: appURL.path)
+        let name = (fileName as NSString).deletingPathExtension
+        return name.isEmpty ? nil : name
+    }
+
     /// Resize an NSImage to a target size
     private func resizeImage(_ image: NSImage, to size: NSSize) -> NSImage {
         let resized = NSImage(size: size)
@@ -238,3 +262,29 @@ public func getAppIconAndColorFFI(bundleIdPtr: UnsafePointer<CChar>) -> UnsafeMu
 
     return strdup(jsonString)
 }
+
+/// FFI function to get an app's localized display name
+/// IMPORTANT: Must run on main thread because NSWorkspace APIs require it
+/// Returns a C string that must be freed with macos_sensing_swift_free_string, or nil if not found
+@_cdecl("macos_sensing_app_display_name")
+public func getAppDisplayNameFFI(bundleIdPtr: UnsafePointer<CChar>) -> UnsafeMutablePointer<CChar>? {
+    guard let bundleIdStr = String(validatingUTF8: bundleIdPtr) else {
+        return nil
+    }
+
+    // IMPORTANT: AppKit APIs must run on main thread
+    var result: String?
+    if Thread.isMainThread {
+        result = AppIconProvider.shared.getDisplayName(forBundleId: bundleIdStr)
+    } else {
+        DispatchQueue.main.sync {
+            result = AppIconProvider.shared.getDisplayName(forBundleId: bundleIdStr)
+        }
+    }
+
+    guard let name = result else {
+        return nil
+    }
+
+    return strdup(name)
+}
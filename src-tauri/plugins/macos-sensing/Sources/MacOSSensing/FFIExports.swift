@@ -3,6 +3,7 @@ import Foundation
 import ApplicationServices
 import Cocoa
 import Carbon
+import UserNotifications
 
 @_cdecl("macos_sensing_swift_get_window")
 public func macos_sensing_swift_get_window() -> UnsafeMutablePointer<WindowMetadataFFI>? {
@@ -202,6 +203,35 @@ public func macos_sensing_swift_island_set_visible(_ visible: Bool) {
     }
 }
 
+// MARK: - User notifications bridge
+
+@_cdecl("macos_sensing_swift_post_notification")
+public func macos_sensing_swift_post_notification(_ titlePtr: UnsafePointer<CChar>, _ bodyPtr: UnsafePointer<CChar>) {
+    let title = String(cString: titlePtr)
+    let body = String(cString: bodyPtr)
+
+    let center = UNUserNotificationCenter.current()
+    center.requestAuthorization(options: [.alert, .sound]) { granted, error in
+        if let error = error {
+            print("Notification authorization error: \(error)")
+            return
+        }
+        guard granted else { return }
+
+        let content = UNMutableNotificationContent()
+        content.title = title
+        content.body = body
+        content.sound = .default
+
+        let request = UNNotificationRequest(identifier: UUID().uuidString, content: content, trigger: nil)
+        center.add(request) { error in
+            if let error = error {
+                print("Notification post error: \(error)")
+            }
+        }
+    }
+}
+
 // MARK: - Audio controls bridge
 
 @_cdecl("macos_sensing_swift_audio_start_monitoring")
@@ -232,6 +262,15 @@ public func macos_sensing_swift_audio_previous_track() {
     }
 }
 
+// MARK: - User activity bridge
+
+/// Seconds since the last keyboard, mouse, or trackpad event in the login session.
+@_cdecl("macos_sensing_swift_get_idle_seconds")
+public func macos_sensing_swift_get_idle_seconds() -> Double {
+    guard let anyInput = CGEventType(rawValue: ~0) else { return 0 }
+    return CGEventSource.secondsSinceLastEventType(.combinedSessionState, eventType: anyInput)
+}
+
 // MARK: - Permission checking bridge
 
 @_cdecl("macos_sensing_swift_check_screen_recording_permission")
@@ -4,20 +4,28 @@ use std::time::Duration;
 
 /// Binaural beat generator
 /// Plays two slightly different frequencies in each ear to create a perceived "beat"
+///
+/// Each channel accumulates its own phase, advancing once per stereo frame, so the
+/// output stays continuous if a frequency changes.
 pub struct BinauralBeats {
     left_freq: f32,
     right_freq: f32,
     sample_rate: u32,
-    num_sample: usize,
+    left_phase: f32,
+    right_phase: f32,
+    /// Next sample is the right channel of the current frame.
+    right_next: bool,
 }
 
 impl BinauralBeats {
-    pub fn new(left_freq: f32, right_freq: f32) -> Self {
+    pub fn new(left_freq: f32, right_freq: f32, sample_rate: u32) -> Self {
         Self {
             left_freq,
             right_freq,
-            sample_rate: 44100,
-            num_sample: 0,
+            sample_rate,
+            left_phase: 0.0,
+            right_phase: 0.0,
+            right_next: false,
         }
     }
 }
@@ -26,18 +34,17 @@ impl Iterator for BinauralBeats {
     type Item = f32;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.num_sample = self.num_sample.wrapping_add(1);
-
-        let t = self.num_sample as f32 / self.sample_rate as f32;
-
-        // Alternate between left and right channels (stereo interleaved)
-        let sample = if self.num_sample % 2 == 0 {
-            // Left channel
-            (2.0 * PI * self.left_freq * t).sin()
+        // Stereo interleaved: left, then right, for each frame
+        let sample = if self.right_next {
+            let sample = self.right_phase.sin();
+            advance_phase(&mut self.right_phase, self.right_freq, self.sample_rate);
+            sample
         } else {
-            // Right channel
-            (2.0 * PI * self.right_freq * t).sin()
+            let sample = self.left_phase.sin();
+            advance_phase(&mut self.left_phase, self.left_freq, self.sample_rate);
+            sample
         };
+        self.right_next = !self.right_next;
 
         Some(sample * 0.15) // Lower amplitude to prevent clipping
     }
@@ -60,3 +67,131 @@ impl Source for BinauralBeats {
         None // Infinite
     }
 }
+
+/// Binaural beat whose beat frequency ramps linearly from `start_beat` to `end_beat`
+/// over `sweep_duration`, then holds at `end_beat` (e.g. alpha drifting to theta).
+///
+/// The left ear stays on the carrier and the right ear plays carrier + beat. Each
+/// channel accumulates its own phase, so changing the frequency doesn't click.
+pub struct BinauralSweep {
+    carrier_freq: f32,
+    start_beat: f32,
+    end_beat: f32,
+    sample_rate: u32,
+    /// Stereo frames over which the beat ramps.
+    sweep_frames: u64,
+    frame: u64,
+    left_phase: f32,
+    right_phase: f32,
+    /// Next sample is the right channel of the current frame.
+    right_next: bool,
+}
+
+impl BinauralSweep {
+    pub fn new(
+        carrier_freq: f32,
+        start_beat: f32,
+        end_beat: f32,
+        sweep_duration: Duration,
+        sample_rate: u32,
+    ) -> Self {
+        let sweep_frames = sweep_duration.as_millis() as u64 * u64::from(sample_rate) / 1000;
+        Self {
+            carrier_freq,
+            start_beat,
+            end_beat,
+            sample_rate,
+            sweep_frames,
+            frame: 0,
+            left_phase: 0.0,
+            right_phase: 0.0,
+            right_next: false,
+        }
+    }
+
+    fn current_beat(&self) -> f32 {
+        if self.frame >= self.sweep_frames {
+            return self.end_beat;
+        }
+        let progress = self.frame as f32 / self.sweep_frames as f32;
+        self.start_beat + (self.end_beat - self.start_beat) * progress
+    }
+}
+
+/// Advance `phase` by one sample of `freq` Hz, wrapped to 0..2π to keep f32 precision.
+fn advance_phase(phase: &mut f32, freq: f32, sample_rate: u32) {
+    *phase = (*phase + 2.0 * PI * freq / sample_rate as f32) % (2.0 * PI);
+}
+
+impl Iterator for BinauralSweep {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Stereo interleaved: left, then right, for each frame
+        let sample = if self.right_next {
+            let sample = self.right_phase.sin();
+            let right_freq = self.carrier_freq + self.current_beat();
+            advance_phase(&mut self.right_phase, right_freq, self.sample_rate);
+            self.frame = self.frame.saturating_add(1);
+            sample
+        } else {
+            let sample = self.left_phase.sin();
+            advance_phase(&mut self.left_phase, self.carrier_freq, self.sample_rate);
+            sample
+        };
+        self.right_next = !self.right_next;
+
+        Some(sample * 0.15) // Same amplitude as `BinauralBeats`
+    }
+}
+
+impl Source for BinauralSweep {
+    fn current_frame_len(&self) -> Option<usize> {
+        None // Infinite stream
+    }
+
+    fn channels(&self) -> u16 {
+        2 // Stereo
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None // Infinite
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest step a 0.15-amplitude sine at `freq` can take between two frames.
+    fn max_step(freq: f32, sample_rate: u32) -> f32 {
+        0.15 * 2.0 * PI * freq / sample_rate as f32
+    }
+
+    #[test]
+    fn binaural_beats_channels_have_no_discontinuities() {
+        let (left_freq, right_freq, sample_rate) = (200.0, 210.0, 44_100);
+        // Ten seconds, long enough for the phases to wrap many times
+        let samples: Vec<f32> = BinauralBeats::new(left_freq, right_freq, sample_rate)
+            .take(2 * 10 * sample_rate as usize)
+            .collect();
+
+        for (channel, freq) in [(0, left_freq), (1, right_freq)] {
+            let limit = max_step(freq, sample_rate) * 1.01;
+            let channel_samples: Vec<f32> =
+                samples.iter().skip(channel).step_by(2).copied().collect();
+            for pair in channel_samples.windows(2) {
+                assert!(
+                    (pair[1] - pair[0]).abs() <= limit,
+                    "channel {channel} jumped from {} to {}",
+                    pair[0],
+                    pair[1]
+                );
+            }
+        }
+    }
+}
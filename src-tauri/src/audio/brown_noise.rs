@@ -12,9 +12,9 @@ pub struct BrownNoise {
 }
 
 impl BrownNoise {
-    pub fn new() -> Self {
+    pub fn new(sample_rate: u32) -> Self {
         Self {
-            sample_rate: 44100,
+            sample_rate,
             last_value: 0.0,
             rng: StdRng::from_entropy(),
         }
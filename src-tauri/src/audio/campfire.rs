@@ -0,0 +1,81 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rodio::Source;
+use std::time::Duration;
+
+/// Campfire generator
+/// A soft brown noise bed with randomized short bursts of high-passed noise for pops
+pub struct Campfire {
+    sample_rate: u32,
+    last_brown: f32,
+    last_white: f32,
+    rng: StdRng,
+    // Current crackle amplitude, decaying exponentially after each pop
+    crackle_amp: f32,
+    crackle_decay: f32,
+    pop_probability: f32,
+}
+
+impl Campfire {
+    /// Average number of pops per second
+    const POPS_PER_SEC: f32 = 4.0;
+    /// Time constant of a pop's decay, in seconds
+    const POP_DECAY_SECS: f32 = 0.004;
+
+    pub fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate as f32;
+        Self {
+            sample_rate,
+            last_brown: 0.0,
+            last_white: 0.0,
+            rng: StdRng::from_entropy(),
+            crackle_amp: 0.0,
+            crackle_decay: (-1.0 / (Self::POP_DECAY_SECS * rate)).exp(),
+            pop_probability: Self::POPS_PER_SEC / rate,
+        }
+    }
+}
+
+impl Iterator for Campfire {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let white: f32 = self.rng.gen_range(-1.0..1.0);
+
+        // Brown noise bed for the low roar of the fire
+        self.last_brown += white * 0.02;
+        self.last_brown = self.last_brown.clamp(-1.0, 1.0);
+        self.last_brown *= 0.9999;
+
+        // Occasionally start a pop with a random strength
+        if self.rng.gen::<f32>() < self.pop_probability {
+            self.crackle_amp = self.rng.gen_range(0.3..0.8);
+        }
+
+        // First difference of white noise is a cheap high-pass, keeping pops sharp
+        let crackle = (white - self.last_white) * 0.5 * self.crackle_amp;
+        self.last_white = white;
+        self.crackle_amp *= self.crackle_decay;
+
+        let mix = self.last_brown * 0.6 + crackle;
+        Some((mix * 0.4).clamp(-0.9, 0.9))
+    }
+}
+
+impl Source for Campfire {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1 // Mono
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
@@ -0,0 +1,75 @@
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{OutputStream, OutputStreamHandle};
+use serde::Serialize;
+
+use super::AudioError;
+
+/// An output device as presented to the UI. cpal has no stable device ids, so the
+/// device name doubles as its id.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioDevice {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+pub fn list_output_devices() -> Result<Vec<AudioDevice>, AudioError> {
+    let host = rodio::cpal::default_host();
+    let default_name = host.default_output_device().and_then(|d| d.name().ok());
+    let devices = host
+        .output_devices()
+        .map_err(|e| AudioError::NoOutputDevice(e.to_string()))?;
+
+    Ok(devices
+        .filter_map(|device| device.name().ok())
+        .map(|name| AudioDevice {
+            id: name.clone(),
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect())
+}
+
+/// Whether any output device is connected, checked by enumeration before playback so
+/// a missing device is reported up front instead of as a failed stream.
+pub fn has_output_device() -> bool {
+    let host = rodio::cpal::default_host();
+    host.default_output_device().is_some()
+        || host
+            .output_devices()
+            .map(|mut devices| devices.next().is_some())
+            .unwrap_or(false)
+}
+
+/// Whether an output device named `name` is currently connected.
+pub fn is_output_device_connected(name: &str) -> bool {
+    rodio::cpal::default_host()
+        .output_devices()
+        .map(|mut devices| devices.any(|d| d.name().map(|n| n == name).unwrap_or(false)))
+        .unwrap_or(false)
+}
+
+/// Open `preferred` if it is still connected, otherwise the system default.
+///
+/// The returned flag is `true` when a preferred device was requested but missing.
+pub fn open_output_stream(
+    preferred: Option<&str>,
+) -> Result<(OutputStream, OutputStreamHandle, bool), AudioError> {
+    if let Some(preferred) = preferred {
+        let host = rodio::cpal::default_host();
+        let device = host.output_devices().ok().and_then(|mut devices| {
+            devices.find(|d| d.name().map(|n| n == preferred).unwrap_or(false))
+        });
+        if let Some(device) = device {
+            if let Ok((stream, handle)) = OutputStream::try_from_device(&device) {
+                return Ok((stream, handle, false));
+            }
+        }
+    }
+
+    let (stream, handle) = OutputStream::try_default().map_err(|e| {
+        AudioError::NoOutputDevice(format!("Failed to create audio output stream: {}", e))
+    })?;
+    Ok((stream, handle, preferred.is_some()))
+}
@@ -0,0 +1,125 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::fmt;
+
+/// Failures surfaced by `AudioEngineHandle`.
+///
+/// Serializes as `{ code, message }` so the UI can branch on `code` (e.g. prompt to
+/// connect headphones on `no_output_device`) while still showing a readable message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioError {
+    /// No usable output device, or the device rejected the stream/sink.
+    NoOutputDevice(String),
+    /// The audio thread could not be spawned or stopped responding.
+    ThreadUnavailable(String),
+    /// The command channel to the audio thread is closed.
+    Channel,
+    /// The requested sample rate is not one of `SUPPORTED_SAMPLE_RATES`.
+    UnsupportedSampleRate(u32),
+    /// The requested volume is NaN or infinite.
+    InvalidVolume(String),
+    /// A requested frequency is NaN, infinite or out of range.
+    InvalidFrequency(String),
+}
+
+impl AudioError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AudioError::NoOutputDevice(_) => "no_output_device",
+            AudioError::ThreadUnavailable(_) => "thread_unavailable",
+            AudioError::Channel => "channel",
+            AudioError::UnsupportedSampleRate(_) => "unsupported_sample_rate",
+            AudioError::InvalidVolume(_) => "invalid_volume",
+            AudioError::InvalidFrequency(_) => "invalid_frequency",
+        }
+    }
+}
+
+impl fmt::Display for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioError::NoOutputDevice(detail) => write!(f, "No audio output device: {}", detail),
+            AudioError::ThreadUnavailable(detail) => {
+                write!(f, "Audio thread unavailable: {}", detail)
+            }
+            AudioError::Channel => write!(f, "Audio thread channel closed"),
+            AudioError::UnsupportedSampleRate(rate) => write!(
+                f,
+                "Unsupported sample rate {} Hz (expected one of {:?})",
+                rate,
+                super::SUPPORTED_SAMPLE_RATES
+            ),
+            AudioError::InvalidVolume(value) => {
+                write!(f, "Invalid volume {} (expected a number in 0..=1)", value)
+            }
+            AudioError::InvalidFrequency(value) => write!(f, "Invalid frequency {} Hz", value),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
+
+impl Serialize for AudioError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AudioError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<AudioError> for String {
+    fn from(err: AudioError) -> Self {
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_stable() {
+        let cases = [
+            (AudioError::NoOutputDevice("x".into()), "no_output_device"),
+            (
+                AudioError::ThreadUnavailable("x".into()),
+                "thread_unavailable",
+            ),
+            (AudioError::Channel, "channel"),
+            (
+                AudioError::UnsupportedSampleRate(96_000),
+                "unsupported_sample_rate",
+            ),
+            (AudioError::InvalidVolume("NaN".into()), "invalid_volume"),
+            (
+                AudioError::InvalidFrequency("inf".into()),
+                "invalid_frequency",
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.code(), code);
+        }
+    }
+
+    #[test]
+    fn serializes_as_code_and_message() {
+        let err = AudioError::UnsupportedSampleRate(96_000);
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "code": "unsupported_sample_rate",
+                "message": err.to_string(),
+            })
+        );
+        assert!(err.to_string().contains("96000"));
+    }
+
+    #[test]
+    fn converts_to_its_message() {
+        assert_eq!(
+            String::from(AudioError::Channel),
+            "Audio thread channel closed"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use rodio::Source;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+/// Length of the RMS window, in milliseconds.
+const WINDOW_MS: u32 = 100;
+
+/// Pass-through `Source` that publishes the RMS of each ~100ms window of samples.
+///
+/// The level is stored as `f32` bits in a shared atomic so readers never block the
+/// audio callback.
+pub struct LevelMeter<S> {
+    inner: S,
+    level: Arc<AtomicU32>,
+    window_len: u32,
+    sum_squares: f32,
+    count: u32,
+}
+
+impl<S: Source<Item = f32>> LevelMeter<S> {
+    pub fn new(inner: S, level: Arc<AtomicU32>) -> Self {
+        let samples_per_sec = inner.sample_rate() * u32::from(inner.channels());
+        Self {
+            inner,
+            level,
+            window_len: (samples_per_sec * WINDOW_MS / 1000).max(1),
+            sum_squares: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for LevelMeter<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(sample) = self.inner.next() else {
+            // The source ended (e.g. a timed preview); don't leave its last level showing
+            self.level.store(0.0f32.to_bits(), Ordering::Relaxed);
+            self.sum_squares = 0.0;
+            self.count = 0;
+            return None;
+        };
+
+        self.sum_squares += sample * sample;
+        self.count += 1;
+        if self.count >= self.window_len {
+            let rms = (self.sum_squares / self.count as f32)
+                .sqrt()
+                .clamp(0.0, 1.0);
+            self.level.store(rms.to_bits(), Ordering::Relaxed);
+            self.sum_squares = 0.0;
+            self.count = 0;
+        }
+
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = f32>> Source for LevelMeter<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// Read the most recent level published by a `LevelMeter`.
+pub fn read_level(level: &AtomicU32) -> f32 {
+    f32::from_bits(level.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn level_resets_when_the_source_ends() {
+        let level = Arc::new(AtomicU32::new(0));
+        // Two 100ms windows of a constant 0.5 signal at 1kHz mono
+        let mut meter = LevelMeter::new(
+            SamplesBuffer::new(1, 1000, vec![0.5f32; 200]),
+            level.clone(),
+        );
+
+        assert_eq!(meter.by_ref().take(200).count(), 200);
+        assert!((read_level(&level) - 0.5).abs() < 1e-6);
+
+        assert_eq!(meter.next(), None);
+        assert_eq!(read_level(&level), 0.0);
+    }
+}
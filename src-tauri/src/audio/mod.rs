@@ -1,53 +1,201 @@
 pub mod binaural;
 pub mod brown_noise;
+pub mod campfire;
+pub mod device;
+pub mod error;
+pub mod meter;
+pub mod ocean;
 pub mod rain;
+pub mod timed;
 
-use binaural::BinauralBeats;
+use binaural::{BinauralBeats, BinauralSweep};
 use brown_noise::BrownNoise;
+use campfire::Campfire;
+use device::open_output_stream;
+pub use error::AudioError;
+use meter::{read_level, LevelMeter};
+use ocean::OceanWaves;
 use rain::RainSound;
+use timed::TimedSource;
 
-use rodio::{OutputStream, Sink};
+use log::warn;
+use rodio::{OutputStream, Sink, Source};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    mpsc::{self, Sender},
-    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    mpsc::{self, RecvTimeoutError, Sender},
+    Arc, Mutex, MutexGuard,
 };
-use std::thread;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Sample rate used by generated sources unless the user picks another.
+pub const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+/// Rates offered to the user: cheap on battery, CD quality, and pro-interface native.
+pub const SUPPORTED_SAMPLE_RATES: [u32; 3] = [22_050, 44_100, 48_000];
+
+/// Called with the device now in use (`None` = system default) whenever playback
+/// moves to another device; the app forwards it as `audio-device-changed`.
+pub type DeviceChangedListener = Arc<dyn Fn(Option<String>) + Send + Sync>;
+
+/// Reject non-finite volumes and clamp the rest to the 0..=1 range rodio expects.
+fn validate_volume(volume: f32) -> Result<f32, AudioError> {
+    if !volume.is_finite() {
+        return Err(AudioError::InvalidVolume(volume.to_string()));
+    }
+    Ok(volume.clamp(0.0, 1.0))
+}
+
+/// Generated sources the engine can play on the main sink or as a preview.
+#[derive(Debug, Clone, Copy)]
+pub enum SoundSource {
+    Binaural {
+        left: f32,
+        right: f32,
+    },
+    /// Binaural beat ramping from `start_beat` to `end_beat` Hz over `duration`
+    BinauralSweep {
+        carrier: f32,
+        start_beat: f32,
+        end_beat: f32,
+        duration: Duration,
+    },
+    BrownNoise,
+    Rain,
+    Ocean,
+    Campfire,
+}
+
+impl SoundSource {
+    /// Build the endless generator for this source at `rate` Hz.
+    fn generate(self, rate: u32) -> Box<dyn Source<Item = f32> + Send> {
+        match self {
+            SoundSource::Binaural { left, right } => {
+                Box::new(BinauralBeats::new(left, right, rate))
+            }
+            SoundSource::BinauralSweep {
+                carrier,
+                start_beat,
+                end_beat,
+                duration,
+            } => Box::new(BinauralSweep::new(
+                carrier, start_beat, end_beat, duration, rate,
+            )),
+            SoundSource::BrownNoise => Box::new(BrownNoise::new(rate)),
+            SoundSource::Rain => Box::new(RainSound::new(rate)),
+            SoundSource::Ocean => Box::new(OceanWaves::new(rate)),
+            SoundSource::Campfire => Box::new(Campfire::new(rate)),
+        }
+    }
+}
 
 enum AudioCommand {
-    Start,
+    Start(Sender<Result<(), AudioError>>),
     Stop,
     Pause,
     Play,
     SetVolume(f32),
-    AppendBinaural { left: f32, right: f32 },
-    AppendBrownNoise,
-    AppendRain,
+    Append {
+        source: SoundSource,
+        /// Fade out and end the source after this long; `None` plays until stopped.
+        auto_stop: Option<Duration>,
+    },
+    Preview {
+        source: SoundSource,
+        duration: Duration,
+    },
+}
+
+/// What the main sink was last asked to play, replayed if the audio thread is respawned.
+#[derive(Debug, Clone, Copy, Default)]
+struct PlaybackState {
+    source: Option<SoundSource>,
+    volume: Option<f32>,
+    paused: bool,
+    /// Wall-clock estimate of when an auto-stopping source ends, so a restore replays
+    /// only what is left.
+    auto_stop_at: Option<Instant>,
+}
+
+/// Lock `mutex`, recovering the data if a panicking thread poisoned it. Every value
+/// guarded here stays consistent across a panic, so one panic must not leave the
+/// engine unusable.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
 pub struct AudioEngineHandle {
     tx: Arc<Mutex<Option<Sender<AudioCommand>>>>,
+    /// Join handle of the current audio thread, checked to catch a thread that died
+    /// before the next command is sent.
+    thread: Mutex<Option<JoinHandle<()>>>,
     is_paused: Arc<AtomicBool>,
+    /// RMS of the main sink's output (f32 bits), updated by `LevelMeter`.
+    level: Arc<AtomicU32>,
+    /// Sample rate new sources are generated at.
+    sample_rate: Arc<AtomicU32>,
+    playback: Mutex<PlaybackState>,
+    /// Name of the preferred output device; `None` follows the system default.
+    output_device: Arc<Mutex<Option<String>>>,
+    /// Device the last sink actually opened on (`None` = system default), which
+    /// differs from `output_device` after a fallback.
+    active_device: Arc<Mutex<Option<String>>>,
+    on_device_changed: DeviceChangedListener,
 }
 
 impl AudioEngineHandle {
-    pub fn new() -> Self {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self::with_device_listener(Arc::new(move |device| {
+            if let Err(err) = app_handle.emit("audio-device-changed", device) {
+                warn!("Failed to emit audio-device-changed: {err}");
+            }
+        }))
+    }
+
+    /// Build an engine that reports device changes to `on_device_changed` rather than
+    /// through a Tauri app handle.
+    pub fn with_device_listener(on_device_changed: DeviceChangedListener) -> Self {
         Self {
             tx: Arc::new(Mutex::new(None)),
+            thread: Mutex::new(None),
             is_paused: Arc::new(AtomicBool::new(false)),
+            level: Arc::new(AtomicU32::new(0)),
+            sample_rate: Arc::new(AtomicU32::new(DEFAULT_SAMPLE_RATE)),
+            playback: Mutex::new(PlaybackState::default()),
+            output_device: Arc::new(Mutex::new(None)),
+            active_device: Arc::new(Mutex::new(None)),
+            on_device_changed,
         }
     }
 
-    fn ensure_thread(&self) -> Result<Sender<AudioCommand>, String> {
-        if let Some(tx) = self.tx.lock().map_err(|e| e.to_string())?.as_ref() {
-            return Ok(tx.clone());
+    /// Sender for the running audio thread, spawning one if none exists yet and
+    /// respawning (with playback restored) if the stored one has exited.
+    fn ensure_thread(&self) -> Result<Sender<AudioCommand>, AudioError> {
+        let Some(tx) = lock(&self.tx).clone() else {
+            return self.spawn_thread();
+        };
+        let exited = lock(&self.thread)
+            .as_ref()
+            .is_some_and(|handle| handle.is_finished());
+        if exited {
+            return self.respawn_thread();
         }
+        Ok(tx)
+    }
 
+    fn spawn_thread(&self) -> Result<Sender<AudioCommand>, AudioError> {
         let (tx, rx) = mpsc::channel::<AudioCommand>();
         let is_paused = Arc::clone(&self.is_paused);
+        let level = Arc::clone(&self.level);
+        let sample_rate = Arc::clone(&self.sample_rate);
+        let output_device = Arc::clone(&self.output_device);
+        let active_device = Arc::clone(&self.active_device);
+        let on_device_changed = Arc::clone(&self.on_device_changed);
 
         // Spawn dedicated audio thread holding non-Send audio objects
-        thread::Builder::new()
+        let handle = thread::Builder::new()
             .name("audio-engine".to_string())
             .spawn(move || {
                 let mut _stream: Option<OutputStream> = None;
@@ -56,28 +204,79 @@ impl AudioEngineHandle {
                 fn ensure_sink(
                     stream: &mut Option<OutputStream>,
                     sink: &mut Option<Sink>,
-                ) -> Result<(), String> {
+                    output_device: &Mutex<Option<String>>,
+                    active_device: &Mutex<Option<String>>,
+                    on_device_changed: &DeviceChangedListener,
+                ) -> Result<(), AudioError> {
                     if sink.is_none() {
-                        let (s, handle) = OutputStream::try_default()
-                            .map_err(|e| format!("Failed to create audio output stream: {}", e))?;
-                        let new_sink = Sink::try_new(&handle)
-                            .map_err(|e| format!("Failed to create audio sink: {}", e))?;
+                        let preferred = lock(output_device).clone();
+                        let (s, handle, fell_back) = open_output_stream(preferred.as_deref())?;
+                        let resolved = if fell_back { None } else { preferred.clone() };
+                        *lock(active_device) = resolved.clone();
+                        if fell_back {
+                            warn!(
+                                "Preferred audio device {:?} unavailable; using system default",
+                                preferred
+                            );
+                            on_device_changed(resolved);
+                        }
+                        let new_sink = Sink::try_new(&handle).map_err(|e| {
+                            AudioError::NoOutputDevice(format!(
+                                "Failed to create audio sink: {}",
+                                e
+                            ))
+                        })?;
                         *stream = Some(s);
                         *sink = Some(new_sink);
                     }
                     Ok(())
                 }
 
-                while let Ok(cmd) = rx.recv() {
+                // Previews play on their own stream so the main sink is left untouched
+                let mut preview_stream: Option<OutputStream> = None;
+                let mut preview_sink: Option<Sink> = None;
+                let mut preview_deadline: Option<Instant> = None;
+
+                loop {
+                    let cmd = match preview_deadline {
+                        Some(deadline) => {
+                            match rx
+                                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                            {
+                                Ok(cmd) => cmd,
+                                Err(RecvTimeoutError::Timeout) => {
+                                    if let Some(s_old) = preview_sink.take() {
+                                        s_old.stop();
+                                    }
+                                    preview_stream = None;
+                                    preview_deadline = None;
+                                    continue;
+                                }
+                                Err(RecvTimeoutError::Disconnected) => break,
+                            }
+                        }
+                        None => match rx.recv() {
+                            Ok(cmd) => cmd,
+                            Err(_) => break,
+                        },
+                    };
+
                     match cmd {
-                        AudioCommand::Start => {
+                        AudioCommand::Start(reply) => {
                             // Stop any existing
                             if let Some(s_old) = sink.take() {
                                 s_old.stop();
                             }
                             _stream = None;
-                            let _ = ensure_sink(&mut _stream, &mut sink);
+                            let result = ensure_sink(
+                                &mut _stream,
+                                &mut sink,
+                                &output_device,
+                                &active_device,
+                                &on_device_changed,
+                            );
                             is_paused.store(false, Ordering::SeqCst);
+                            let _ = reply.send(result);
                         }
                         AudioCommand::Stop => {
                             if let Some(s_old) = sink.take() {
@@ -85,6 +284,7 @@ impl AudioEngineHandle {
                             }
                             _stream = None;
                             is_paused.store(false, Ordering::SeqCst);
+                            level.store(0f32.to_bits(), Ordering::Relaxed);
                         }
                         AudioCommand::Pause => {
                             if let Some(ref s) = sink {
@@ -103,80 +303,319 @@ impl AudioEngineHandle {
                                 s.set_volume(v.clamp(0.0, 1.0));
                             }
                         }
-                        AudioCommand::AppendBinaural { left, right } => {
-                            let _ = ensure_sink(&mut _stream, &mut sink);
+                        AudioCommand::Append { source, auto_stop } => {
+                            let _ = ensure_sink(
+                                &mut _stream,
+                                &mut sink,
+                                &output_device,
+                                &active_device,
+                                &on_device_changed,
+                            );
                             if let Some(ref s) = sink {
-                                s.append(BinauralBeats::new(left, right));
+                                let level = Arc::clone(&level);
+                                let generated =
+                                    source.generate(sample_rate.load(Ordering::Relaxed));
+                                match auto_stop {
+                                    Some(after) => s.append(LevelMeter::new(
+                                        TimedSource::new(generated, after),
+                                        level,
+                                    )),
+                                    None => s.append(LevelMeter::new(generated, level)),
+                                }
                             }
                         }
-                        AudioCommand::AppendBrownNoise => {
-                            let _ = ensure_sink(&mut _stream, &mut sink);
-                            if let Some(ref s) = sink {
-                                s.append(BrownNoise::new());
+                        AudioCommand::Preview { source, duration } => {
+                            // A new preview replaces any preview still playing
+                            if let Some(s_old) = preview_sink.take() {
+                                s_old.stop();
                             }
-                        }
-                        AudioCommand::AppendRain => {
-                            let _ = ensure_sink(&mut _stream, &mut sink);
-                            if let Some(ref s) = sink {
-                                s.append(RainSound::new());
+                            preview_stream = None;
+                            preview_deadline = None;
+
+                            if ensure_sink(
+                                &mut preview_stream,
+                                &mut preview_sink,
+                                &output_device,
+                                &active_device,
+                                &on_device_changed,
+                            )
+                            .is_ok()
+                            {
+                                if let Some(ref s) = preview_sink {
+                                    s.append(source.generate(sample_rate.load(Ordering::Relaxed)));
+                                    s.play();
+                                }
+                                preview_deadline = Some(Instant::now() + duration);
                             }
                         }
                     }
                 }
             })
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| AudioError::ThreadUnavailable(e.to_string()))?;
 
         let tx_clone = tx.clone();
-        *self.tx.lock().map_err(|e| e.to_string())? = Some(tx);
+        *lock(&self.thread) = Some(handle);
+        *lock(&self.tx) = Some(tx);
         Ok(tx_clone)
     }
 
-    pub fn start(&self) -> Result<(), String> {
+    /// Send a command, respawning the audio thread once if it has died.
+    fn send(&self, cmd: AudioCommand) -> Result<(), AudioError> {
         let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::Start).map_err(|e| e.to_string())
+        match tx.send(cmd) {
+            Ok(()) => Ok(()),
+            Err(mpsc::SendError(cmd)) => {
+                let tx = self.respawn_thread()?;
+                tx.send(cmd).map_err(|_| AudioError::Channel)
+            }
+        }
     }
 
-    pub fn set_volume(&self, volume: f32) -> Result<(), String> {
-        let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::SetVolume(volume))
-            .map_err(|e| e.to_string())
+    /// Replace a dead audio thread and restore whatever the main sink was playing.
+    fn respawn_thread(&self) -> Result<Sender<AudioCommand>, AudioError> {
+        warn!("Audio thread is gone; respawning");
+        *lock(&self.tx) = None;
+        let tx = self.spawn_thread()?;
+
+        self.restore_playback(&tx)?;
+
+        (self.on_device_changed)(lock(&self.active_device).clone());
+
+        Ok(tx)
     }
 
-    pub fn play(&self) -> Result<(), String> {
-        let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::Play).map_err(|e| e.to_string())
+    /// Rebuild the main sink and replay the remembered source, volume and pause state.
+    fn restore_playback(&self, tx: &Sender<AudioCommand>) -> Result<(), AudioError> {
+        let playback = *lock(&self.playback);
+        let Some(source) = playback.source else {
+            return Ok(());
+        };
+        let auto_stop = match playback.auto_stop_at {
+            Some(at) => match at.checked_duration_since(Instant::now()) {
+                Some(remaining) if !remaining.is_zero() => Some(remaining),
+                // The source already ran out; there is nothing left to replay
+                _ => return Ok(()),
+            },
+            None => None,
+        };
+
+        let (reply_tx, reply_rx) = mpsc::channel();
+        tx.send(AudioCommand::Start(reply_tx))
+            .map_err(|_| AudioError::Channel)?;
+        reply_rx.recv().map_err(|_| {
+            AudioError::ThreadUnavailable("audio thread exited before replying".into())
+        })??;
+        tx.send(AudioCommand::Append { source, auto_stop })
+            .map_err(|_| AudioError::Channel)?;
+        if let Some(volume) = playback.volume {
+            tx.send(AudioCommand::SetVolume(volume))
+                .map_err(|_| AudioError::Channel)?;
+        }
+        let resume = if playback.paused {
+            AudioCommand::Pause
+        } else {
+            AudioCommand::Play
+        };
+        tx.send(resume).map_err(|_| AudioError::Channel)
     }
 
-    pub fn pause(&self) -> Result<(), String> {
-        let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::Pause).map_err(|e| e.to_string())
+    /// Route playback to the named output device (`None` = system default).
+    /// Anything currently playing is moved to the new device. Returns the device now
+    /// in use, which is `None` if `device` isn't connected.
+    pub fn set_output_device(&self, device: Option<String>) -> Result<Option<String>, AudioError> {
+        let resolved = device
+            .clone()
+            .filter(|name| device::is_output_device_connected(name));
+        *lock(&self.output_device) = device;
+        // Rebuilding a sink below records the device it actually opened on
+        *lock(&self.active_device) = resolved;
+
+        let has_thread = lock(&self.tx).is_some();
+        if has_thread {
+            let tx = self.ensure_thread()?;
+            self.restore_playback(&tx)?;
+        }
+        Ok(lock(&self.active_device).clone())
+    }
+
+    /// Generate sources at `rate` Hz. Anything currently playing is regenerated.
+    pub fn set_sample_rate(&self, rate: u32) -> Result<(), AudioError> {
+        if !SUPPORTED_SAMPLE_RATES.contains(&rate) {
+            return Err(AudioError::UnsupportedSampleRate(rate));
+        }
+        self.sample_rate.store(rate, Ordering::Relaxed);
+
+        let has_thread = lock(&self.tx).is_some();
+        if has_thread {
+            let tx = self.ensure_thread()?;
+            self.restore_playback(&tx)?;
+        }
+        Ok(())
+    }
+
+    fn update_playback(&self, f: impl FnOnce(&mut PlaybackState)) {
+        f(&mut lock(&self.playback));
+    }
+
+    /// Whether any audio output device is connected.
+    pub fn has_audio_output_device(&self) -> bool {
+        device::has_output_device()
+    }
+
+    /// Reset the main sink. Waits for the audio thread so a missing output device is
+    /// reported here rather than silently producing no sound.
+    pub fn start(&self) -> Result<(), AudioError> {
+        self.update_playback(|p| *p = PlaybackState::default());
+        let (reply_tx, reply_rx) = mpsc::channel();
+        self.send(AudioCommand::Start(reply_tx))?;
+        reply_rx.recv().map_err(|_| {
+            AudioError::ThreadUnavailable("audio thread exited before replying".into())
+        })?
+    }
+
+    /// Set the main sink volume. Values outside 0..=1 are clamped; the applied volume is
+    /// returned so callers can snap their controls to it.
+    pub fn set_volume(&self, volume: f32) -> Result<f32, AudioError> {
+        let volume = validate_volume(volume)?;
+        self.update_playback(|p| p.volume = Some(volume));
+        self.send(AudioCommand::SetVolume(volume))?;
+        Ok(volume)
+    }
+
+    pub fn play(&self) -> Result<(), AudioError> {
+        self.update_playback(|p| p.paused = false);
+        self.send(AudioCommand::Play)
+    }
+
+    pub fn pause(&self) -> Result<(), AudioError> {
+        self.update_playback(|p| p.paused = true);
+        self.send(AudioCommand::Pause)
     }
 
-    pub fn stop(&self) -> Result<(), String> {
-        if let Ok(Some(tx)) = self.tx.lock().map(|g| g.clone()) {
+    pub fn stop(&self) -> Result<(), AudioError> {
+        self.update_playback(|p| *p = PlaybackState::default());
+        if let Some(tx) = lock(&self.tx).clone() {
             let _ = tx.send(AudioCommand::Stop);
         }
         Ok(())
     }
 
-    pub fn is_paused(&self) -> Result<bool, String> {
+    pub fn is_paused(&self) -> Result<bool, AudioError> {
         Ok(self.is_paused.load(Ordering::SeqCst))
     }
 
-    pub fn append_binaural(&self, left: f32, right: f32) -> Result<(), String> {
-        let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::AppendBinaural { left, right })
-            .map_err(|e| e.to_string())
+    /// Current output level of the main sink in 0..1; 0 while paused or stopped.
+    pub fn level(&self) -> f32 {
+        if self.is_paused.load(Ordering::SeqCst) {
+            return 0.0;
+        }
+        read_level(&self.level)
     }
 
-    pub fn append_brown_noise(&self) -> Result<(), String> {
-        let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::AppendBrownNoise)
-            .map_err(|e| e.to_string())
+    /// Queue `source` on the main sink. With `auto_stop` set it fades out and ends on
+    /// its own after that long instead of playing until `stop`.
+    pub fn append(
+        &self,
+        source: SoundSource,
+        auto_stop: Option<Duration>,
+    ) -> Result<(), AudioError> {
+        self.update_playback(|p| {
+            p.source = Some(source);
+            p.auto_stop_at = auto_stop.map(|after| Instant::now() + after);
+        });
+        self.send(AudioCommand::Append { source, auto_stop })
     }
 
-    pub fn append_rain(&self) -> Result<(), String> {
-        let tx = self.ensure_thread()?;
-        tx.send(AudioCommand::AppendRain).map_err(|e| e.to_string())
+    /// Queue a binaural beat on the main sink whose beat frequency ramps from
+    /// `start_beat` to `end_beat` Hz over `sweep`, then holds. The carrier must be
+    /// positive and the beats non-negative.
+    pub fn append_binaural_sweep(
+        &self,
+        carrier: f32,
+        start_beat: f32,
+        end_beat: f32,
+        sweep: Duration,
+        auto_stop: Option<Duration>,
+    ) -> Result<(), AudioError> {
+        if !carrier.is_finite() || carrier <= 0.0 {
+            return Err(AudioError::InvalidFrequency(carrier.to_string()));
+        }
+        for beat in [start_beat, end_beat] {
+            if !beat.is_finite() || beat < 0.0 {
+                return Err(AudioError::InvalidFrequency(beat.to_string()));
+            }
+        }
+        let source = SoundSource::BinauralSweep {
+            carrier,
+            start_beat,
+            end_beat,
+            duration: sweep,
+        };
+        self.append(source, auto_stop)
+    }
+
+    /// Play `source` for `duration` on a separate sink, then stop it automatically.
+    pub fn preview(&self, source: SoundSource, duration: Duration) -> Result<(), AudioError> {
+        self.send(AudioCommand::Preview { source, duration })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_volume_rejects_nan_and_clamps_out_of_range() {
+        assert!(matches!(
+            validate_volume(f32::NAN),
+            Err(AudioError::InvalidVolume(_))
+        ));
+        assert_eq!(validate_volume(-0.5).unwrap(), 0.0);
+        assert_eq!(validate_volume(1.5).unwrap(), 1.0);
+        assert_eq!(validate_volume(0.25).unwrap(), 0.25);
+    }
+
+    #[test]
+    fn play_respawns_a_dead_audio_thread() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&changes);
+        let engine = AudioEngineHandle::with_device_listener(Arc::new(move |device| {
+            lock(&recorded).push(device);
+        }));
+        // A sender whose receiver is gone, as if the audio thread had died
+        let (dead_tx, dead_rx) = mpsc::channel();
+        drop(dead_rx);
+        *lock(&engine.tx) = Some(dead_tx);
+
+        engine.play().unwrap();
+
+        assert!(lock(&engine.thread)
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished()));
+        let tx = lock(&engine.tx).clone().unwrap();
+        assert!(tx.send(AudioCommand::Play).is_ok());
+        assert_eq!(*lock(&changes), vec![None]);
+    }
+
+    #[test]
+    fn start_recovers_from_poisoned_locks() {
+        let engine = Arc::new(AudioEngineHandle::with_device_listener(Arc::new(|_| {})));
+        let poisoner = Arc::clone(&engine);
+        let _ = thread::spawn(move || {
+            let _tx = poisoner.tx.lock().unwrap();
+            let _thread = poisoner.thread.lock().unwrap();
+            panic!("poisoning the audio engine locks");
+        })
+        .join();
+        assert!(engine.tx.is_poisoned());
+        assert!(engine.thread.is_poisoned());
+
+        // Without an output device the sink can't open, but the audio thread must
+        // still have been spawned and replied
+        let result = engine.start();
+        assert!(
+            matches!(result, Ok(()) | Err(AudioError::NoOutputDevice(_))),
+            "{result:?}"
+        );
     }
 }
@@ -0,0 +1,82 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rodio::Source;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+/// Ocean wave generator
+/// Low-passed brown noise under a slow swell envelope for the wash-in/wash-out of waves
+pub struct OceanWaves {
+    sample_rate: u32,
+    last_brown: f32,
+    // One-pole low-pass filter state and coefficient
+    lowpass: f32,
+    lowpass_alpha: f32,
+    rng: StdRng,
+    swell_phase: f32,
+}
+
+impl OceanWaves {
+    /// Period of one wave is 1 / SWELL_HZ seconds
+    const SWELL_HZ: f32 = 0.1;
+    /// Low-pass cutoff; keeps the deep rumble and drops the hiss
+    const CUTOFF_HZ: f32 = 500.0;
+
+    pub fn new(sample_rate: u32) -> Self {
+        let lowpass_alpha = 1.0 - (-TAU * Self::CUTOFF_HZ / sample_rate as f32).exp();
+        Self {
+            sample_rate,
+            last_brown: 0.0,
+            lowpass: 0.0,
+            lowpass_alpha,
+            rng: StdRng::from_entropy(),
+            swell_phase: 0.0,
+        }
+    }
+
+    // Generate brown noise sample
+    fn brown_noise_sample(&mut self) -> f32 {
+        let white = self.rng.gen_range(-1.0..1.0);
+        self.last_brown += white * 0.02;
+        self.last_brown = self.last_brown.clamp(-1.0, 1.0);
+        self.last_brown *= 0.9999;
+        self.last_brown
+    }
+}
+
+impl Iterator for OceanWaves {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let brown = self.brown_noise_sample();
+        self.lowpass += self.lowpass_alpha * (brown - self.lowpass);
+
+        // Raised-cosine swell: quiet trough between waves, smooth build to the crest
+        self.swell_phase += TAU * Self::SWELL_HZ / self.sample_rate as f32;
+        if self.swell_phase > TAU {
+            self.swell_phase -= TAU;
+        }
+        let swell = 0.5 - 0.5 * self.swell_phase.cos();
+        let envelope = 0.25 + 0.75 * swell * swell.sqrt();
+
+        Some(self.lowpass * envelope * 0.5)
+    }
+}
+
+impl Source for OceanWaves {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1 // Mono
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
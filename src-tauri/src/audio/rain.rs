@@ -18,9 +18,9 @@ pub struct RainSound {
 }
 
 impl RainSound {
-    pub fn new() -> Self {
+    pub fn new(sample_rate: u32) -> Self {
         Self {
-            sample_rate: 44100,
+            sample_rate,
             last_brown: 0.0,
             x1: 0.0,
             x2: 0.0,
@@ -0,0 +1,75 @@
+use rodio::Source;
+use std::time::Duration;
+
+/// Length of the fade-out that ends a timed source, so it stops without a click.
+const FADE_OUT_MS: u64 = 2_000;
+
+/// Wraps an endless generated `Source` so it ends after a fixed duration, fading out
+/// over the final `FADE_OUT_MS` (or the whole duration, if shorter).
+///
+/// Time is counted in samples, so a paused sink doesn't eat into the duration.
+pub struct TimedSource<S> {
+    inner: S,
+    duration: Duration,
+    /// Samples (across all channels) left before the source ends.
+    remaining: u64,
+    fade_len: u64,
+}
+
+impl<S: Source<Item = f32>> TimedSource<S> {
+    pub fn new(inner: S, duration: Duration) -> Self {
+        let channels = u64::from(inner.channels());
+        let samples_per_sec = u64::from(inner.sample_rate()) * channels;
+        let frames = duration.as_millis() as u64 * u64::from(inner.sample_rate()) / 1000;
+        let remaining = frames * channels;
+        let fade_len = (FADE_OUT_MS * samples_per_sec / 1000).min(remaining);
+        Self {
+            inner,
+            duration,
+            remaining,
+            fade_len,
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for TimedSource<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let sample = self.inner.next()?;
+        self.remaining -= 1;
+
+        let gain = if self.remaining < self.fade_len {
+            self.remaining as f32 / self.fade_len as f32
+        } else {
+            1.0
+        };
+        Some(sample * gain)
+    }
+}
+
+impl<S: Source<Item = f32>> Source for TimedSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        let remaining = self.remaining as usize;
+        Some(
+            self.inner
+                .current_frame_len()
+                .map_or(remaining, |len| len.min(remaining)),
+        )
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+}
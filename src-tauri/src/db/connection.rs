@@ -1,18 +1,25 @@
 use std::{
-    path::PathBuf,
+    future::Future,
+    path::{Path, PathBuf},
     sync::{mpsc, Arc, Mutex},
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, Context, Result};
-use log::{error, info};
-use rusqlite::Connection;
+use log::{error, info, warn};
+use rusqlite::{Connection, OpenFlags};
 use tokio::sync::oneshot;
 
 use super::migrations;
 
 type DbTask = Box<dyn FnOnce(&mut Connection) + Send + 'static>;
 
+/// How long dropping the last `Database` handle waits for the worker to finish its
+/// current task before detaching it, so a long VACUUM can't hold up app exit.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 enum DbCommand {
     Execute(DbTask),
     Shutdown,
@@ -21,6 +28,9 @@ enum DbCommand {
 struct DatabaseInner {
     sender: mpsc::Sender<DbCommand>,
     worker: Mutex<Option<JoinHandle<()>>>,
+    read_only: bool,
+    /// File backing the database; `None` for in-memory databases.
+    path: Option<PathBuf>,
 }
 
 impl Drop for DatabaseInner {
@@ -34,6 +44,19 @@ impl Drop for DatabaseInner {
             if let Err(err) = self.sender.send(DbCommand::Shutdown) {
                 error!("Failed to send shutdown to DB thread: {err}");
             }
+
+            let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+            while !handle.is_finished() && Instant::now() < deadline {
+                thread::sleep(SHUTDOWN_POLL_INTERVAL);
+            }
+
+            if !handle.is_finished() {
+                warn!(
+                    "DB thread still busy after {:?}; detaching it so shutdown can continue",
+                    SHUTDOWN_TIMEOUT
+                );
+                return;
+            }
             if let Err(join_err) = handle.join() {
                 error!("Failed to join DB thread: {join_err:?}");
             }
@@ -41,6 +64,14 @@ impl Drop for DatabaseInner {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum WorkerMode {
+    ReadWrite,
+    InMemory,
+    /// No WAL pragma and no migrations; the connection can't write.
+    ReadOnly,
+}
+
 #[derive(Clone)]
 pub struct Database {
     inner: Arc<DatabaseInner>,
@@ -54,14 +85,62 @@ impl Database {
             })?;
         }
 
+        let path_for_thread = db_path.clone();
+        let database = Self::spawn_worker(
+            move || Connection::open(&path_for_thread),
+            WorkerMode::ReadWrite,
+            Some(db_path.clone()),
+        )?;
+
+        info!("Database initialized at {}", db_path.as_path().display());
+
+        Ok(database)
+    }
+
+    /// Open a private `:memory:` database with migrations applied. Intended for tests.
+    pub fn new_in_memory() -> Result<Self> {
+        // WAL is meaningless for an in-memory database, so don't request it
+        Self::spawn_worker(Connection::open_in_memory, WorkerMode::InMemory, None)
+    }
+
+    /// Open an existing database without migrating or writing to it. Used when the
+    /// file was created by a newer build, so the user can still browse their history.
+    pub fn open_read_only(db_path: PathBuf) -> Result<Self> {
+        let path_for_thread = db_path.clone();
+        let database = Self::spawn_worker(
+            move || Connection::open_with_flags(&path_for_thread, OpenFlags::SQLITE_OPEN_READ_ONLY),
+            WorkerMode::ReadOnly,
+            Some(db_path.clone()),
+        )?;
+
+        info!(
+            "Database opened read-only at {}",
+            db_path.as_path().display()
+        );
+
+        Ok(database)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.inner.read_only
+    }
+
+    /// Path of the database file, or `None` for an in-memory database.
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    fn spawn_worker<F>(open: F, mode: WorkerMode, path: Option<PathBuf>) -> Result<Self>
+    where
+        F: FnOnce() -> rusqlite::Result<Connection> + Send + 'static,
+    {
         let (command_tx, command_rx) = mpsc::channel::<DbCommand>();
         let (ready_tx, ready_rx) = mpsc::channel();
-        let path_for_thread = db_path.clone();
 
         let worker = thread::Builder::new()
             .name("lefocus-db".into())
             .spawn(move || {
-                let mut conn = match Connection::open(&path_for_thread) {
+                let mut conn = match open() {
                     Ok(connection) => connection,
                     Err(err) => {
                         let _ =
@@ -72,15 +151,20 @@ impl Database {
                     }
                 };
 
-                if let Err(err) = conn.pragma_update(None, "journal_mode", "WAL") {
-                    error!("Failed to enable WAL mode: {err}");
+                if mode == WorkerMode::ReadWrite {
+                    if let Err(err) = conn.pragma_update(None, "journal_mode", "WAL") {
+                        error!("Failed to enable WAL mode: {err}");
+                    }
                 }
                 if let Err(err) = conn.pragma_update(None, "foreign_keys", "ON") {
                     error!("Failed to enable foreign keys: {err}");
                 }
 
-                let init_result =
-                    migrations::run_migrations(&mut conn).context("failed to run migrations");
+                let init_result = if mode == WorkerMode::ReadOnly {
+                    Ok(())
+                } else {
+                    migrations::run_migrations(&mut conn).context("failed to run migrations")
+                };
                 if ready_tx.send(init_result).is_err() {
                     error!("DB initialization receiver dropped before ready signal");
                     return;
@@ -95,6 +179,16 @@ impl Database {
                     }
                 }
 
+                // Drop anything queued behind the shutdown; each task's reply sender
+                // goes with it, so its caller gets an error instead of waiting forever
+                let dropped = command_rx
+                    .try_iter()
+                    .filter(|command| matches!(command, DbCommand::Execute(_)))
+                    .count();
+                if dropped > 0 {
+                    warn!("Dropped {dropped} queued DB tasks at shutdown");
+                }
+
                 info!("Database thread shutting down");
             })
             .with_context(|| "failed to spawn database worker thread")?;
@@ -103,37 +197,108 @@ impl Database {
             .recv()
             .context("database worker exited before signaling readiness")??;
 
-        info!("Database initialized at {}", db_path.as_path().display());
-
         Ok(Self {
             inner: Arc::new(DatabaseInner {
                 sender: command_tx,
                 worker: Mutex::new(Some(worker)),
+                read_only: mode == WorkerMode::ReadOnly,
+                path,
             }),
         })
     }
 
+    /// Schema version recorded in the database (`PRAGMA user_version`).
+    pub async fn schema_version(&self) -> Result<i32> {
+        self.execute(|conn| {
+            let version = conn
+                .pragma_query_value(None, "user_version", |row| row.get(0))
+                .context("failed to read user_version pragma")?;
+            Ok(version)
+        })
+        .await
+    }
+
+    /// Cheap integrity probe (`PRAGMA quick_check`). Returns `"ok"` for a healthy
+    /// database, otherwise the first problem SQLite found.
+    pub async fn quick_check(&self) -> Result<String> {
+        self.execute(|conn| {
+            let result = conn
+                .pragma_query_value(None, "quick_check", |row| row.get(0))
+                .context("failed to run quick_check pragma")?;
+            Ok(result)
+        })
+        .await
+    }
+
     pub async fn execute<F, T>(&self, task: F) -> Result<T>
     where
         F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
         T: Send + 'static,
     {
-        let sender = self.inner.sender.clone();
-        let (reply_tx, reply_rx) = oneshot::channel();
+        submit(&self.inner.sender, task)?.await
+    }
+}
 
-        let command = DbCommand::Execute(Box::new(move |conn| {
-            let result = task(conn);
-            if reply_tx.send(result).is_err() {
-                error!("DB caller dropped before receiving result");
-            }
-        }));
+/// Queue `task` on the DB thread. The returned future resolves to the task's result,
+/// or fails if the thread shuts down before running it.
+fn submit<F, T>(
+    sender: &mpsc::Sender<DbCommand>,
+    task: F,
+) -> Result<impl Future<Output = Result<T>>>
+where
+    F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (reply_tx, reply_rx) = oneshot::channel();
 
-        sender
-            .send(command)
-            .map_err(|err| anyhow!("failed to send command to DB thread: {err}"))?;
+    let command = DbCommand::Execute(Box::new(move |conn| {
+        let result = task(conn);
+        if reply_tx.send(result).is_err() {
+            error!("DB caller dropped before receiving result");
+        }
+    }));
 
+    sender
+        .send(command)
+        .map_err(|err| anyhow!("failed to send command to DB thread: {err}"))?;
+
+    Ok(async move {
         reply_rx
             .await
-            .map_err(|_| anyhow!("database thread terminated unexpectedly"))?
+            .map_err(|_| anyhow!("database thread stopped before replying"))?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn drop_waits_for_a_slow_task_and_fails_tasks_queued_behind_shutdown() {
+        let db = Database::new_in_memory().unwrap();
+        let sender = db.inner.sender.clone();
+
+        let (started_tx, started_rx) = mpsc::channel();
+        let slow = submit(&sender, move |_| {
+            started_tx.send(()).unwrap();
+            thread::sleep(Duration::from_millis(500));
+            Ok(())
+        })
+        .unwrap();
+        started_rx.recv().unwrap();
+
+        // Queued while drop below is waiting, so it lands behind the shutdown
+        let late = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            submit(&sender, |_| Ok(())).unwrap()
+        });
+
+        let dropped_at = Instant::now();
+        drop(db);
+        assert!(dropped_at.elapsed() < SHUTDOWN_TIMEOUT);
+
+        slow.await.unwrap();
+        let err = late.join().unwrap().await.unwrap_err();
+        assert_eq!(err.to_string(), "database thread stopped before replying");
     }
 }
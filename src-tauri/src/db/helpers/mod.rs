@@ -3,7 +3,8 @@ use std::convert::TryFrom;
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 
-use crate::db::models::SessionStatus;
+use crate::db::models::{SegmentType, SessionEventType, SessionStatus};
+use crate::{timer::TimerMode, SoundType};
 
 pub fn to_i64(value: u64) -> Result<i64> {
     i64::try_from(value).map_err(|_| anyhow!("value {value} exceeds SQLite INTEGER range"))
@@ -38,3 +39,47 @@ pub fn parse_status(value: &str) -> Result<SessionStatus> {
         other => Err(anyhow!("unknown session status {other}")),
     }
 }
+
+pub fn parse_session_event_type(value: &str) -> Result<SessionEventType> {
+    match value {
+        "started" => Ok(SessionEventType::Started),
+        "resumed" => Ok(SessionEventType::Resumed),
+        "target_reached" => Ok(SessionEventType::TargetReached),
+        "ended" => Ok(SessionEventType::Ended),
+        "auto_ended" => Ok(SessionEventType::AutoEnded),
+        "cancelled" => Ok(SessionEventType::Cancelled),
+        "interrupted" => Ok(SessionEventType::Interrupted),
+        "sensing_suspended" => Ok(SessionEventType::SensingSuspended),
+        "sensing_resumed" => Ok(SessionEventType::SensingResumed),
+        other => Err(anyhow!("unknown session event type {other}")),
+    }
+}
+
+pub fn parse_segment_type(value: &str) -> Result<SegmentType> {
+    match value {
+        "Stable" => Ok(SegmentType::Stable),
+        "Transitioning" => Ok(SegmentType::Transitioning),
+        "Distracted" => Ok(SegmentType::Distracted),
+        other => Err(anyhow!("unknown segment type {other}")),
+    }
+}
+
+pub fn parse_timer_mode(value: &str) -> Result<TimerMode> {
+    match value {
+        "Countdown" => Ok(TimerMode::Countdown),
+        "Stopwatch" => Ok(TimerMode::Stopwatch),
+        "Break" => Ok(TimerMode::Break),
+        other => Err(anyhow!("unknown timer mode {other}")),
+    }
+}
+
+pub fn parse_sound_type(value: &str) -> Result<SoundType> {
+    match value {
+        "Binaural" => Ok(SoundType::Binaural),
+        "BrownNoise" => Ok(SoundType::BrownNoise),
+        "Rain" => Ok(SoundType::Rain),
+        "Ocean" => Ok(SoundType::Ocean),
+        "Campfire" => Ok(SoundType::Campfire),
+        other => Err(anyhow!("unknown sound type {other}")),
+    }
+}
@@ -1,7 +1,28 @@
 use anyhow::{bail, Context, Result};
 use rusqlite::{Connection, Transaction};
 
-const CURRENT_SCHEMA_VERSION: i32 = 11;
+/// Schema version this build migrates databases to.
+pub const CURRENT_SCHEMA_VERSION: i32 = 23;
+
+/// The database was written by a newer build than this one understands (e.g. after a
+/// downgrade). Kept as a distinct type so startup can downcast and degrade gracefully.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaTooNewError {
+    pub database_version: i32,
+    pub supported_version: i32,
+}
+
+impl std::fmt::Display for SchemaTooNewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "database version ({}) is newer than supported schema ({})",
+            self.database_version, self.supported_version
+        )
+    }
+}
+
+impl std::error::Error for SchemaTooNewError {}
 
 pub fn run_migrations(conn: &mut Connection) -> Result<()> {
     let mut version: i32 = conn
@@ -9,11 +30,11 @@ pub fn run_migrations(conn: &mut Connection) -> Result<()> {
         .context("failed to read user_version pragma")?;
 
     if version > CURRENT_SCHEMA_VERSION {
-        bail!(
-            "database version ({}) is newer than supported schema ({})",
-            version,
-            CURRENT_SCHEMA_VERSION
-        );
+        return Err(SchemaTooNewError {
+            database_version: version,
+            supported_version: CURRENT_SCHEMA_VERSION,
+        }
+        .into());
     }
 
     if version == CURRENT_SCHEMA_VERSION {
@@ -95,6 +116,66 @@ fn apply_migration(tx: &Transaction<'_>, version: i32) -> Result<()> {
                 .context("failed to execute schema_v11.sql")?;
             Ok(())
         }
+        12 => {
+            tx.execute_batch(include_str!("schemas/schema_v12.sql"))
+                .context("failed to execute schema_v12.sql")?;
+            Ok(())
+        }
+        13 => {
+            tx.execute_batch(include_str!("schemas/schema_v13.sql"))
+                .context("failed to execute schema_v13.sql")?;
+            Ok(())
+        }
+        14 => {
+            tx.execute_batch(include_str!("schemas/schema_v14.sql"))
+                .context("failed to execute schema_v14.sql")?;
+            Ok(())
+        }
+        15 => {
+            tx.execute_batch(include_str!("schemas/schema_v15.sql"))
+                .context("failed to execute schema_v15.sql")?;
+            Ok(())
+        }
+        16 => {
+            tx.execute_batch(include_str!("schemas/schema_v16.sql"))
+                .context("failed to execute schema_v16.sql")?;
+            Ok(())
+        }
+        17 => {
+            tx.execute_batch(include_str!("schemas/schema_v17.sql"))
+                .context("failed to execute schema_v17.sql")?;
+            Ok(())
+        }
+        18 => {
+            tx.execute_batch(include_str!("schemas/schema_v18.sql"))
+                .context("failed to execute schema_v18.sql")?;
+            Ok(())
+        }
+        19 => {
+            tx.execute_batch(include_str!("schemas/schema_v19.sql"))
+                .context("failed to execute schema_v19.sql")?;
+            Ok(())
+        }
+        20 => {
+            tx.execute_batch(include_str!("schemas/schema_v20.sql"))
+                .context("failed to execute schema_v20.sql")?;
+            Ok(())
+        }
+        21 => {
+            tx.execute_batch(include_str!("schemas/schema_v21.sql"))
+                .context("failed to execute schema_v21.sql")?;
+            Ok(())
+        }
+        22 => {
+            tx.execute_batch(include_str!("schemas/schema_v22.sql"))
+                .context("failed to execute schema_v22.sql")?;
+            Ok(())
+        }
+        23 => {
+            tx.execute_batch(include_str!("schemas/schema_v23.sql"))
+                .context("failed to execute schema_v23.sql")?;
+            Ok(())
+        }
         _ => bail!("unknown migration target version: {version}"),
     }
 }
@@ -3,6 +3,9 @@ pub mod helpers;
 mod migrations;
 pub mod models;
 pub mod repositories;
+#[cfg(test)]
+pub mod test_support;
 
 pub use connection::Database;
+pub use migrations::{SchemaTooNewError, CURRENT_SCHEMA_VERSION};
 pub use models::{ContextReading, Session, SessionInfo, SessionStatus};
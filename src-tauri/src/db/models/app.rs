@@ -10,3 +10,16 @@ pub struct App {
     pub icon_color: Option<String>, // Hex color string like "#AABBCC"
     pub icon_fetched_at: Option<DateTime<Utc>>,
 }
+
+/// An app from the `apps` table with its lifetime usage, for app pickers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownApp {
+    pub bundle_id: String,
+    pub app_name: Option<String>,
+    pub icon_data_url: Option<String>,
+    pub icon_color: Option<String>,
+    /// Total segment time across all sessions
+    pub total_secs: i64,
+    pub session_count: u32,
+}
@@ -0,0 +1,26 @@
+//! Auto-labeling rule data models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Labels a session with `label_id` when `bundle_id` takes up at least
+/// `min_percentage` (0-100) of its segmented time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoLabelRule {
+    pub id: i64,
+    pub bundle_id: String,
+    pub label_id: i64,
+    pub min_percentage: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Input data for creating or updating an auto-labeling rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoLabelRuleInput {
+    pub bundle_id: String,
+    pub label_id: i64,
+    pub min_percentage: f64,
+}
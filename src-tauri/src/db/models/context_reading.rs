@@ -23,3 +23,28 @@ pub struct ContextReading {
     /// Segment ID that this reading belongs to (set after segmentation)
     pub segment_id: Option<String>,
 }
+
+/// One reading in a segment's timeline, without its (potentially large) OCR text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineReading {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub window_title: String,
+    pub phash: Option<String>,
+    pub ocr_word_count: Option<u64>,
+    /// Share of words that changed since the previous reading (0..=1); `None` when
+    /// either reading lacks OCR.
+    pub ocr_text_delta: Option<f64>,
+}
+
+/// A stretch of a session with no readings, where sensing missed captures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureGap {
+    /// Timestamp of the reading before the gap.
+    pub start: DateTime<Utc>,
+    /// Timestamp of the reading after the gap.
+    pub end: DateTime<Utc>,
+    pub gap_secs: f64,
+}
@@ -2,10 +2,13 @@
 //!
 //! See system design documentation:
 //! - `Label`, `LabelInput`: Phase 7 (phase-7-labels.md)
+//! - `LabelDefaults`: timer settings pre-filled when a label is picked
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::{timer::TimerMode, SoundType};
+
 /// Represents a label for categorizing sessions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +20,9 @@ pub struct Label {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub default_target_ms: Option<u64>,
+    pub default_mode: Option<TimerMode>,
+    pub default_sound: Option<SoundType>,
 }
 
 /// Input data for creating or updating a label
@@ -25,4 +31,19 @@ pub struct Label {
 pub struct LabelInput {
     pub name: String,
     pub color: String,
+    #[serde(default)]
+    pub default_target_ms: Option<u64>,
+    #[serde(default)]
+    pub default_mode: Option<TimerMode>,
+    #[serde(default)]
+    pub default_sound: Option<SoundType>,
+}
+
+/// Timer settings to pre-fill when a label is selected; `None` leaves the form as is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelDefaults {
+    pub target_ms: Option<u64>,
+    pub mode: Option<TimerMode>,
+    pub sound: Option<SoundType>,
 }
@@ -1,11 +1,19 @@
 pub mod app;
+pub mod auto_label_rule;
 pub mod context_reading;
 pub mod label;
 pub mod segment;
 pub mod session;
+pub mod session_event;
 
-pub use app::App;
-pub use context_reading::ContextReading;
-pub use label::{Label, LabelInput};
-pub use segment::{Interruption, Segment};
-pub use session::{Session, SessionInfo, SessionStatus, SessionSummary, TopApp};
+pub use app::{App, KnownApp};
+pub use auto_label_rule::{AutoLabelRule, AutoLabelRuleInput};
+pub use context_reading::{CaptureGap, ContextReading, TimelineReading};
+pub use label::{Label, LabelDefaults, LabelInput};
+pub use segment::{Interruption, Segment, SegmentHighlight, SegmentType};
+pub use session::{
+    ActiveMsDiscrepancy, DailyFocus, DailyProgress, Session, SessionComparison,
+    SessionComparisonSide, SessionDiff, SessionFocusStats, SessionInfo, SessionStatus,
+    SessionSummary, SharedAppDelta, TopApp,
+};
+pub use session_event::{SessionEvent, SessionEventType};
@@ -10,6 +10,31 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Classification of a segment's focus quality.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SegmentType {
+    Stable,
+    Transitioning,
+    Distracted,
+}
+
+impl SegmentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentType::Stable => "Stable",
+            SegmentType::Transitioning => "Transitioning",
+            SegmentType::Distracted => "Distracted",
+        }
+    }
+}
+
+impl Default for SegmentType {
+    fn default() -> Self {
+        SegmentType::Stable
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Segment {
@@ -21,13 +46,17 @@ pub struct Segment {
     pub bundle_id: String,
     pub app_name: Option<String>,
     pub window_title: Option<String>,
+    pub segment_type: SegmentType,
     pub confidence: f64,
     pub duration_score: Option<f64>,
     pub stability_score: Option<f64>,
     pub visual_clarity_score: Option<f64>,
     pub ocr_quality_score: Option<f64>,
+    pub reading_activity_score: Option<f64>,
     pub reading_count: i64,
     pub unique_phash_count: Option<i64>,
+    /// Mean OCR word count over readings that ran OCR ("text density"); `None` if none did
+    pub avg_ocr_word_count: Option<f64>,
     pub segment_summary: Option<String>,
     /// App icon data URL from apps table (populated by JOIN)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -35,6 +64,18 @@ pub struct Segment {
     /// App icon dominant color from apps table (populated by JOIN)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_color: Option<String>,
+    /// Free-form tags from `segment_tags` (populated by a secondary query)
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A long segment with the context of the session it belongs to, for highlights.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentHighlight {
+    pub segment: Segment,
+    pub session_started_at: DateTime<Utc>,
+    pub session_label_id: Option<i64>,
 }
 
 impl Segment {
@@ -4,8 +4,9 @@
 //! - `Session`, `SessionStatus`, `SessionInfo`: Phase 2 (phase-2-timer-database.md)
 //! - `TopApp`, `SessionSummary`: Phase 4.5 (phase-4.5-activities-view.md)
 //! - `app_icons` in SessionSummary: Phase 6 (phase-6-ux-apps-table.md)
+//! - `SessionComparison` and its parts: A/B comparison of two sessions
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -37,8 +38,15 @@ pub struct Session {
     pub stopped_at: Option<DateTime<Utc>>,
     pub status: SessionStatus,
     pub target_ms: u64,
+    /// Active time shown to the user; clamped to `target_ms` for countdowns.
     pub active_ms: u64,
+    /// True active time, including any overrun past a countdown's target.
+    pub actual_active_ms: u64,
     pub label_id: Option<i64>,
+    /// Why a `Cancelled` session was abandoned, if the caller said.
+    pub cancel_reason: Option<String>,
+    /// Break sessions are recorded but excluded from session lists.
+    pub is_break: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -52,6 +60,7 @@ pub struct SessionInfo {
     pub status: SessionStatus,
     pub target_ms: u64,
     pub active_ms: u64,
+    pub actual_active_ms: u64,
     pub label_id: Option<i64>,
 }
 
@@ -64,11 +73,49 @@ impl From<Session> for SessionInfo {
             status: session.status,
             target_ms: session.target_ms,
             active_ms: session.active_ms,
+            actual_active_ms: session.actual_active_ms,
             label_id: session.label_id,
         }
     }
 }
 
+/// Focus time for one local day measured against the daily goal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyProgress {
+    /// Local calendar date the progress is for
+    pub date: NaiveDate,
+    pub focused_minutes: u64,
+    /// 0 when no goal is set
+    pub goal_minutes: u32,
+    /// Percent of the goal reached; can exceed 100, and is 0 without a goal
+    pub percentage: f64,
+}
+
+/// Focus time for one local day in a date-range chart (`get_focus_by_day`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyFocus {
+    pub local_date: NaiveDate,
+    pub total_active_ms: u64,
+    pub session_count: u32,
+}
+
+/// A completed session whose stored `active_ms` disagrees with its segments
+/// (`audit_active_ms`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveMsDiscrepancy {
+    pub session_id: String,
+    pub started_at: DateTime<Utc>,
+    pub stored_active_ms: u64,
+    /// Summed segment durations, clamped to the session's target like `active_ms`
+    pub segment_active_ms: u64,
+    pub segment_count: u32,
+    /// Whether `active_ms` was rewritten to `segment_active_ms`
+    pub repaired: bool,
+}
+
 /// Aggregated app duration for a session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -97,4 +144,59 @@ pub struct SessionSummary {
     /// Map of bundle_id -> icon_color (hex string like "#AABBCC")
     /// Dominant color extracted from app icons
     pub app_colors: HashMap<String, Option<String>>,
+    /// Interruptions per active hour exceed the `scattered_interruptions_per_hour` setting
+    pub scattered: bool,
+}
+
+/// Segment-derived focus figures for one session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionFocusStats {
+    /// Percent (0-100) of segmented time spent in stable segments; 0 without segments
+    pub focus_score: f64,
+    pub interruption_count: u32,
+}
+
+/// One session's side of a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionComparisonSide {
+    pub summary: SessionSummary,
+    pub focus: SessionFocusStats,
+}
+
+/// An app used in both compared sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedAppDelta {
+    pub bundle_id: String,
+    pub app_name: Option<String>,
+    pub duration_secs_a: u32,
+    pub duration_secs_b: u32,
+    /// `duration_secs_b - duration_secs_a`
+    pub delta_secs: i64,
+}
+
+/// Differences between two sessions; every delta is B minus A
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDiff {
+    pub active_ms_delta: i64,
+    pub focus_score_delta: f64,
+    pub interruption_count_delta: i64,
+    /// Apps used only in A, longest first
+    pub apps_only_in_a: Vec<TopApp>,
+    /// Apps used only in B, longest first
+    pub apps_only_in_b: Vec<TopApp>,
+    /// Apps used in both, largest absolute change first
+    pub shared_apps: Vec<SharedAppDelta>,
+}
+
+/// Two sessions side by side plus what changed between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionComparison {
+    pub a: SessionComparisonSide,
+    pub b: SessionComparisonSide,
+    pub diff: SessionDiff,
 }
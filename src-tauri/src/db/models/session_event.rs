@@ -0,0 +1,50 @@
+//! Append-only log of session lifecycle events, for answering "why did my session
+//! end?" after the fact without digging through the app log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEventType {
+    Started,
+    /// Picked back up by startup recovery after a crash
+    Resumed,
+    /// A countdown or break ran down to zero
+    TargetReached,
+    Ended,
+    /// Ended by the app rather than the user (e.g. the stopwatch duration cap)
+    AutoEnded,
+    Cancelled,
+    /// Left running by a crash and marked `Interrupted` on startup
+    Interrupted,
+    SensingSuspended,
+    SensingResumed,
+}
+
+impl SessionEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionEventType::Started => "started",
+            SessionEventType::Resumed => "resumed",
+            SessionEventType::TargetReached => "target_reached",
+            SessionEventType::Ended => "ended",
+            SessionEventType::AutoEnded => "auto_ended",
+            SessionEventType::Cancelled => "cancelled",
+            SessionEventType::Interrupted => "interrupted",
+            SessionEventType::SensingSuspended => "sensing_suspended",
+            SessionEventType::SensingResumed => "sensing_resumed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEvent {
+    pub id: i64,
+    pub session_id: String,
+    pub event_type: SessionEventType,
+    /// Free-form context, e.g. the cancel reason or the mode a session started in
+    pub detail: Option<String>,
+    pub at: DateTime<Utc>,
+}
@@ -7,7 +7,11 @@
 //! - Other repositories (e.g., `segments.rs`) may import `AppRepository` from this module.
 //! - **DO NOT** import from `segments`, `sessions`, or `context_readings` here to avoid circular dependencies.
 
-use crate::db::{connection::Database, models::App};
+use crate::db::{
+    connection::Database,
+    models::{App, KnownApp},
+};
+use crate::utils::color::color_or_fallback;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
@@ -91,6 +95,30 @@ impl<'a> AppRepository<'a> {
         Ok(result.unwrap_or(false))
     }
 
+    /// Check if app has a non-empty name
+    pub fn has_name(&self, bundle_id: &str) -> Result<bool> {
+        let result: Option<bool> = self
+            .conn
+            .query_row(
+                "SELECT app_name IS NOT NULL AND app_name != '' FROM apps WHERE bundle_id = ?1",
+                params![bundle_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(result.unwrap_or(false))
+    }
+
+    /// Set the app name, but only if none was recorded
+    pub fn fill_missing_name(&self, bundle_id: &str, app_name: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE apps SET app_name = ?1, updated_at = ?2
+             WHERE bundle_id = ?3 AND (app_name IS NULL OR app_name = '')",
+            params![app_name, now, bundle_id],
+        )?;
+        Ok(())
+    }
+
     /// Check if app has a color
     pub fn has_color(&self, bundle_id: &str) -> Result<bool> {
         let result: Option<bool> = self
@@ -104,6 +132,66 @@ impl<'a> AppRepository<'a> {
         Ok(result.unwrap_or(false))
     }
 
+    /// Get apps that have an icon but no color (fetched before colors were stored)
+    pub fn get_apps_missing_color(&self) -> Result<Vec<App>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, bundle_id, app_name, icon_data_url, icon_color, icon_fetched_at
+             FROM apps
+             WHERE icon_data_url IS NOT NULL
+               AND (icon_color IS NULL OR icon_color = '')
+             ORDER BY bundle_id ASC",
+        )?;
+
+        let apps = stmt
+            .query_map([], |row| {
+                Ok(App {
+                    id: row.get(0)?,
+                    bundle_id: row.get(1)?,
+                    app_name: row.get(2)?,
+                    icon_data_url: row.get(3)?,
+                    icon_color: row.get(4)?,
+                    icon_fetched_at: row
+                        .get::<_, Option<String>>(5)?
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc)),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(apps)
+    }
+
+    /// Every app in the `apps` table with total segment time and session count,
+    /// most used first. Apps never seen in a segment are included with zero usage.
+    pub fn list_known_apps(&self) -> Result<Vec<KnownApp>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.bundle_id, a.app_name, a.icon_data_url, a.icon_color,
+                    COALESCE(SUM(s.duration_secs), 0) AS total_secs,
+                    COUNT(DISTINCT s.session_id) AS session_count
+             FROM apps a
+             LEFT JOIN segments s ON s.bundle_id = a.bundle_id
+             GROUP BY a.bundle_id
+             ORDER BY total_secs DESC, a.bundle_id ASC",
+        )?;
+
+        let apps = stmt
+            .query_map([], |row| {
+                let bundle_id: String = row.get(0)?;
+                let icon_color: Option<String> = row.get(3)?;
+                Ok(KnownApp {
+                    app_name: row.get(1)?,
+                    icon_data_url: row.get(2)?,
+                    icon_color: Some(color_or_fallback(icon_color, &bundle_id)),
+                    total_secs: row.get(4)?,
+                    session_count: row.get(5)?,
+                    bundle_id,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(apps)
+    }
+
     // /// Get apps with missing icons (for background fetch)
     // /// Used for post-migration backfill: after schema_v7 migration backfills apps table
     // /// from existing segments, this finds apps without icons to fetch them.
@@ -158,6 +246,29 @@ impl Database {
         .await
     }
 
+    /// Check if app has a name
+    pub async fn app_has_name(&self, bundle_id: &str) -> Result<bool> {
+        let bundle_id = bundle_id.to_string();
+
+        self.execute(move |conn| {
+            let app_repo = AppRepository::new(conn);
+            app_repo.has_name(&bundle_id)
+        })
+        .await
+    }
+
+    /// Store a resolved app name for an app that has none
+    pub async fn fill_missing_app_name(&self, bundle_id: &str, app_name: &str) -> Result<()> {
+        let bundle_id = bundle_id.to_string();
+        let app_name = app_name.to_string();
+
+        self.execute(move |conn| {
+            let app_repo = AppRepository::new(conn);
+            app_repo.fill_missing_name(&bundle_id, &app_name)
+        })
+        .await
+    }
+
     /// Check if app has a color
     pub async fn app_has_color(&self, bundle_id: &str) -> Result<bool> {
         let bundle_id = bundle_id.to_string();
@@ -187,6 +298,24 @@ impl Database {
         .await
     }
 
+    /// Get apps that have an icon but no color
+    pub async fn get_apps_missing_color(&self) -> Result<Vec<App>> {
+        self.execute(move |conn| {
+            let app_repo = AppRepository::new(conn);
+            app_repo.get_apps_missing_color()
+        })
+        .await
+    }
+
+    /// List every known app with lifetime usage
+    pub async fn list_known_apps(&self) -> Result<Vec<KnownApp>> {
+        self.execute(move |conn| {
+            let app_repo = AppRepository::new(conn);
+            app_repo.list_known_apps()
+        })
+        .await
+    }
+
     // TODO(Phase 7): Uncomment when implementing post-migration icon backfill
     // /// Get apps with missing icons
     // /// Used for post-migration backfill: after schema_v7 migration backfills apps table
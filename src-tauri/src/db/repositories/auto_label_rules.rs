@@ -0,0 +1,191 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, Row};
+
+use crate::db::{
+    connection::Database,
+    helpers::parse_datetime,
+    models::{AutoLabelRule, AutoLabelRuleInput, TopApp},
+    repositories::sessions::ensure_label_assignable,
+};
+
+fn row_to_rule(row: &Row) -> Result<AutoLabelRule> {
+    let created_at: String = row.get("created_at")?;
+    let updated_at: String = row.get("updated_at")?;
+
+    Ok(AutoLabelRule {
+        id: row.get("id")?,
+        bundle_id: row.get("bundle_id")?,
+        label_id: row.get("label_id")?,
+        min_percentage: row.get("min_percentage")?,
+        created_at: parse_datetime(&created_at, "created_at")?,
+        updated_at: parse_datetime(&updated_at, "updated_at")?,
+    })
+}
+
+fn validate_rule_input(conn: &Connection, input: &AutoLabelRuleInput) -> Result<()> {
+    if input.bundle_id.trim().is_empty() {
+        bail!("bundle_id must not be empty");
+    }
+    if !(0.0..=100.0).contains(&input.min_percentage) {
+        bail!(
+            "min_percentage must be between 0 and 100, got {}",
+            input.min_percentage
+        );
+    }
+    ensure_label_assignable(conn, input.label_id)
+}
+
+fn get_rule_by_id(conn: &Connection, rule_id: i64) -> Result<AutoLabelRule> {
+    let mut stmt = conn.prepare(
+        "SELECT id, bundle_id, label_id, min_percentage, created_at, updated_at
+         FROM auto_label_rules
+         WHERE id = ?1",
+    )?;
+    let mut rows = stmt.query(params![rule_id])?;
+    match rows.next()? {
+        Some(row) => row_to_rule(row),
+        None => Err(anyhow!("Auto-label rule {} not found", rule_id)),
+    }
+}
+
+impl Database {
+    /// Create a new auto-labeling rule. Rules are evaluated in creation order.
+    pub async fn create_auto_label_rule(&self, input: AutoLabelRuleInput) -> Result<AutoLabelRule> {
+        self.execute(move |conn| {
+            validate_rule_input(conn, &input)?;
+
+            let now = Utc::now().to_rfc3339();
+            conn.execute(
+                "INSERT INTO auto_label_rules (bundle_id, label_id, min_percentage, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![input.bundle_id, input.label_id, input.min_percentage, now, now],
+            )?;
+
+            get_rule_by_id(conn, conn.last_insert_rowid())
+        })
+        .await
+    }
+
+    /// Get all auto-labeling rules in evaluation order
+    pub async fn get_auto_label_rules(&self) -> Result<Vec<AutoLabelRule>> {
+        self.execute(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, bundle_id, label_id, min_percentage, created_at, updated_at
+                 FROM auto_label_rules
+                 ORDER BY id ASC",
+            )?;
+
+            let mut rows = stmt.query([])?;
+            let mut rules = Vec::new();
+            while let Some(row) = rows.next()? {
+                rules.push(row_to_rule(row)?);
+            }
+
+            Ok(rules)
+        })
+        .await
+    }
+
+    /// Replace the fields of an existing auto-labeling rule
+    pub async fn update_auto_label_rule(
+        &self,
+        rule_id: i64,
+        input: AutoLabelRuleInput,
+    ) -> Result<AutoLabelRule> {
+        self.execute(move |conn| {
+            validate_rule_input(conn, &input)?;
+
+            let rows_affected = conn.execute(
+                "UPDATE auto_label_rules
+                 SET bundle_id = ?1,
+                     label_id = ?2,
+                     min_percentage = ?3,
+                     updated_at = ?4
+                 WHERE id = ?5",
+                params![
+                    input.bundle_id,
+                    input.label_id,
+                    input.min_percentage,
+                    Utc::now().to_rfc3339(),
+                    rule_id
+                ],
+            )?;
+
+            if rows_affected == 0 {
+                return Err(anyhow!("Auto-label rule {} not found", rule_id));
+            }
+
+            get_rule_by_id(conn, rule_id)
+        })
+        .await
+    }
+
+    pub async fn delete_auto_label_rule(&self, rule_id: i64) -> Result<()> {
+        self.execute(move |conn| {
+            let rows_affected = conn.execute(
+                "DELETE FROM auto_label_rules WHERE id = ?1",
+                params![rule_id],
+            )?;
+
+            if rows_affected == 0 {
+                return Err(anyhow!("Auto-label rule {} not found", rule_id));
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Apply the first rule matched by `top_apps` to an unlabeled session.
+    ///
+    /// Sessions that already have a label are left untouched. Returns the label id that
+    /// was applied, if any.
+    pub async fn apply_auto_label_rules(
+        &self,
+        session_id: &str,
+        top_apps: Vec<TopApp>,
+    ) -> Result<Option<i64>> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT r.bundle_id, r.label_id, r.min_percentage
+                 FROM auto_label_rules r
+                 JOIN labels l ON l.id = r.label_id
+                 WHERE l.deleted_at IS NULL
+                 ORDER BY r.id ASC",
+            )?;
+            let rules = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, f64>(2)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            let matched = rules.into_iter().find(|(bundle_id, _, min_percentage)| {
+                top_apps
+                    .iter()
+                    .any(|app| &app.bundle_id == bundle_id && app.percentage >= *min_percentage)
+            });
+
+            let Some((_, label_id, _)) = matched else {
+                return Ok(None);
+            };
+
+            // Never override a label the user already chose
+            let rows_affected = conn.execute(
+                "UPDATE sessions
+                 SET label_id = ?1,
+                     updated_at = ?2
+                 WHERE id = ?3 AND label_id IS NULL",
+                params![label_id, Utc::now().to_rfc3339(), session_id],
+            )?;
+
+            Ok((rows_affected > 0).then_some(label_id))
+        })
+        .await
+    }
+}
@@ -1,57 +1,162 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::params;
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use serde_json::{from_str, to_string};
 
 use crate::db::{
     connection::Database,
     helpers::{parse_datetime, to_i64, to_u64},
-    models::ContextReading,
+    models::{CaptureGap, ContextReading, TimelineReading},
 };
 use crate::macos_bridge::{WindowBounds, WindowMetadata};
 
+/// A gap between readings counts as missing data once it exceeds this many intervals.
+const CAPTURE_GAP_INTERVALS: u64 = 2;
+
+const INSERT_READING_SQL: &str = "INSERT INTO context_readings (
+        session_id,
+        timestamp,
+        window_id,
+        bundle_id,
+        window_title,
+        owner_name,
+        bounds_json,
+        phash,
+        ocr_text,
+        ocr_confidence,
+        ocr_word_count,
+        segment_id
+    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)";
+
+fn insert_reading(stmt: &mut rusqlite::Statement<'_>, record: &ContextReading) -> Result<()> {
+    let window_id = to_i64(u64::from(record.window_metadata.window_id))?;
+    let bounds_json =
+        to_string(&record.window_metadata.bounds).context("failed to serialize window bounds")?;
+    let ocr_word_count = match record.ocr_word_count {
+        Some(count) => Some(to_i64(count)?),
+        None => None,
+    };
+
+    stmt.execute(params![
+        record.session_id,
+        record.timestamp.to_rfc3339(),
+        window_id,
+        record.window_metadata.bundle_id,
+        record.window_metadata.title,
+        record.window_metadata.owner_name,
+        bounds_json,
+        record.phash,
+        record.ocr_text,
+        record.ocr_confidence,
+        ocr_word_count,
+        record.segment_id,
+    ])?;
+    Ok(())
+}
+
+const SELECT_READING_COLUMNS: &str = "id, session_id, timestamp, window_id, bundle_id, \
+    window_title, owner_name, bounds_json, phash, ocr_text, ocr_confidence, ocr_word_count, \
+    segment_id";
+
+/// Map a row selected with `SELECT_READING_COLUMNS` to a `ContextReading`.
+fn row_to_reading(row: &Row) -> rusqlite::Result<ContextReading> {
+    let id: Option<i64> = row.get(0)?;
+    let session_id: String = row.get(1)?;
+    let timestamp_str: String = row.get(2)?;
+    let window_id: i64 = row.get(3)?;
+    let bundle_id: String = row.get(4)?;
+    let window_title: String = row.get(5)?;
+    let owner_name: String = row.get(6)?;
+    let bounds_json: String = row.get(7)?;
+    let phash: Option<String> = row.get(8)?;
+    let ocr_text: Option<String> = row.get(9)?;
+    let ocr_confidence: Option<f64> = row.get(10)?;
+    let ocr_word_count: Option<i64> = row.get(11)?;
+    let segment_id: Option<String> = row.get(12)?;
+
+    let timestamp = parse_datetime(&timestamp_str, "timestamp").map_err(|e| {
+        rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            e.to_string(),
+        )))
+    })?;
+    let window_id_u32 = to_u64(window_id, "window_id").map_err(|e| {
+        rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            e.to_string(),
+        )))
+    })? as u32;
+    let bounds: WindowBounds = from_str(&bounds_json).map_err(|e| {
+        rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            e.to_string(),
+        )))
+    })?;
+
+    let window_metadata = WindowMetadata {
+        window_id: window_id_u32,
+        bundle_id,
+        title: window_title,
+        owner_name,
+        bounds,
+    };
+
+    Ok(ContextReading {
+        id,
+        session_id,
+        timestamp,
+        window_metadata,
+        phash,
+        ocr_text,
+        ocr_confidence,
+        ocr_word_count: ocr_word_count.map(|c| c as u64),
+        segment_id,
+    })
+}
+
+/// Readings assigned to a segment, oldest first.
+pub(crate) fn readings_for_segment(
+    conn: &Connection,
+    segment_id: &str,
+) -> rusqlite::Result<Vec<ContextReading>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SELECT_READING_COLUMNS}
+         FROM context_readings
+         WHERE segment_id = ?1
+         ORDER BY timestamp ASC"
+    ))?;
+    let readings = stmt
+        .query_map(params![segment_id], row_to_reading)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(readings)
+}
+
 impl Database {
     pub async fn insert_context_reading(&self, reading: &ContextReading) -> Result<()> {
         let record = reading.clone();
         self.execute(move |conn| {
-            let window_id = to_i64(u64::from(record.window_metadata.window_id))?;
-            let bounds_json = to_string(&record.window_metadata.bounds)
-                .context("failed to serialize window bounds")?;
-            let ocr_word_count = match record.ocr_word_count {
-                Some(count) => Some(to_i64(count)?),
-                None => None,
-            };
-
-            conn.execute(
-                "INSERT INTO context_readings (
-                    session_id,
-                    timestamp,
-                    window_id,
-                    bundle_id,
-                    window_title,
-                    owner_name,
-                    bounds_json,
-                    phash,
-                    ocr_text,
-                    ocr_confidence,
-                    ocr_word_count,
-                    segment_id
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-                params![
-                    record.session_id,
-                    record.timestamp.to_rfc3339(),
-                    window_id,
-                    record.window_metadata.bundle_id,
-                    record.window_metadata.title,
-                    record.window_metadata.owner_name,
-                    bounds_json,
-                    record.phash,
-                    record.ocr_text,
-                    record.ocr_confidence,
-                    ocr_word_count,
-                    record.segment_id,
-                ],
-            )?;
+            let mut stmt = conn.prepare_cached(INSERT_READING_SQL)?;
+            insert_reading(&mut stmt, &record)
+        })
+        .await
+    }
+
+    /// Insert several readings in one transaction with a single prepared statement.
+    pub async fn insert_context_readings_batch(&self, readings: &[ContextReading]) -> Result<()> {
+        if readings.is_empty() {
+            return Ok(());
+        }
+
+        let records = readings.to_vec();
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+            {
+                let mut stmt = tx.prepare_cached(INSERT_READING_SQL)?;
+                for record in &records {
+                    insert_reading(&mut stmt, record)?;
+                }
+            }
+            tx.commit()?;
             Ok(())
         })
         .await
@@ -63,80 +168,14 @@ impl Database {
     ) -> Result<Vec<ContextReading>> {
         let session_id = session_id.to_string();
         self.execute(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT 
-                    id,
-                    session_id,
-                    timestamp,
-                    window_id,
-                    bundle_id,
-                    window_title,
-                    owner_name,
-                    bounds_json,
-                    phash,
-                    ocr_text,
-                    ocr_confidence,
-                    ocr_word_count,
-                    segment_id
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {SELECT_READING_COLUMNS}
                 FROM context_readings
                 WHERE session_id = ?1
-                ORDER BY timestamp ASC",
-            )?;
-
-            let readings_iter = stmt.query_map(params![session_id], |row| {
-                let id: Option<i64> = row.get(0)?;
-                let session_id: String = row.get(1)?;
-                let timestamp_str: String = row.get(2)?;
-                let window_id: i64 = row.get(3)?;
-                let bundle_id: String = row.get(4)?;
-                let window_title: String = row.get(5)?;
-                let owner_name: String = row.get(6)?;
-                let bounds_json: String = row.get(7)?;
-                let phash: Option<String> = row.get(8)?;
-                let ocr_text: Option<String> = row.get(9)?;
-                let ocr_confidence: Option<f64> = row.get(10)?;
-                let ocr_word_count: Option<i64> = row.get(11)?;
-                let segment_id: Option<String> = row.get(12)?;
-
-                let timestamp = parse_datetime(&timestamp_str, "timestamp").map_err(|e| {
-                    rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        e.to_string(),
-                    )))
-                })?;
-                let window_id_u32 = to_u64(window_id, "window_id").map_err(|e| {
-                    rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        e.to_string(),
-                    )))
-                })? as u32;
-                let bounds: WindowBounds = from_str(&bounds_json).map_err(|e| {
-                    rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        e.to_string(),
-                    )))
-                })?;
-
-                let window_metadata = WindowMetadata {
-                    window_id: window_id_u32,
-                    bundle_id,
-                    title: window_title,
-                    owner_name,
-                    bounds,
-                };
+                ORDER BY timestamp ASC"
+            ))?;
 
-                Ok(ContextReading {
-                    id,
-                    session_id,
-                    timestamp,
-                    window_metadata,
-                    phash,
-                    ocr_text,
-                    ocr_confidence,
-                    ocr_word_count: ocr_word_count.map(|c| c as u64),
-                    segment_id,
-                })
-            })?;
+            let readings_iter = stmt.query_map(params![session_id], row_to_reading)?;
 
             let mut readings = Vec::new();
             for reading_result in readings_iter {
@@ -148,6 +187,78 @@ impl Database {
         .await
     }
 
+    /// Full OCR text of one reading, for inspecting a single capture without
+    /// loading every reading's text. `None` when the reading skipped OCR.
+    pub async fn get_reading_ocr_text(&self, reading_id: i64) -> Result<Option<String>> {
+        self.execute(move |conn| {
+            let ocr_text: Option<Option<String>> = conn
+                .query_row(
+                    "SELECT ocr_text FROM context_readings WHERE id = ?1",
+                    params![reading_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            ocr_text.ok_or_else(|| anyhow!("Reading {} not found", reading_id))
+        })
+        .await
+    }
+
+    /// Store `(reading_id, ocr_text_delta)` pairs computed after a session ends.
+    pub async fn update_ocr_text_deltas(&self, deltas: &[(i64, f64)]) -> Result<()> {
+        if deltas.is_empty() {
+            return Ok(());
+        }
+
+        let deltas = deltas.to_vec();
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+            {
+                let mut stmt =
+                    tx.prepare("UPDATE context_readings SET ocr_text_delta = ?1 WHERE id = ?2")?;
+                for (reading_id, delta) in &deltas {
+                    stmt.execute(params![delta, reading_id])?;
+                }
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Readings in a segment, oldest first, with visual and textual change markers.
+    pub async fn get_segment_timeline(&self, segment_id: &str) -> Result<Vec<TimelineReading>> {
+        let segment_id = segment_id.to_string();
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, timestamp, window_title, phash, ocr_word_count, ocr_text_delta
+                FROM context_readings
+                WHERE segment_id = ?1
+                ORDER BY timestamp ASC",
+            )?;
+
+            let mut rows = stmt.query(params![segment_id])?;
+            let mut timeline = Vec::new();
+            while let Some(row) = rows.next()? {
+                let timestamp: String = row.get(1)?;
+                let ocr_word_count = match row.get::<_, Option<i64>>(4)? {
+                    Some(count) => Some(to_u64(count, "ocr_word_count")?),
+                    None => None,
+                };
+                timeline.push(TimelineReading {
+                    id: row.get(0)?,
+                    timestamp: parse_datetime(&timestamp, "timestamp")?,
+                    window_title: row.get(2)?,
+                    phash: row.get(3)?,
+                    ocr_word_count,
+                    ocr_text_delta: row.get(5)?,
+                });
+            }
+
+            Ok(timeline)
+        })
+        .await
+    }
+
     /// Update context_readings with their corresponding segment_id based on time range.
     /// Readings are matched to segments if their timestamp falls within [segment.start_time, segment.end_time].
     pub async fn update_readings_with_segment_ids(
@@ -260,4 +371,86 @@ impl Database {
         })
         .await
     }
+
+    /// Distinct window titles for an app across every recorded session, with estimated
+    /// durations, longest first.
+    pub async fn get_window_titles_for_app_all_time(
+        &self,
+        bundle_id: &str,
+        limit: u32,
+    ) -> Result<Vec<(String, i64)>> {
+        const READING_INTERVAL_SECS: i64 = 5;
+        let bundle_id = bundle_id.to_string();
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT window_title, COUNT(*) as reading_count
+                FROM context_readings
+                WHERE bundle_id = ?1
+                AND window_title IS NOT NULL
+                AND window_title != ''
+                GROUP BY window_title
+                ORDER BY reading_count DESC
+                LIMIT ?2",
+            )?;
+
+            let titles_iter = stmt.query_map(params![bundle_id, limit], |row| {
+                let title: String = row.get(0)?;
+                let count: i64 = row.get(1)?;
+                let duration_secs = count * READING_INTERVAL_SECS;
+                Ok((title, duration_secs))
+            })?;
+
+            let mut titles = Vec::new();
+            for title_result in titles_iter {
+                titles.push(title_result?);
+            }
+
+            Ok(titles)
+        })
+        .await
+    }
+
+    /// Intervals between consecutive readings that exceed `CAPTURE_GAP_INTERVALS` times
+    /// the expected capture interval, in timestamp order.
+    pub async fn get_capture_gaps(
+        &self,
+        session_id: &str,
+        expected_interval_secs: u64,
+    ) -> Result<Vec<CaptureGap>> {
+        let session_id = session_id.to_string();
+        let threshold_ms =
+            i64::try_from(expected_interval_secs.saturating_mul(CAPTURE_GAP_INTERVALS * 1000))
+                .unwrap_or(i64::MAX);
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT timestamp
+                FROM context_readings
+                WHERE session_id = ?1
+                ORDER BY timestamp ASC",
+            )?;
+
+            let timestamps_iter =
+                stmt.query_map(params![session_id], |row| row.get::<_, String>(0))?;
+
+            let mut gaps = Vec::new();
+            let mut previous: Option<DateTime<Utc>> = None;
+            for timestamp_result in timestamps_iter {
+                let timestamp = parse_datetime(&timestamp_result?, "timestamp")?;
+                if let Some(start) = previous {
+                    let gap_ms = (timestamp - start).num_milliseconds();
+                    if gap_ms > threshold_ms {
+                        gaps.push(CaptureGap {
+                            start,
+                            end: timestamp,
+                            gap_secs: gap_ms as f64 / 1000.0,
+                        });
+                    }
+                }
+                previous = Some(timestamp);
+            }
+
+            Ok(gaps)
+        })
+        .await
+    }
 }
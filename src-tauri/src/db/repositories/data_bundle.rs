@@ -0,0 +1,310 @@
+//! Whole-database export/import as one versioned JSON document.
+//!
+//! Rows are stored as column -> value maps so the bundle tracks the schema
+//! without a hand-written struct per table; the schema version recorded in the
+//! bundle must match `CURRENT_SCHEMA_VERSION` to be imported.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params_from_iter, types::ValueRef, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::db::{connection::Database, CURRENT_SCHEMA_VERSION};
+
+/// Marker identifying a LeFocus data bundle.
+pub const DATA_BUNDLE_FORMAT: &str = "lefocus-data";
+
+/// User tables in foreign-key order: parents are inserted before children.
+const BUNDLE_TABLES: &[&str] = &[
+    "labels",
+    "apps",
+    "auto_label_rules",
+    "sessions",
+    "session_events",
+    "segments",
+    "interruptions",
+    "segment_tags",
+];
+
+/// Raw sensing readings; by far the largest table, so exporting it is opt-in.
+const READINGS_TABLE: &str = "context_readings";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataBundle {
+    pub format: String,
+    pub schema_version: i32,
+    pub exported_at: DateTime<Utc>,
+    pub includes_readings: bool,
+    /// Table name -> rows, each row a column -> value map
+    pub tables: BTreeMap<String, Vec<Map<String, Value>>>,
+}
+
+fn bundle_tables(include_readings: bool) -> Vec<&'static str> {
+    let mut tables = BUNDLE_TABLES.to_vec();
+    if include_readings {
+        tables.push(READINGS_TABLE);
+    }
+    tables
+}
+
+fn export_table(conn: &Connection, table: &str) -> Result<Vec<Map<String, Value>>> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {table}"))?;
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+
+    let mut rows = stmt.query([])?;
+    let mut exported = Vec::new();
+    while let Some(row) = rows.next()? {
+        let mut object = Map::with_capacity(columns.len());
+        for (index, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(index)? {
+                ValueRef::Null => Value::Null,
+                ValueRef::Integer(i) => Value::from(i),
+                ValueRef::Real(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+                ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+                ValueRef::Blob(_) => {
+                    bail!("{table}.{column} holds a BLOB, which bundles do not support")
+                }
+            };
+            object.insert(column.clone(), value);
+        }
+        exported.push(object);
+    }
+    Ok(exported)
+}
+
+fn json_to_sql(value: &Value) -> Result<rusqlite::types::Value> {
+    use rusqlite::types::Value as Sql;
+
+    Ok(match value {
+        Value::Null => Sql::Null,
+        Value::Bool(b) => Sql::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Sql::Integer(i),
+            None => Sql::Real(
+                n.as_f64()
+                    .ok_or_else(|| anyhow!("unsupported number {n}"))?,
+            ),
+        },
+        Value::String(s) => Sql::Text(s.clone()),
+        Value::Array(_) | Value::Object(_) => bail!("nested JSON values are not supported"),
+    })
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>("name"))?
+        .collect::<Result<HashSet<_>, _>>()?;
+    Ok(columns)
+}
+
+fn import_table(conn: &Connection, table: &str, rows: &[Map<String, Value>]) -> Result<()> {
+    let known_columns = table_columns(conn, table)?;
+
+    for (index, row) in rows.iter().enumerate() {
+        let columns: Vec<&String> = row.keys().collect();
+        if let Some(unknown) = columns.iter().find(|c| !known_columns.contains(c.as_str())) {
+            bail!("{table} row {index}: unknown column {unknown}");
+        }
+
+        let values = row
+            .values()
+            .map(json_to_sql)
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("{table} row {index}"))?;
+        let column_list = columns
+            .iter()
+            .map(|c| c.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let placeholders = vec!["?"; columns.len()].join(", ");
+
+        conn.execute(
+            &format!("INSERT INTO {table} ({column_list}) VALUES ({placeholders})"),
+            params_from_iter(values),
+        )
+        .with_context(|| format!("failed to insert {table} row {index}"))?;
+    }
+    Ok(())
+}
+
+impl Database {
+    /// Serialize every user table into one versioned JSON bundle. Context readings
+    /// are only included when `include_readings` is set, since they dominate size.
+    pub async fn export_all_data(&self, include_readings: bool) -> Result<String> {
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+            let mut tables = BTreeMap::new();
+            for table in bundle_tables(include_readings) {
+                tables.insert(table.to_string(), export_table(&tx, table)?);
+            }
+            drop(tx);
+
+            let bundle = DataBundle {
+                format: DATA_BUNDLE_FORMAT.to_string(),
+                schema_version: CURRENT_SCHEMA_VERSION,
+                exported_at: Utc::now(),
+                includes_readings: include_readings,
+                tables,
+            };
+            Ok(serde_json::to_string(&bundle)?)
+        })
+        .await
+    }
+
+    /// Restore a bundle produced by `export_all_data` into an empty database.
+    /// Everything is inserted in one transaction, so a bad bundle changes nothing.
+    pub async fn import_all_data(&self, json: &str) -> Result<()> {
+        let bundle: DataBundle = serde_json::from_str(json).context("invalid data bundle")?;
+        if bundle.format != DATA_BUNDLE_FORMAT {
+            bail!("not a LeFocus data bundle (format {:?})", bundle.format);
+        }
+        if bundle.schema_version != CURRENT_SCHEMA_VERSION {
+            bail!(
+                "bundle schema version {} does not match this build's schema version {}",
+                bundle.schema_version,
+                CURRENT_SCHEMA_VERSION
+            );
+        }
+
+        let tables = bundle_tables(true);
+        if let Some(unknown) = bundle.tables.keys().find(|t| !tables.contains(&t.as_str())) {
+            bail!("bundle contains unknown table {unknown}");
+        }
+
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            for table in &tables {
+                let has_rows: bool = tx.query_row(
+                    &format!("SELECT EXISTS(SELECT 1 FROM {table})"),
+                    [],
+                    |row| row.get(0),
+                )?;
+                if has_rows {
+                    bail!("import needs an empty database, but {table} has rows");
+                }
+            }
+
+            for table in &tables {
+                if let Some(rows) = bundle.tables.get(*table) {
+                    import_table(&tx, table, rows)?;
+                }
+            }
+
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{Interruption, LabelInput, SessionEventType};
+    use crate::db::test_support::{segment, session, t0};
+
+    /// A database with a labelled session, its segments, an interruption, and a tag.
+    async fn populated() -> Database {
+        let db = Database::new_in_memory().unwrap();
+        let label = db
+            .create_label(LabelInput {
+                name: "Deep work".to_string(),
+                color: "#4f46e5".to_string(),
+                default_target_ms: None,
+                default_mode: None,
+                default_sound: None,
+            })
+            .await
+            .unwrap();
+
+        let mut labelled = session("s", t0());
+        labelled.label_id = Some(label.id);
+        db.insert_session(&labelled).await.unwrap();
+        db.log_session_event("s", SessionEventType::Ended, None)
+            .await
+            .unwrap();
+
+        let first = segment("s", "com.a", t0(), 1_800);
+        let second = segment("s", "com.b", first.end_time, 1_800);
+        let interruption = Interruption {
+            id: "i".to_string(),
+            segment_id: first.id.clone(),
+            bundle_id: "com.c".to_string(),
+            app_name: None,
+            timestamp: first.start_time,
+            duration_secs: 5,
+            icon_data_url: None,
+            icon_color: None,
+        };
+        db.insert_segments_and_interruptions("s", &[first.clone(), second], &[interruption])
+            .await
+            .unwrap();
+        db.add_segment_tag(&first.id, "review").await.unwrap();
+        db
+    }
+
+    fn bundle(json: &str) -> DataBundle {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn export_then_import_round_trips_every_table() {
+        let source = populated().await;
+        let exported = source.export_all_data(false).await.unwrap();
+
+        let target = Database::new_in_memory().unwrap();
+        target.import_all_data(&exported).await.unwrap();
+        let reexported = target.export_all_data(false).await.unwrap();
+
+        let (before, after) = (bundle(&exported), bundle(&reexported));
+        for table in [
+            "labels",
+            "sessions",
+            "segments",
+            "interruptions",
+            "segment_tags",
+        ] {
+            assert!(
+                !before.tables[table].is_empty(),
+                "{table} was not populated"
+            );
+        }
+        assert_eq!(before.tables, after.tables);
+
+        let restored = target.get_session("s").await.unwrap().unwrap();
+        assert!(restored.label_id.is_some());
+        let segments = target.get_segments_for_session("s", None).await.unwrap();
+        assert_eq!(segments.len(), 2);
+        let tags = target.get_segment_tags(&segments[0].id).await.unwrap();
+        assert_eq!(tags, ["review"]);
+        let interruptions = target.get_all_interruptions_for_session("s").await.unwrap();
+        assert_eq!(interruptions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn import_rejects_a_non_empty_database() {
+        let source = populated().await;
+        let exported = source.export_all_data(false).await.unwrap();
+
+        let err = source.import_all_data(&exported).await.unwrap_err();
+        assert!(err.to_string().contains("empty database"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn import_rejects_a_mismatched_schema_version() {
+        let exported = populated().await.export_all_data(false).await.unwrap();
+        let mut json: Value = serde_json::from_str(&exported).unwrap();
+        json["schemaVersion"] = Value::from(CURRENT_SCHEMA_VERSION - 1);
+
+        let target = Database::new_in_memory().unwrap();
+        let err = target.import_all_data(&json.to_string()).await.unwrap_err();
+        assert!(err.to_string().contains("schema version"), "{err:#}");
+        let nothing_imported = target.export_all_data(false).await.unwrap();
+        assert!(bundle(&nothing_imported).tables.values().all(Vec::is_empty));
+    }
+}
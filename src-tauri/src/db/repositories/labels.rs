@@ -4,8 +4,10 @@ use rusqlite::{params, Row};
 
 use crate::db::{
     connection::Database,
-    helpers::{parse_datetime, parse_optional_datetime},
-    models::Label,
+    helpers::{
+        parse_datetime, parse_optional_datetime, parse_sound_type, parse_timer_mode, to_i64, to_u64,
+    },
+    models::{Label, LabelDefaults, LabelInput},
 };
 
 const MAX_LABELS: i64 = 9;
@@ -14,6 +16,9 @@ fn row_to_label(row: &Row) -> Result<Label> {
     let created_at: String = row.get("created_at")?;
     let updated_at: String = row.get("updated_at")?;
     let deleted_at: Option<String> = row.get("deleted_at")?;
+    let default_target_ms: Option<i64> = row.get("default_target_ms")?;
+    let default_mode: Option<String> = row.get("default_mode")?;
+    let default_sound: Option<String> = row.get("default_sound")?;
 
     Ok(Label {
         id: row.get("id")?,
@@ -23,13 +28,41 @@ fn row_to_label(row: &Row) -> Result<Label> {
         created_at: parse_datetime(&created_at, "created_at")?,
         updated_at: parse_datetime(&updated_at, "updated_at")?,
         deleted_at: parse_optional_datetime(deleted_at, "deleted_at")?,
+        default_target_ms: default_target_ms
+            .map(|ms| to_u64(ms, "default_target_ms"))
+            .transpose()?,
+        default_mode: default_mode.as_deref().map(parse_timer_mode).transpose()?,
+        default_sound: default_sound.as_deref().map(parse_sound_type).transpose()?,
     })
 }
 
+/// Column values for a label's defaults, rejecting a zero-length target.
+fn defaults_to_sql(
+    defaults: &LabelDefaults,
+) -> Result<(Option<i64>, Option<&'static str>, Option<&'static str>)> {
+    let target_ms = match defaults.target_ms {
+        Some(0) => bail!("Default target must be greater than zero"),
+        Some(ms) => Some(to_i64(ms)?),
+        None => None,
+    };
+    Ok((
+        target_ms,
+        defaults.mode.as_ref().map(|mode| mode.as_str()),
+        defaults.sound.as_ref().map(|sound| sound.as_str()),
+    ))
+}
+
 impl Database {
     /// Create a new label
     /// Returns an error if max labels (9) reached or name is duplicate
-    pub async fn create_label(&self, name: String, color: String) -> Result<Label> {
+    pub async fn create_label(&self, input: LabelInput) -> Result<Label> {
+        let (default_target_ms, default_mode, default_sound) = defaults_to_sql(&LabelDefaults {
+            target_ms: input.default_target_ms,
+            mode: input.default_mode,
+            sound: input.default_sound,
+        })?;
+        let LabelInput { name, color, .. } = input;
+
         self.execute(move |conn| {
             let now = Utc::now();
 
@@ -61,16 +94,27 @@ impl Database {
 
             // Insert the label
             conn.execute(
-                "INSERT INTO labels (name, color, order_index, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![name, color, next_index, now.to_rfc3339(), now.to_rfc3339(),],
+                "INSERT INTO labels (name, color, order_index, created_at, updated_at,
+                                     default_target_ms, default_mode, default_sound)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    name,
+                    color,
+                    next_index,
+                    now.to_rfc3339(),
+                    now.to_rfc3339(),
+                    default_target_ms,
+                    default_mode,
+                    default_sound,
+                ],
             )?;
 
             let label_id = conn.last_insert_rowid();
 
             // Retrieve the created label
             let mut stmt = conn.prepare(
-                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at
+                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at,
+                        default_target_ms, default_mode, default_sound
                  FROM labels
                  WHERE id = ?1",
             )?;
@@ -89,7 +133,8 @@ impl Database {
     pub async fn get_labels(&self) -> Result<Vec<Label>> {
         self.execute(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at
+                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at,
+                        default_target_ms, default_mode, default_sound
                  FROM labels
                  WHERE deleted_at IS NULL
                  ORDER BY order_index ASC",
@@ -125,14 +170,18 @@ impl Database {
     //     .await
     // }
 
-    /// Update a label's name and/or color
+    /// Update a label's name, color, and/or timer defaults. `defaults` replaces all
+    /// three defaults at once, so `None` fields inside it clear them.
     /// Returns an error if name is duplicate
     pub async fn update_label(
         &self,
         label_id: i64,
         name: Option<String>,
         color: Option<String>,
+        defaults: Option<LabelDefaults>,
     ) -> Result<Label> {
+        let defaults = defaults.as_ref().map(defaults_to_sql).transpose()?;
+
         self.execute(move |conn| {
             let now = Utc::now();
 
@@ -148,6 +197,14 @@ impl Database {
                 updates.push("color = ?");
                 params_vec.push(Box::new(c));
             }
+            if let Some((target_ms, mode, sound)) = defaults {
+                updates.push("default_target_ms = ?");
+                params_vec.push(Box::new(target_ms));
+                updates.push("default_mode = ?");
+                params_vec.push(Box::new(mode));
+                updates.push("default_sound = ?");
+                params_vec.push(Box::new(sound));
+            }
 
             if updates.is_empty() {
                 return Err(anyhow!("No fields to update"));
@@ -176,7 +233,8 @@ impl Database {
 
             // Retrieve the updated label
             let mut stmt = conn.prepare(
-                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at
+                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at,
+                        default_target_ms, default_mode, default_sound
                  FROM labels
                  WHERE id = ?1",
             )?;
@@ -191,6 +249,30 @@ impl Database {
         .await
     }
 
+    /// Timer defaults for a label, for pre-filling the timer form when it's picked.
+    pub async fn get_label_defaults(&self, label_id: i64) -> Result<LabelDefaults> {
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, name, color, order_index, created_at, updated_at, deleted_at,
+                        default_target_ms, default_mode, default_sound
+                 FROM labels
+                 WHERE id = ?1 AND deleted_at IS NULL",
+            )?;
+            let mut rows = stmt.query(params![label_id])?;
+            let label = match rows.next()? {
+                Some(row) => row_to_label(row)?,
+                None => return Err(anyhow!("Label not found or already deleted")),
+            };
+
+            Ok(LabelDefaults {
+                target_ms: label.default_target_ms,
+                mode: label.default_mode,
+                sound: label.default_sound,
+            })
+        })
+        .await
+    }
+
     /// Soft delete a label and set all sessions with this label to NULL
     pub async fn soft_delete_label(&self, label_id: i64) -> Result<()> {
         self.execute(move |conn| {
@@ -216,8 +298,41 @@ impl Database {
                 params![label_id],
             )?;
 
+            // Auto-label rules targeting a deleted label can never apply again
+            conn.execute(
+                "DELETE FROM auto_label_rules WHERE label_id = ?1",
+                params![label_id],
+            )?;
+
             Ok(())
         })
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str) -> LabelInput {
+        LabelInput {
+            name: name.to_string(),
+            color: "#4f46e5".to_string(),
+            default_target_ms: None,
+            default_mode: None,
+            default_sound: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_label_enforces_max_count() {
+        let db = Database::new_in_memory().unwrap();
+        for i in 0..MAX_LABELS {
+            let label = db.create_label(input(&format!("label {i}"))).await.unwrap();
+            assert_eq!(label.order_index, i);
+        }
+
+        let err = db.create_label(input("one too many")).await.unwrap_err();
+        assert!(err.to_string().contains("Maximum of 9 labels"));
+    }
+}
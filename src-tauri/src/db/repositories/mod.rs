@@ -1,5 +1,9 @@
 pub mod apps;
+pub mod auto_label_rules;
 pub mod context_readings;
+pub mod data_bundle;
 pub mod labels;
+pub mod segment_tags;
 pub mod segments;
+pub mod session_events;
 pub mod sessions;
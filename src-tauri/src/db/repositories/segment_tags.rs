@@ -0,0 +1,126 @@
+//! Free-form tags on individual segments, alongside the session's single label.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+
+use crate::db::{connection::Database, models::Segment};
+
+/// Longest tag accepted, in characters.
+const MAX_TAG_CHARS: usize = 64;
+
+fn normalize_tag(tag: &str) -> Result<String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        bail!("Tag must not be empty");
+    }
+    if tag.chars().count() > MAX_TAG_CHARS {
+        bail!("Tag must be at most {} characters", MAX_TAG_CHARS);
+    }
+    Ok(tag.to_string())
+}
+
+fn tags_for_segment(conn: &Connection, segment_id: &str) -> Result<Vec<String>> {
+    let mut stmt =
+        conn.prepare("SELECT tag FROM segment_tags WHERE segment_id = ?1 ORDER BY tag ASC")?;
+    let tags = stmt
+        .query_map(params![segment_id], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(tags)
+}
+
+/// Fill in `tags` on each segment with one query.
+pub(crate) fn attach_tags(conn: &Connection, segments: &mut [Segment]) -> rusqlite::Result<()> {
+    if segments.is_empty() {
+        return Ok(());
+    }
+
+    let placeholders = segments.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+    let query = format!(
+        "SELECT segment_id, tag FROM segment_tags WHERE segment_id IN ({}) ORDER BY tag ASC",
+        placeholders
+    );
+    let mut stmt = conn.prepare(&query)?;
+    let params: Vec<&dyn rusqlite::ToSql> = segments
+        .iter()
+        .map(|s| &s.id as &dyn rusqlite::ToSql)
+        .collect();
+
+    let mut tags_by_segment: HashMap<String, Vec<String>> = HashMap::new();
+    let mut rows = stmt.query(params.as_slice())?;
+    while let Some(row) = rows.next()? {
+        tags_by_segment
+            .entry(row.get(0)?)
+            .or_default()
+            .push(row.get(1)?);
+    }
+
+    for segment in segments {
+        segment.tags = tags_by_segment.remove(&segment.id).unwrap_or_default();
+    }
+    Ok(())
+}
+
+/// Copy every tag of `from_ids` onto `to_id`, for segments replaced by merge/split.
+pub(crate) fn copy_tags(conn: &Connection, from_ids: &[&str], to_id: &str) -> rusqlite::Result<()> {
+    for from_id in from_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO segment_tags (segment_id, tag, created_at)
+             SELECT ?1, tag, created_at FROM segment_tags WHERE segment_id = ?2",
+            params![to_id, from_id],
+        )?;
+    }
+    Ok(())
+}
+
+fn ensure_segment_exists(conn: &Connection, segment_id: &str) -> Result<()> {
+    conn.query_row(
+        "SELECT 1 FROM segments WHERE id = ?1",
+        params![segment_id],
+        |_| Ok(()),
+    )
+    .optional()?
+    .ok_or_else(|| anyhow!("Segment {} not found", segment_id))
+}
+
+impl Database {
+    /// Tag a segment. Adding a tag it already has is a no-op. Returns the
+    /// segment's tags afterwards.
+    pub async fn add_segment_tag(&self, segment_id: &str, tag: &str) -> Result<Vec<String>> {
+        let segment_id = segment_id.to_string();
+        let tag = normalize_tag(tag)?;
+        self.execute(move |conn| {
+            ensure_segment_exists(conn, &segment_id)?;
+            conn.execute(
+                "INSERT OR IGNORE INTO segment_tags (segment_id, tag, created_at)
+                 VALUES (?1, ?2, ?3)",
+                params![segment_id, tag, Utc::now().to_rfc3339()],
+            )?;
+            tags_for_segment(conn, &segment_id)
+        })
+        .await
+    }
+
+    /// Remove a tag from a segment. Returns the segment's remaining tags.
+    pub async fn remove_segment_tag(&self, segment_id: &str, tag: &str) -> Result<Vec<String>> {
+        let segment_id = segment_id.to_string();
+        let tag = tag.trim().to_string();
+        self.execute(move |conn| {
+            ensure_segment_exists(conn, &segment_id)?;
+            conn.execute(
+                "DELETE FROM segment_tags WHERE segment_id = ?1 AND tag = ?2",
+                params![segment_id, tag],
+            )?;
+            tags_for_segment(conn, &segment_id)
+        })
+        .await
+    }
+
+    /// Tags on a segment, alphabetically.
+    pub async fn get_segment_tags(&self, segment_id: &str) -> Result<Vec<String>> {
+        let segment_id = segment_id.to_string();
+        self.execute(move |conn| tags_for_segment(conn, &segment_id))
+            .await
+    }
+}
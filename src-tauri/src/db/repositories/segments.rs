@@ -1,17 +1,37 @@
-use anyhow::Result;
-use rusqlite::{params, Row};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use std::collections::HashSet;
 
 use crate::db::{
     connection::Database,
-    helpers::parse_datetime,
-    models::{Interruption, Segment, TopApp},
-    repositories::apps::AppRepository,
+    helpers::{parse_datetime, parse_segment_type},
+    models::{Interruption, Segment, SegmentHighlight, SessionFocusStats, TopApp},
+    repositories::{
+        apps::AppRepository,
+        context_readings::readings_for_segment,
+        segment_tags::{attach_tags, copy_tags},
+    },
 };
+use crate::segmentation::{split, SegmentationConfig};
+use crate::utils::color::color_or_fallback;
+
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// `count` interruptions spread over `active_ms` of active time, per hour.
+pub fn interruptions_per_active_hour(count: usize, active_ms: u64) -> f64 {
+    if active_ms == 0 {
+        return 0.0;
+    }
+    count as f64 / (active_ms as f64 / MS_PER_HOUR)
+}
 
 fn row_to_segment(row: &Row) -> Result<Segment, rusqlite::Error> {
     let start_time_str: String = row.get("start_time")?;
     let end_time_str: String = row.get("end_time")?;
+    let segment_type_str: String = row.get("segment_type")?;
+    let bundle_id: String = row.get("bundle_id")?;
+    let icon_color: Option<String> = row.get("icon_color").ok().flatten();
 
     Ok(Segment {
         id: row.get("id")?,
@@ -29,29 +49,39 @@ fn row_to_segment(row: &Row) -> Result<Segment, rusqlite::Error> {
             )))
         })?,
         duration_secs: row.get("duration_secs")?,
-        bundle_id: row.get("bundle_id")?,
         app_name: row.get("app_name")?,
         window_title: row.get("window_title")?,
+        segment_type: parse_segment_type(&segment_type_str).map_err(|e| {
+            rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e.to_string(),
+            )))
+        })?,
         confidence: row.get("confidence")?,
         duration_score: row.get("duration_score")?,
         stability_score: row.get("stability_score")?,
         visual_clarity_score: row.get("visual_clarity_score")?,
         ocr_quality_score: row.get("ocr_quality_score")?,
+        reading_activity_score: row.get("reading_activity_score")?,
         reading_count: row.get("reading_count")?,
         unique_phash_count: row.get("unique_phash_count")?,
+        avg_ocr_word_count: row.get("avg_ocr_word_count")?,
         segment_summary: row.get("segment_summary")?,
         icon_data_url: row.get("icon_data_url").ok(),
-        icon_color: row.get("icon_color").ok(),
+        icon_color: Some(color_or_fallback(icon_color, &bundle_id)),
+        bundle_id,
+        tags: Vec::new(),
     })
 }
 
 fn row_to_interruption(row: &Row) -> Result<Interruption, rusqlite::Error> {
     let timestamp_str: String = row.get("timestamp")?;
+    let bundle_id: String = row.get("bundle_id")?;
+    let icon_color: Option<String> = row.get("icon_color").ok().flatten();
 
     Ok(Interruption {
         id: row.get("id")?,
         segment_id: row.get("segment_id")?,
-        bundle_id: row.get("bundle_id")?,
         app_name: row.get("app_name")?,
         timestamp: parse_datetime(&timestamp_str, "timestamp").map_err(|e| {
             rusqlite::Error::ToSqlConversionFailure(Box::new(std::io::Error::new(
@@ -61,10 +91,80 @@ fn row_to_interruption(row: &Row) -> Result<Interruption, rusqlite::Error> {
         })?,
         duration_secs: row.get("duration_secs")?,
         icon_data_url: row.get("icon_data_url").ok(),
-        icon_color: row.get("icon_color").ok(),
+        icon_color: Some(color_or_fallback(icon_color, &bundle_id)),
+        bundle_id,
     })
 }
 
+fn insert_segment(conn: &Connection, segment: &Segment) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT INTO segments (
+            id,
+            session_id,
+            start_time,
+            end_time,
+            duration_secs,
+            bundle_id,
+            app_name,
+            window_title,
+            segment_type,
+            confidence,
+            duration_score,
+            stability_score,
+            visual_clarity_score,
+            ocr_quality_score,
+            reading_activity_score,
+            reading_count,
+            unique_phash_count,
+            segment_summary,
+            avg_ocr_word_count
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+        params![
+            segment.id,
+            segment.session_id,
+            segment.start_time.to_rfc3339(),
+            segment.end_time.to_rfc3339(),
+            segment.duration_secs,
+            segment.bundle_id,
+            segment.app_name,
+            segment.window_title,
+            segment.segment_type.as_str(),
+            segment.confidence,
+            segment.duration_score,
+            segment.stability_score,
+            segment.visual_clarity_score,
+            segment.ocr_quality_score,
+            segment.reading_activity_score,
+            segment.reading_count,
+            segment.unique_phash_count,
+            segment.segment_summary,
+            segment.avg_ocr_word_count,
+        ],
+    )
+}
+
+/// Load one segment with its app icon and tags, or `None` if the id is unknown.
+fn load_segment(conn: &Connection, segment_id: &str) -> rusqlite::Result<Option<Segment>> {
+    let segment = conn
+        .query_row(
+            "SELECT segments.*, apps.icon_data_url, apps.icon_color
+             FROM segments
+             LEFT JOIN apps ON segments.bundle_id = apps.bundle_id
+             WHERE segments.id = ?1",
+            params![segment_id],
+            row_to_segment,
+        )
+        .optional()?;
+
+    let Some(segment) = segment else {
+        return Ok(None);
+    };
+    let mut segments = [segment];
+    attach_tags(conn, &mut segments)?;
+    let [segment] = segments;
+    Ok(Some(segment))
+}
+
 /// Spawn a background task to fetch and store app icons for the given bundle IDs.
 /// This is non-blocking - the function returns immediately after spawning the task.
 fn spawn_icon_fetch_task(db: Database, bundle_ids: HashSet<String>) {
@@ -97,7 +197,11 @@ fn spawn_icon_fetch_task(db: Database, bundle_ids: HashSet<String>) {
 
     tokio::spawn(async move {
         for bundle_id in bundle_ids_to_fetch {
-            match crate::macos_bridge::get_app_icon_and_color(&bundle_id) {
+            if let Ok(false) = db.app_has_name(&bundle_id).await {
+                crate::sensing::fill_app_display_name(&db, &bundle_id).await;
+            }
+
+            match crate::sensing::fetch_app_icon_and_color(&bundle_id).await {
                 Some((icon_data_url, icon_color)) => {
                     let color_opt = if icon_color.is_empty() {
                         None
@@ -265,44 +369,7 @@ impl Database {
                 )?;
 
                 // Insert segment
-                tx.execute(
-                    "INSERT INTO segments (
-                        id,
-                        session_id,
-                        start_time,
-                        end_time,
-                        duration_secs,
-                        bundle_id,
-                        app_name,
-                        window_title,
-                        confidence,
-                        duration_score,
-                        stability_score,
-                        visual_clarity_score,
-                        ocr_quality_score,
-                        reading_count,
-                        unique_phash_count,
-                        segment_summary
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-                    params![
-                        segment.id,
-                        segment.session_id,
-                        segment.start_time.to_rfc3339(),
-                        segment.end_time.to_rfc3339(),
-                        segment.duration_secs,
-                        segment.bundle_id,
-                        segment.app_name,
-                        segment.window_title,
-                        segment.confidence,
-                        segment.duration_score,
-                        segment.stability_score,
-                        segment.visual_clarity_score,
-                        segment.ocr_quality_score,
-                        segment.reading_count,
-                        segment.unique_phash_count,
-                        segment.segment_summary,
-                    ],
-                )?;
+                insert_segment(&tx, segment)?;
 
                 // Track apps with missing icons
                 if let Some(app) = app_repo.get_app(&segment.bundle_id)? {
@@ -370,7 +437,12 @@ impl Database {
 
     /// Load all segments for a session, ordered by start_time.
     /// Includes icon data from the apps table via LEFT JOIN.
-    pub async fn get_segments_for_session(&self, session_id: &str) -> Result<Vec<Segment>> {
+    /// When `min_confidence` is set, segments below it are left out.
+    pub async fn get_segments_for_session(
+        &self,
+        session_id: &str,
+        min_confidence: Option<f64>,
+    ) -> Result<Vec<Segment>> {
         let session_id = session_id.to_string();
         self.execute(move |conn| {
             let mut stmt = conn.prepare(
@@ -383,34 +455,415 @@ impl Database {
                     segments.bundle_id,
                     segments.app_name,
                     segments.window_title,
+                    segments.segment_type,
                     segments.confidence,
                     segments.duration_score,
                     segments.stability_score,
                     segments.visual_clarity_score,
                     segments.ocr_quality_score,
+                    segments.reading_activity_score,
                     segments.reading_count,
                     segments.unique_phash_count,
                     segments.segment_summary,
+                    segments.avg_ocr_word_count,
                     apps.icon_data_url,
                     apps.icon_color
                 FROM segments
                 LEFT JOIN apps ON segments.bundle_id = apps.bundle_id
                 WHERE segments.session_id = ?1
+                  AND (?2 IS NULL OR segments.confidence >= ?2)
                 ORDER BY segments.start_time ASC",
             )?;
 
-            let segments_iter = stmt.query_map(params![session_id], |row| row_to_segment(row))?;
+            let segments_iter = stmt.query_map(params![session_id, min_confidence], |row| {
+                row_to_segment(row)
+            })?;
 
             let mut segments = Vec::new();
             for segment_result in segments_iter {
                 segments.push(segment_result?);
             }
+            attach_tags(conn, &mut segments)?;
+
+            Ok(segments)
+        })
+        .await
+    }
+
+    /// Manually re-attribute a segment to another app, e.g. when segmentation
+    /// picked up a helper process. Top-app aggregates read from `segments`, so
+    /// they reflect the change immediately.
+    ///
+    /// The segment's context_readings keep their original bundle_id: they record
+    /// what was actually sampled, while the segment row carries the correction.
+    pub async fn update_segment_app(
+        &self,
+        segment_id: &str,
+        bundle_id: &str,
+        app_name: Option<&str>,
+    ) -> Result<()> {
+        let bundle_id = bundle_id.trim().to_string();
+        if bundle_id.is_empty() {
+            bail!("bundle_id must not be empty");
+        }
+        let segment_id = segment_id.to_string();
+        let app_name = app_name.map(String::from);
+
+        let tx_bundle_id = bundle_id.clone();
+        let missing_icon = self
+            .execute(move |conn| {
+                let tx = conn.transaction()?;
+                let app_repo = AppRepository::new(&tx);
+                app_repo.ensure_app_exists(&tx_bundle_id, app_name.as_deref())?;
+
+                let updated = tx.execute(
+                    "UPDATE segments SET bundle_id = ?1, app_name = ?2 WHERE id = ?3",
+                    params![tx_bundle_id, app_name, segment_id],
+                )?;
+                if updated == 0 {
+                    return Err(anyhow!("Segment {} not found", segment_id));
+                }
+
+                let missing_icon = !app_repo.has_icon(&tx_bundle_id)?;
+                tx.commit()?;
+                Ok(missing_icon)
+            })
+            .await?;
+
+        if missing_icon {
+            spawn_icon_fetch_task(self.clone(), HashSet::from([bundle_id]));
+        }
+
+        Ok(())
+    }
+
+    /// Manually merge two adjacent segments of the same session into one spanning
+    /// both. Identity and scores come from the longer segment, confidence is
+    /// duration-weighted, and reading counts are summed. Interruptions and
+    /// context_readings of both are moved to the merged segment before the
+    /// originals are deleted.
+    pub async fn merge_segments(&self, segment_id_a: &str, segment_id_b: &str) -> Result<Segment> {
+        if segment_id_a == segment_id_b {
+            bail!("Cannot merge a segment with itself");
+        }
+        let segment_id_a = segment_id_a.to_string();
+        let segment_id_b = segment_id_b.to_string();
+
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            let a = load_segment(&tx, &segment_id_a)?
+                .ok_or_else(|| anyhow!("Segment {} not found", segment_id_a))?;
+            let b = load_segment(&tx, &segment_id_b)?
+                .ok_or_else(|| anyhow!("Segment {} not found", segment_id_b))?;
+            if a.session_id != b.session_id {
+                bail!("Segments belong to different sessions");
+            }
+            let (first, second) = if a.start_time <= b.start_time {
+                (a, b)
+            } else {
+                (b, a)
+            };
+
+            // Adjacent means no other segment of the session starts between them
+            let between: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM segments
+                 WHERE session_id = ?1 AND id NOT IN (?2, ?3)
+                   AND start_time >= ?4 AND start_time <= ?5",
+                params![
+                    first.session_id,
+                    first.id,
+                    second.id,
+                    first.start_time.to_rfc3339(),
+                    second.start_time.to_rfc3339(),
+                ],
+                |row| row.get(0),
+            )?;
+            if between > 0 {
+                bail!("Segments {} and {} are not adjacent", first.id, second.id);
+            }
+
+            let start_time = first.start_time;
+            let end_time = first.end_time.max(second.end_time);
+            let duration_secs = (end_time - start_time).num_seconds();
+            let total_weight = (first.duration_secs + second.duration_secs).max(1) as f64;
+            let confidence = (first.confidence * first.duration_secs as f64
+                + second.confidence * second.duration_secs as f64)
+                / total_weight;
+            let reading_count = first.reading_count + second.reading_count;
+            let unique_phash_count = match (first.unique_phash_count, second.unique_phash_count) {
+                (None, None) => None,
+                (x, y) => Some(x.unwrap_or(0) + y.unwrap_or(0)),
+            };
+            // Weighted by reading count; exact only if both ran OCR on the same share
+            let avg_ocr_word_count = match (first.avg_ocr_word_count, second.avg_ocr_word_count) {
+                (Some(a), Some(b)) if reading_count > 0 => Some(
+                    (a * first.reading_count as f64 + b * second.reading_count as f64)
+                        / reading_count as f64,
+                ),
+                (a, b) => a.or(b),
+            };
+
+            let dominant = if second.duration_secs > first.duration_secs {
+                &second
+            } else {
+                &first
+            };
+            let merged = Segment {
+                id: uuid::Uuid::new_v4().to_string(),
+                start_time,
+                end_time,
+                duration_secs,
+                confidence,
+                reading_count,
+                unique_phash_count,
+                avg_ocr_word_count,
+                icon_data_url: None,
+                icon_color: None,
+                ..dominant.clone()
+            };
+            insert_segment(&tx, &merged)?;
+
+            for old_id in [&first.id, &second.id] {
+                tx.execute(
+                    "UPDATE interruptions SET segment_id = ?1 WHERE segment_id = ?2",
+                    params![merged.id, old_id],
+                )?;
+                tx.execute(
+                    "UPDATE context_readings SET segment_id = ?1 WHERE segment_id = ?2",
+                    params![merged.id, old_id],
+                )?;
+            }
+            copy_tags(&tx, &[first.id.as_str(), second.id.as_str()], &merged.id)?;
+            tx.execute(
+                "DELETE FROM segments WHERE id IN (?1, ?2)",
+                params![first.id, second.id],
+            )?;
+
+            let merged = load_segment(&tx, &merged.id)?
+                .ok_or_else(|| anyhow!("Merged segment missing after insert"))?;
+            tx.commit()?;
+            Ok(merged)
+        })
+        .await
+    }
+
+    /// Manually split a segment at `at`, which must lie strictly inside it. The
+    /// two children cover `[start, at)` and `[at, end]` and are rescored from
+    /// their own readings; readings and interruptions move to the child they fall
+    /// in, and the original is deleted. Returns the children in time order.
+    pub async fn split_segment(
+        &self,
+        segment_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(Segment, Segment)> {
+        let segment_id = segment_id.to_string();
+
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            let segment = load_segment(&tx, &segment_id)?
+                .ok_or_else(|| anyhow!("Segment {} not found", segment_id))?;
+            if at <= segment.start_time || at >= segment.end_time {
+                bail!(
+                    "Split time {} is not inside segment {} ({} - {})",
+                    at.to_rfc3339(),
+                    segment_id,
+                    segment.start_time.to_rfc3339(),
+                    segment.end_time.to_rfc3339()
+                );
+            }
+
+            let readings = readings_for_segment(&tx, &segment_id)?;
+            let (first, second) =
+                split::split_segment(&segment, at, &readings, &SegmentationConfig::default());
+            insert_segment(&tx, &first)?;
+            insert_segment(&tx, &second)?;
+
+            for reading in &readings {
+                let child_id = if reading.timestamp < at {
+                    &first.id
+                } else {
+                    &second.id
+                };
+                tx.execute(
+                    "UPDATE context_readings SET segment_id = ?1 WHERE id = ?2",
+                    params![child_id, reading.id],
+                )?;
+            }
+
+            let mut stmt =
+                tx.prepare("SELECT id, timestamp FROM interruptions WHERE segment_id = ?1")?;
+            let interruptions = stmt
+                .query_map(params![segment_id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            drop(stmt);
+            for (interruption_id, timestamp) in interruptions {
+                let timestamp = parse_datetime(&timestamp, "timestamp")?;
+                let child_id = if timestamp < at {
+                    &first.id
+                } else {
+                    &second.id
+                };
+                tx.execute(
+                    "UPDATE interruptions SET segment_id = ?1 WHERE id = ?2",
+                    params![child_id, interruption_id],
+                )?;
+            }
+
+            copy_tags(&tx, &[segment_id.as_str()], &first.id)?;
+            copy_tags(&tx, &[segment_id.as_str()], &second.id)?;
+            tx.execute("DELETE FROM segments WHERE id = ?1", params![segment_id])?;
+
+            let first = load_segment(&tx, &first.id)?
+                .ok_or_else(|| anyhow!("Split segment missing after insert"))?;
+            let second = load_segment(&tx, &second.id)?
+                .ok_or_else(|| anyhow!("Split segment missing after insert"))?;
+            tx.commit()?;
+            Ok((first, second))
+        })
+        .await
+    }
 
+    /// Segments carrying `tag` across all sessions, newest first.
+    pub async fn get_segments_by_tag(&self, tag: &str) -> Result<Vec<Segment>> {
+        let tag = tag.trim().to_string();
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT segments.*, apps.icon_data_url, apps.icon_color
+                 FROM segment_tags
+                 JOIN segments ON segments.id = segment_tags.segment_id
+                 LEFT JOIN apps ON segments.bundle_id = apps.bundle_id
+                 WHERE segment_tags.tag = ?1
+                 ORDER BY segments.start_time DESC",
+            )?;
+            let mut segments = stmt
+                .query_map(params![tag], row_to_segment)?
+                .collect::<Result<Vec<_>, _>>()?;
+            attach_tags(conn, &mut segments)?;
             Ok(segments)
         })
         .await
     }
 
+    /// Longest segments across finished focus sessions, longest first, optionally
+    /// limited to `min_confidence` and segments starting in `[start, end)`. Only
+    /// stable segments count, and System UI is excluded, so idle stretches in front
+    /// of the desktop or a lock screen don't crowd out real work.
+    pub async fn get_longest_segments(
+        &self,
+        limit: usize,
+        min_confidence: Option<f64>,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SegmentHighlight>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let start = start.map(|t| t.to_rfc3339());
+        let end = end.map(|t| t.to_rfc3339());
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT segments.*, apps.icon_data_url, apps.icon_color,
+                        sessions.started_at AS session_started_at,
+                        sessions.label_id AS session_label_id
+                 FROM segments
+                 JOIN sessions ON sessions.id = segments.session_id
+                 LEFT JOIN apps ON segments.bundle_id = apps.bundle_id
+                 WHERE segments.segment_type = 'Stable'
+                   AND segments.bundle_id != 'com.apple.system'
+                   AND sessions.status IN ('Completed', 'Interrupted')
+                   AND sessions.is_break = 0
+                   AND (?1 IS NULL OR segments.confidence >= ?1)
+                   AND (?2 IS NULL OR segments.start_time >= ?2)
+                   AND (?3 IS NULL OR segments.start_time < ?3)
+                 ORDER BY segments.duration_secs DESC, segments.start_time DESC
+                 LIMIT ?4",
+            )?;
+
+            let mut rows = stmt.query(params![min_confidence, start, end, limit])?;
+            let mut segments = Vec::new();
+            let mut sessions = Vec::new();
+            while let Some(row) = rows.next()? {
+                let started_at: String = row.get("session_started_at")?;
+                segments.push(row_to_segment(row)?);
+                sessions.push((
+                    parse_datetime(&started_at, "session_started_at")?,
+                    row.get::<_, Option<i64>>("session_label_id")?,
+                ));
+            }
+            attach_tags(conn, &mut segments)?;
+
+            Ok(segments
+                .into_iter()
+                .zip(sessions)
+                .map(
+                    |(segment, (session_started_at, session_label_id))| SegmentHighlight {
+                        segment,
+                        session_started_at,
+                        session_label_id,
+                    },
+                )
+                .collect())
+        })
+        .await
+    }
+
+    /// Total seconds of a session's segments that fall below `min_confidence`,
+    /// i.e. the time `get_segments_for_session` hides at that threshold.
+    pub async fn get_low_confidence_duration(
+        &self,
+        session_id: &str,
+        min_confidence: f64,
+    ) -> Result<i64> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            let total: i64 = conn.query_row(
+                "SELECT COALESCE(SUM(duration_secs), 0)
+                 FROM segments
+                 WHERE session_id = ?1 AND confidence < ?2",
+                params![session_id, min_confidence],
+                |row| row.get(0),
+            )?;
+            Ok(total)
+        })
+        .await
+    }
+
+    /// Focus score and interruption count for a session, derived from its segments.
+    pub async fn get_session_focus_stats(&self, session_id: &str) -> Result<SessionFocusStats> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            let (total_secs, stable_secs): (i64, i64) = conn.query_row(
+                "SELECT COALESCE(SUM(duration_secs), 0),
+                        COALESCE(SUM(CASE WHEN segment_type = 'Stable' THEN duration_secs END), 0)
+                 FROM segments
+                 WHERE session_id = ?1",
+                params![session_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            let interruption_count: i64 = conn.query_row(
+                "SELECT COUNT(*)
+                 FROM interruptions
+                 JOIN segments ON interruptions.segment_id = segments.id
+                 WHERE segments.session_id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )?;
+
+            let focus_score = if total_secs > 0 {
+                stable_secs as f64 * 100.0 / total_secs as f64
+            } else {
+                0.0
+            };
+            Ok(SessionFocusStats {
+                focus_score,
+                interruption_count: interruption_count as u32,
+            })
+        })
+        .await
+    }
+
     /// Get interruptions for a specific segment.
     /// Includes icon data from the apps table via LEFT JOIN.
     pub async fn get_interruptions_for_segment(
@@ -448,18 +901,71 @@ impl Database {
         .await
     }
 
+    /// Interruptions per hour of active time in a session; 0 for sessions with no
+    /// active time.
+    pub async fn get_session_interruption_rate(&self, session_id: &str) -> Result<f64> {
+        let session = self
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session {} not found", session_id))?;
+        let interruptions = self.get_all_interruptions_for_session(session_id).await?;
+        Ok(interruptions_per_active_hour(interruptions.len(), session.active_ms))
+    }
+
+    /// Get every interruption in a session across all of its segments, in time order.
+    pub async fn get_all_interruptions_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<Interruption>> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT
+                    interruptions.id,
+                    interruptions.segment_id,
+                    interruptions.bundle_id,
+                    interruptions.app_name,
+                    interruptions.timestamp,
+                    interruptions.duration_secs,
+                    apps.icon_data_url,
+                    apps.icon_color
+                FROM interruptions
+                JOIN segments ON interruptions.segment_id = segments.id
+                LEFT JOIN apps ON interruptions.bundle_id = apps.bundle_id
+                WHERE segments.session_id = ?1
+                ORDER BY interruptions.timestamp ASC",
+            )?;
+
+            let interruptions_iter =
+                stmt.query_map(params![session_id], |row| row_to_interruption(row))?;
+
+            let mut interruptions = Vec::new();
+            for interruption_result in interruptions_iter {
+                interruptions.push(interruption_result?);
+            }
+
+            Ok(interruptions)
+        })
+        .await
+    }
+
     /// Get top N apps for a session, aggregated by bundle_id with durations and percentages.
+    /// When `exclude_system_ui` is set, the synthetic `com.apple.system` bundle is left out of
+    /// both the ranking and the total used for percentages (readings/segments stay stored).
     pub async fn get_top_apps_for_session(
         &self,
         session_id: &str,
         limit: usize,
+        exclude_system_ui: bool,
     ) -> Result<Vec<TopApp>> {
         let session_id = session_id.to_string();
         self.execute(move |conn| {
             // First check if there are any segments
             let total_duration: i64 = conn.query_row(
-                "SELECT COALESCE(SUM(duration_secs), 0) FROM segments WHERE session_id = ?1",
-                params![&session_id],
+                "SELECT COALESCE(SUM(duration_secs), 0) FROM segments
+                 WHERE session_id = ?1
+                 AND (?2 = 0 OR bundle_id != 'com.apple.system')",
+                params![&session_id, exclude_system_ui],
                 |row| row.get(0),
             )?;
 
@@ -476,20 +982,23 @@ impl Database {
                     (SUM(duration_secs) * 100.0 / ?2) as percentage
                  FROM segments
                  WHERE session_id = ?1
+                 AND (?4 = 0 OR bundle_id != 'com.apple.system')
                  GROUP BY bundle_id
                  ORDER BY total_duration DESC
                  LIMIT ?3",
             )?;
 
-            let apps_iter =
-                stmt.query_map(params![&session_id, total_duration, limit as i64], |row| {
+            let apps_iter = stmt.query_map(
+                params![&session_id, total_duration, limit as i64, exclude_system_ui],
+                |row| {
                     Ok(TopApp {
                         bundle_id: row.get("bundle_id")?,
                         app_name: row.get("app_name")?,
                         duration_secs: row.get::<_, i64>("total_duration")? as u32,
                         percentage: row.get("percentage")?,
                     })
-                })?;
+                },
+            )?;
 
             let mut apps = Vec::new();
             for app_result in apps_iter {
@@ -501,3 +1010,167 @@ impl Database {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::SegmentType;
+    use crate::db::test_support::{reading, segment, session, t0};
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn segment_type_round_trips() {
+        let db = Database::new_in_memory().unwrap();
+        db.insert_session(&session("s", t0())).await.unwrap();
+        let types = [
+            SegmentType::Stable,
+            SegmentType::Transitioning,
+            SegmentType::Distracted,
+        ];
+        let segments: Vec<Segment> = types
+            .iter()
+            .enumerate()
+            .map(|(i, segment_type)| Segment {
+                segment_type: *segment_type,
+                ..segment("s", "com.a", t0() + Duration::seconds(i as i64 * 60), 60)
+            })
+            .collect();
+        db.insert_segments_and_interruptions("s", &segments, &[])
+            .await
+            .unwrap();
+
+        let stored = db.get_segments_for_session("s", None).await.unwrap();
+        let stored_types: Vec<SegmentType> = stored.iter().map(|s| s.segment_type).collect();
+        assert_eq!(stored_types, types);
+        assert_eq!(
+            parse_segment_type("Distracted").unwrap(),
+            SegmentType::Distracted
+        );
+        assert!(parse_segment_type("distracted").is_err());
+    }
+
+    #[tokio::test]
+    async fn top_apps_percentages_sum_durations_per_app() {
+        let db = Database::new_in_memory().unwrap();
+        db.insert_session(&session("s", t0())).await.unwrap();
+        let start = t0();
+        let segments = vec![
+            segment("s", "com.a", start, 300),
+            segment("s", "com.b", start + Duration::seconds(300), 100),
+            segment("s", "com.a", start + Duration::seconds(400), 100),
+            segment("s", "com.apple.system", start + Duration::seconds(500), 500),
+        ];
+        db.insert_segments_and_interruptions("s", &segments, &[])
+            .await
+            .unwrap();
+
+        let apps = db.get_top_apps_for_session("s", 10, true).await.unwrap();
+        assert_eq!(apps.len(), 2);
+        assert_eq!(apps[0].bundle_id, "com.a");
+        assert_eq!(apps[0].duration_secs, 400);
+        assert!((apps[0].percentage - 80.0).abs() < 1e-9);
+        assert_eq!(apps[1].bundle_id, "com.b");
+        assert!((apps[1].percentage - 20.0).abs() < 1e-9);
+
+        // Counting system UI widens the total, so every share shrinks
+        let apps = db.get_top_apps_for_session("s", 1, false).await.unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].bundle_id, "com.apple.system");
+        assert!((apps[0].percentage - 50.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn merge_segments_requires_adjacency_and_moves_readings() {
+        let db = Database::new_in_memory().unwrap();
+        db.insert_session(&session("s", t0())).await.unwrap();
+        let start = t0();
+        let segments = vec![
+            segment("s", "com.a", start, 60),
+            segment("s", "com.b", start + Duration::seconds(60), 60),
+            segment("s", "com.a", start + Duration::seconds(120), 60),
+        ];
+        db.insert_segments_and_interruptions("s", &segments, &[])
+            .await
+            .unwrap();
+        let readings: Vec<_> = [10, 70, 130]
+            .iter()
+            .zip(&segments)
+            .map(|(offset, seg)| reading("s", start + Duration::seconds(*offset), &seg.bundle_id))
+            .collect();
+        db.insert_context_readings_batch(&readings).await.unwrap();
+        let ranges: Vec<_> = segments
+            .iter()
+            .map(|s| (s.id.clone(), s.start_time, s.end_time))
+            .collect();
+        db.update_readings_with_segment_ids("s", &ranges)
+            .await
+            .unwrap();
+
+        // The middle segment sits between the first and last
+        let err = db
+            .merge_segments(&segments[0].id, &segments[2].id)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not adjacent"));
+
+        // Argument order doesn't matter for adjacent segments
+        let merged = db
+            .merge_segments(&segments[1].id, &segments[0].id)
+            .await
+            .unwrap();
+        assert_eq!(merged.start_time, start);
+        assert_eq!(merged.end_time, start + Duration::seconds(120));
+        assert_eq!(merged.duration_secs, 120);
+        assert_eq!(merged.reading_count, 2);
+
+        let remaining = db.get_segments_for_session("s", None).await.unwrap();
+        let ids: Vec<&str> = remaining.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, [merged.id.as_str(), segments[2].id.as_str()]);
+
+        let stored = db.get_context_readings_for_session("s").await.unwrap();
+        let owners: Vec<Option<&str>> = stored.iter().map(|r| r.segment_id.as_deref()).collect();
+        assert_eq!(
+            owners,
+            [
+                Some(merged.id.as_str()),
+                Some(merged.id.as_str()),
+                Some(segments[2].id.as_str()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn min_confidence_hides_segments_and_reports_their_duration() {
+        let db = Database::new_in_memory().unwrap();
+        db.insert_session(&session("s", t0())).await.unwrap();
+        let confidences = [0.9, 0.2, 0.5, 0.49];
+        let segments: Vec<Segment> = confidences
+            .iter()
+            .enumerate()
+            .map(|(i, confidence)| Segment {
+                confidence: *confidence,
+                ..segment(
+                    "s",
+                    "com.a",
+                    t0() + Duration::seconds(i as i64 * 100),
+                    60 + i as i64,
+                )
+            })
+            .collect();
+        db.insert_segments_and_interruptions("s", &segments, &[])
+            .await
+            .unwrap();
+
+        let all = db.get_segments_for_session("s", None).await.unwrap();
+        assert_eq!(all.len(), 4);
+
+        // The threshold itself passes
+        let shown = db.get_segments_for_session("s", Some(0.5)).await.unwrap();
+        let shown_confidences: Vec<f64> = shown.iter().map(|s| s.confidence).collect();
+        assert_eq!(shown_confidences, [0.9, 0.5]);
+
+        let hidden_secs = db.get_low_confidence_duration("s", 0.5).await.unwrap();
+        assert_eq!(hidden_secs, 61 + 63);
+        assert_eq!(db.get_low_confidence_duration("s", 0.0).await.unwrap(), 0);
+    }
+}
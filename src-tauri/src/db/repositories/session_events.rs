@@ -0,0 +1,70 @@
+//! Session lifecycle event log (`session_events`).
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use crate::db::{
+    connection::Database,
+    helpers::{parse_datetime, parse_session_event_type},
+    models::{SessionEvent, SessionEventType},
+};
+
+impl Database {
+    /// Append a lifecycle event for `session_id`, timestamped now.
+    pub async fn log_session_event(
+        &self,
+        session_id: &str,
+        event_type: SessionEventType,
+        detail: Option<String>,
+    ) -> Result<()> {
+        let session_id = session_id.to_string();
+        let at = Utc::now();
+        self.execute(move |conn| {
+            conn.execute(
+                "INSERT INTO session_events (session_id, event_type, detail, at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![session_id, event_type.as_str(), detail, at.to_rfc3339()],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Every event logged for `session_id`, oldest first.
+    pub async fn get_session_events(&self, session_id: &str) -> Result<Vec<SessionEvent>> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, session_id, event_type, detail, at
+                 FROM session_events
+                 WHERE session_id = ?1
+                 ORDER BY at ASC, id ASC",
+            )?;
+            let rows = stmt
+                .query_map(params![session_id], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            rows.into_iter()
+                .map(|(id, session_id, event_type, detail, at)| {
+                    Ok(SessionEvent {
+                        id,
+                        session_id,
+                        event_type: parse_session_event_type(&event_type)?,
+                        detail,
+                        at: parse_datetime(&at, "at")?,
+                    })
+                })
+                .collect()
+        })
+        .await
+    }
+}
@@ -1,13 +1,51 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, Utc};
+use std::collections::HashMap;
 use rusqlite::{params, OptionalExtension, Row};
 
 use crate::db::{
     connection::Database,
     helpers::{parse_datetime, parse_optional_datetime, parse_status, to_i64, to_u64},
-    models::{Session, SessionStatus},
+    models::{ActiveMsDiscrepancy, DailyFocus, DailyProgress, Session, SessionStatus},
 };
 
+/// Longest range `get_focus_by_day` fills, in days.
+const MAX_FOCUS_RANGE_DAYS: i64 = 366;
+
+/// Difference between `active_ms` and summed segment time that `audit_active_ms`
+/// ignores: two 5s capture intervals, since segments start at a session's first
+/// reading and end one interval past its last.
+const ACTIVE_MS_AUDIT_TOLERANCE_MS: u64 = 10_000;
+
+fn fixed_offset(tz_offset_minutes: i32) -> Result<FixedOffset> {
+    tz_offset_minutes
+        .checked_mul(60)
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(|| anyhow::anyhow!("Invalid timezone offset: {} minutes", tz_offset_minutes))
+}
+
+/// UTC instant of local midnight starting `date`, `tz_offset_minutes` east of UTC.
+fn local_day_start(date: NaiveDate, tz_offset_minutes: i32) -> DateTime<Utc> {
+    (date.and_time(NaiveTime::MIN) - Duration::minutes(tz_offset_minutes.into())).and_utc()
+}
+
+/// Error unless `label_id` refers to an existing, non-deleted label.
+pub(crate) fn ensure_label_assignable(conn: &rusqlite::Connection, label_id: i64) -> Result<()> {
+    let deleted_at: Option<Option<String>> = conn
+        .query_row(
+            "SELECT deleted_at FROM labels WHERE id = ?1",
+            params![label_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match deleted_at {
+        None => Err(anyhow::anyhow!("Label {} not found", label_id)),
+        Some(Some(_)) => Err(anyhow::anyhow!("Label {} has been deleted", label_id)),
+        Some(None) => Ok(()),
+    }
+}
+
 fn row_to_session(row: &Row) -> Result<Session> {
     let started_at: String = row.get("started_at")?;
     let stopped_at: Option<String> = row.get("stopped_at")?;
@@ -16,7 +54,10 @@ fn row_to_session(row: &Row) -> Result<Session> {
     let status: String = row.get("status")?;
     let target_ms: i64 = row.get("target_ms")?;
     let active_ms: i64 = row.get("active_ms")?;
+    let actual_active_ms: i64 = row.get("actual_active_ms")?;
     let label_id: Option<i64> = row.get("label_id")?;
+    let cancel_reason: Option<String> = row.get("cancel_reason")?;
+    let is_break: bool = row.get("is_break")?;
 
     Ok(Session {
         id: row.get("id")?,
@@ -25,7 +66,10 @@ fn row_to_session(row: &Row) -> Result<Session> {
         status: parse_status(&status)?,
         target_ms: to_u64(target_ms, "target_ms")?,
         active_ms: to_u64(active_ms, "active_ms")?,
+        actual_active_ms: to_u64(actual_active_ms, "actual_active_ms")?,
         label_id,
+        cancel_reason,
+        is_break,
         created_at: parse_datetime(&created_at, "created_at")?,
         updated_at: parse_datetime(&updated_at, "updated_at")?,
     })
@@ -36,8 +80,8 @@ impl Database {
         let record = session.clone();
         self.execute(move |conn| {
             conn.execute(
-                "INSERT INTO sessions (id, started_at, stopped_at, status, target_ms, active_ms, label_id, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                "INSERT INTO sessions (id, started_at, stopped_at, status, target_ms, active_ms, actual_active_ms, label_id, is_break, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
                 params![
                     record.id,
                     record.started_at.to_rfc3339(),
@@ -48,7 +92,9 @@ impl Database {
                     record.status.as_str(),
                     to_i64(record.target_ms)?,
                     to_i64(record.active_ms)?,
+                    to_i64(record.actual_active_ms)?,
                     record.label_id,
+                    record.is_break,
                     record.created_at.to_rfc3339(),
                     record.updated_at.to_rfc3339(),
                 ],
@@ -58,10 +104,13 @@ impl Database {
         .await
     }
 
+    /// `active_ms` is the display value (clamped to the countdown target);
+    /// `actual_active_ms` is the unclamped active time.
     pub async fn update_session_progress(
         &self,
         session_id: &str,
         active_ms: u64,
+        actual_active_ms: u64,
         updated_at: DateTime<Utc>,
     ) -> Result<()> {
         let session_id = session_id.to_string();
@@ -69,9 +118,15 @@ impl Database {
             conn.execute(
                 "UPDATE sessions
                  SET active_ms = ?1,
-                     updated_at = ?2
-                 WHERE id = ?3",
-                params![to_i64(active_ms)?, updated_at.to_rfc3339(), session_id,],
+                     actual_active_ms = ?2,
+                     updated_at = ?3
+                 WHERE id = ?4",
+                params![
+                    to_i64(active_ms)?,
+                    to_i64(actual_active_ms)?,
+                    updated_at.to_rfc3339(),
+                    session_id,
+                ],
             )?;
             Ok(())
         })
@@ -83,6 +138,7 @@ impl Database {
         session_id: &str,
         status: SessionStatus,
         active_ms: u64,
+        actual_active_ms: u64,
         stopped_at: Option<DateTime<Utc>>,
         updated_at: DateTime<Utc>,
     ) -> Result<()> {
@@ -92,12 +148,14 @@ impl Database {
                 "UPDATE sessions
                  SET status = ?1,
                      active_ms = ?2,
-                     stopped_at = ?3,
-                     updated_at = ?4
-                 WHERE id = ?5",
+                     actual_active_ms = ?3,
+                     stopped_at = ?4,
+                     updated_at = ?5
+                 WHERE id = ?6",
                 params![
                     status.as_str(),
                     to_i64(active_ms)?,
+                    to_i64(actual_active_ms)?,
                     stopped_at.map(|dt| dt.to_rfc3339()),
                     updated_at.to_rfc3339(),
                     session_id,
@@ -108,17 +166,67 @@ impl Database {
         .await
     }
 
-    pub async fn get_session(&self, session_id: &str) -> Result<Session> {
+    /// Flag a session whose active time left out idle stretches under
+    /// `active_requires_interaction`, so `audit_active_ms` doesn't hold it to its
+    /// segment durations.
+    pub async fn mark_session_idle_excluded(&self, session_id: &str) -> Result<()> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            conn.execute(
+                "UPDATE sessions SET idle_excluded = 1 WHERE id = ?1",
+                params![session_id],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Mark a session `Cancelled`, recording an optional reason for analytics.
+    pub async fn mark_session_cancelled(
+        &self,
+        session_id: &str,
+        active_ms: u64,
+        cancelled_at: DateTime<Utc>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        let session_id = session_id.to_string();
+        self.execute(move |conn| {
+            conn.execute(
+                "UPDATE sessions
+                 SET status = ?1,
+                     active_ms = ?2,
+                     actual_active_ms = ?2,
+                     stopped_at = ?3,
+                     updated_at = ?3,
+                     cancel_reason = ?4
+                 WHERE id = ?5",
+                params![
+                    SessionStatus::Cancelled.as_str(),
+                    to_i64(active_ms)?,
+                    cancelled_at.to_rfc3339(),
+                    reason,
+                    session_id,
+                ],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Fetch a single session by id. Returns `None` if no session has this id.
+    pub async fn get_session(&self, session_id: &str) -> Result<Option<Session>> {
         let session_id = session_id.to_string();
         self.execute(move |conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, label_id, created_at, updated_at
+                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, actual_active_ms, label_id, cancel_reason, is_break, created_at, updated_at
                  FROM sessions
                  WHERE id = ?1",
             )?;
 
             let session = stmt
-                .query_row(params![session_id], |row| Ok(row_to_session(row)))?
+                .query_row(params![session_id], |row| Ok(row_to_session(row)))
+                .optional()?
+                .transpose()
                 .map_err(|e| anyhow::anyhow!("Failed to parse session: {}", e))?;
 
             Ok(session)
@@ -129,7 +237,7 @@ impl Database {
     pub async fn get_incomplete_session(&self) -> Result<Option<Session>> {
         self.execute(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, label_id, created_at, updated_at
+                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, actual_active_ms, label_id, cancel_reason, is_break, created_at, updated_at
                  FROM sessions
                  WHERE status = 'Running'
                  ORDER BY started_at DESC
@@ -176,9 +284,9 @@ impl Database {
     pub async fn list_sessions(&self) -> Result<Vec<Session>> {
         self.execute(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, label_id, created_at, updated_at
+                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, actual_active_ms, label_id, cancel_reason, is_break, created_at, updated_at
                  FROM sessions
-                 WHERE status IN ('Completed', 'Interrupted')
+                 WHERE status IN ('Completed', 'Interrupted') AND is_break = 0
                  ORDER BY started_at DESC",
             )?;
 
@@ -198,18 +306,73 @@ impl Database {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Session>> {
+        self.list_sessions_by_status(
+            vec![SessionStatus::Completed, SessionStatus::Interrupted],
+            limit,
+            offset,
+        )
+        .await
+    }
+
+    /// Non-break sessions whose status is one of `statuses`, newest first.
+    /// An empty `statuses` matches nothing.
+    pub async fn list_sessions_by_status(
+        &self,
+        statuses: Vec<SessionStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Session>> {
+        if statuses.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let limit = limit as i64;
         let offset = offset as i64;
         self.execute(move |conn| {
+            let placeholders = statuses.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+            let query = format!(
+                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, actual_active_ms, label_id, cancel_reason, is_break, created_at, updated_at
+                 FROM sessions
+                 WHERE status IN ({}) AND is_break = 0
+                 ORDER BY started_at DESC
+                 LIMIT ? OFFSET ?",
+                placeholders
+            );
+
+            let mut stmt = conn.prepare(&query)?;
+            let status_strs: Vec<&str> = statuses.iter().map(|s| s.as_str()).collect();
+            let mut params: Vec<&dyn rusqlite::ToSql> = status_strs
+                .iter()
+                .map(|s| s as &dyn rusqlite::ToSql)
+                .collect();
+            params.push(&limit);
+            params.push(&offset);
+
+            let mut rows = stmt.query(params.as_slice())?;
+            let mut sessions = Vec::new();
+            while let Some(row) = rows.next()? {
+                sessions.push(row_to_session(row)?);
+            }
+
+            Ok(sessions)
+        })
+        .await
+    }
+
+    /// Finished sessions that have context readings but no segments, e.g. ones
+    /// interrupted by a crash before segmentation ran. Oldest first.
+    pub async fn list_unsegmented_sessions(&self) -> Result<Vec<Session>> {
+        self.execute(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, label_id, created_at, updated_at
+                "SELECT id, started_at, stopped_at, status, target_ms, active_ms, actual_active_ms, label_id, cancel_reason, is_break, created_at, updated_at
                  FROM sessions
                  WHERE status IN ('Completed', 'Interrupted')
-                 ORDER BY started_at DESC
-                 LIMIT ?1 OFFSET ?2",
+                 AND EXISTS (SELECT 1 FROM context_readings WHERE context_readings.session_id = sessions.id)
+                 AND NOT EXISTS (SELECT 1 FROM segments WHERE segments.session_id = sessions.id)
+                 ORDER BY started_at ASC",
             )?;
 
-            let mut rows = stmt.query(params![limit, offset])?;
+            let mut rows = stmt.query([])?;
             let mut sessions = Vec::new();
             while let Some(row) = rows.next()? {
                 sessions.push(row_to_session(row)?);
@@ -220,6 +383,188 @@ impl Database {
         .await
     }
 
+    /// Focus time so far on the local day containing now, where the local zone is
+    /// `tz_offset_minutes` east of UTC. Counts the active time of completed and
+    /// interrupted non-break sessions that started that day.
+    pub async fn get_today_progress(
+        &self,
+        tz_offset_minutes: i32,
+        goal_minutes: u32,
+    ) -> Result<DailyProgress> {
+        let offset = fixed_offset(tz_offset_minutes)?;
+        let date = Utc::now().with_timezone(&offset).date_naive();
+        let day_start = local_day_start(date, tz_offset_minutes);
+        let day_end = day_start + Duration::days(1);
+
+        let focused_ms: i64 = self
+            .execute(move |conn| {
+                let total = conn.query_row(
+                    "SELECT COALESCE(SUM(active_ms), 0)
+                     FROM sessions
+                     WHERE status IN ('Completed', 'Interrupted') AND is_break = 0
+                       AND started_at >= ?1 AND started_at < ?2",
+                    params![day_start.to_rfc3339(), day_end.to_rfc3339()],
+                    |row| row.get(0),
+                )?;
+                Ok(total)
+            })
+            .await?;
+        let focused_ms = to_u64(focused_ms, "active_ms")?;
+
+        let percentage = if goal_minutes == 0 {
+            0.0
+        } else {
+            focused_ms as f64 / (f64::from(goal_minutes) * 60_000.0) * 100.0
+        };
+
+        Ok(DailyProgress {
+            date,
+            focused_minutes: focused_ms / 60_000,
+            goal_minutes,
+            percentage,
+        })
+    }
+
+    /// Focus time per local day from `start` to `end` inclusive, where the local zone
+    /// is `tz_offset_minutes` east of UTC. Sessions count toward the day they started
+    /// on, with the same filter as `get_today_progress`; days without sessions are
+    /// included as zeros so a chart gets one bar per day.
+    pub async fn get_focus_by_day(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        tz_offset_minutes: i32,
+    ) -> Result<Vec<DailyFocus>> {
+        let offset = fixed_offset(tz_offset_minutes)?;
+        if end < start {
+            anyhow::bail!("Range end {} is before its start {}", end, start);
+        }
+        let days = (end - start).num_days() + 1;
+        if days > MAX_FOCUS_RANGE_DAYS {
+            anyhow::bail!("Range spans {days} days; at most {MAX_FOCUS_RANGE_DAYS} are allowed");
+        }
+
+        let range_start = local_day_start(start, tz_offset_minutes);
+        let range_end = range_start + Duration::days(days);
+
+        let rows: Vec<(String, i64)> = self
+            .execute(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT started_at, active_ms
+                     FROM sessions
+                     WHERE status IN ('Completed', 'Interrupted') AND is_break = 0
+                       AND started_at >= ?1 AND started_at < ?2",
+                )?;
+                let rows = stmt
+                    .query_map(
+                        params![range_start.to_rfc3339(), range_end.to_rfc3339()],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await?;
+
+        let mut by_day: HashMap<NaiveDate, (u64, u32)> = HashMap::new();
+        for (started_at, active_ms) in rows {
+            let local_date = parse_datetime(&started_at, "started_at")?
+                .with_timezone(&offset)
+                .date_naive();
+            let entry = by_day.entry(local_date).or_default();
+            entry.0 += to_u64(active_ms, "active_ms")?;
+            entry.1 += 1;
+        }
+
+        Ok(start
+            .iter_days()
+            .take(days as usize)
+            .map(|local_date| {
+                let (total_active_ms, session_count) =
+                    by_day.get(&local_date).copied().unwrap_or_default();
+                DailyFocus {
+                    local_date,
+                    total_active_ms,
+                    session_count,
+                }
+            })
+            .collect())
+    }
+
+    /// Compare each completed session's `active_ms` with its summed segment durations
+    /// (clamped to `target_ms`, as `active_ms` is for countdowns) and return those off
+    /// by more than `ACTIVE_MS_AUDIT_TOLERANCE_MS`, oldest first. With `repair`, each
+    /// discrepancy's `active_ms` is rewritten to the segment total in one transaction.
+    ///
+    /// Sessions without segments (e.g. stopwatch or short sessions that never got
+    /// segmented) are skipped; there is nothing to check them against. So are sessions
+    /// flagged `idle_excluded`: their segments span idle time that `active_ms` left
+    /// out on purpose.
+    pub async fn audit_active_ms(&self, repair: bool) -> Result<Vec<ActiveMsDiscrepancy>> {
+        self.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            let rows: Vec<(String, String, i64, i64, i64, i64)> = {
+                let mut stmt = tx.prepare(
+                    "SELECT s.id, s.started_at, s.target_ms, s.active_ms,
+                            SUM(seg.duration_secs), COUNT(seg.id)
+                     FROM sessions s
+                     JOIN segments seg ON seg.session_id = s.id
+                     WHERE s.status = 'Completed' AND s.idle_excluded = 0
+                     GROUP BY s.id
+                     ORDER BY s.started_at ASC",
+                )?;
+                let rows = stmt
+                    .query_map([], |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                            row.get(5)?,
+                        ))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows
+            };
+
+            let mut discrepancies = Vec::new();
+            for (session_id, started_at, target_ms, active_ms, seg_secs, seg_count) in rows {
+                let stored_active_ms = to_u64(active_ms, "active_ms")?;
+                let segment_active_ms = (seg_secs.max(0) as u64)
+                    .saturating_mul(1000)
+                    .min(to_u64(target_ms, "target_ms")?);
+                if stored_active_ms.abs_diff(segment_active_ms) <= ACTIVE_MS_AUDIT_TOLERANCE_MS {
+                    continue;
+                }
+
+                if repair {
+                    tx.execute(
+                        "UPDATE sessions SET active_ms = ?1, updated_at = ?2 WHERE id = ?3",
+                        params![
+                            to_i64(segment_active_ms)?,
+                            Utc::now().to_rfc3339(),
+                            session_id
+                        ],
+                    )?;
+                }
+
+                discrepancies.push(ActiveMsDiscrepancy {
+                    started_at: parse_datetime(&started_at, "started_at")?,
+                    session_id,
+                    stored_active_ms,
+                    segment_active_ms,
+                    segment_count: seg_count as u32,
+                    repaired: repair,
+                });
+            }
+
+            tx.commit()?;
+            Ok(discrepancies)
+        })
+        .await
+    }
+
     /// Update the label_id for a session
     pub async fn update_session_label(
         &self,
@@ -228,18 +573,9 @@ impl Database {
     ) -> Result<()> {
         let session_id = session_id.to_string();
         self.execute(move |conn| {
+            // `None` clears the label; otherwise the label must exist and not be soft-deleted
             if let Some(label_id) = label_id {
-                let exists: Option<i64> = conn
-                    .query_row(
-                        "SELECT id FROM labels WHERE id = ?1 AND deleted_at IS NULL",
-                        params![label_id],
-                        |row| row.get(0),
-                    )
-                    .optional()?;
-
-                if exists.is_none() {
-                    return Err(anyhow::anyhow!("Label not found or has been deleted"));
-                }
+                ensure_label_assignable(conn, label_id)?;
             }
 
             let rows_affected = conn.execute(
@@ -251,7 +587,7 @@ impl Database {
             )?;
 
             if rows_affected == 0 {
-                return Err(anyhow::anyhow!("Session not found"));
+                return Err(anyhow::anyhow!("Session {} not found", session_id));
             }
 
             Ok(())
@@ -259,6 +595,39 @@ impl Database {
         .await
     }
 
+    /// Set (or clear with `None`) the label on many sessions in a single transaction.
+    /// Returns the number of sessions updated; unknown ids are skipped.
+    pub async fn set_label_for_sessions(
+        &self,
+        session_ids: Vec<String>,
+        label_id: Option<i64>,
+    ) -> Result<usize> {
+        self.execute(move |conn| {
+            if let Some(label_id) = label_id {
+                ensure_label_assignable(conn, label_id)?;
+            }
+
+            let tx = conn.transaction()?;
+            let now = Utc::now().to_rfc3339();
+            let mut rows_affected = 0;
+            {
+                let mut stmt = tx.prepare(
+                    "UPDATE sessions
+                     SET label_id = ?1,
+                         updated_at = ?2
+                     WHERE id = ?3",
+                )?;
+                for session_id in &session_ids {
+                    rows_affected += stmt.execute(params![label_id, now, session_id])?;
+                }
+            }
+            tx.commit()?;
+
+            Ok(rows_affected)
+        })
+        .await
+    }
+
     /// Delete a session and all its related data (segments, interruptions)
     /// 
     /// Note: `context_readings` are automatically deleted via ON DELETE CASCADE
@@ -298,3 +667,89 @@ impl Database {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::LabelInput;
+    use crate::db::test_support::{segment, session, t0};
+
+    #[tokio::test]
+    async fn incomplete_session_round_trips() {
+        let db = Database::new_in_memory().unwrap();
+        let label = db
+            .create_label(LabelInput {
+                name: "Deep work".to_string(),
+                color: "#4f46e5".to_string(),
+                default_target_ms: None,
+                default_mode: None,
+                default_sound: None,
+            })
+            .await
+            .unwrap();
+
+        let mut running = session("running", t0());
+        running.stopped_at = None;
+        running.status = SessionStatus::Running;
+        running.active_ms = 0;
+        running.actual_active_ms = 0;
+        running.label_id = Some(label.id);
+        running.updated_at = running.started_at;
+        db.insert_session(&running).await.unwrap();
+        db.insert_session(&session("finished", t0() - Duration::hours(2)))
+            .await
+            .unwrap();
+
+        let loaded = db.get_incomplete_session().await.unwrap().unwrap();
+        assert_eq!(loaded.id, running.id);
+        assert_eq!(loaded.started_at, running.started_at);
+        assert_eq!(loaded.stopped_at, None);
+        assert_eq!(loaded.status, SessionStatus::Running);
+        assert_eq!(loaded.target_ms, running.target_ms);
+        assert_eq!(loaded.active_ms, 0);
+        assert_eq!(loaded.label_id, Some(label.id));
+        assert!(!loaded.is_break);
+        assert_eq!(loaded.created_at, running.created_at);
+    }
+
+    #[tokio::test]
+    async fn audit_active_ms_reports_and_repairs_drift() {
+        let db = Database::new_in_memory().unwrap();
+        // Within tolerance of its segments
+        let matching = session("matching", t0());
+        // Stored an hour, but its segments cover only half of it
+        let drifted = session("drifted", t0() + Duration::hours(2));
+        // Idle time was left out of active_ms on purpose; its segments still span it
+        let mut idle = session("idle", t0() + Duration::hours(4));
+        idle.active_ms = 1_200_000;
+        idle.actual_active_ms = 1_200_000;
+        for (s, segment_secs) in [(&matching, 3_595), (&drifted, 1_800), (&idle, 3_600)] {
+            db.insert_session(s).await.unwrap();
+            let segments = [segment(&s.id, "com.a", s.started_at, segment_secs)];
+            db.insert_segments_and_interruptions(&s.id, &segments, &[])
+                .await
+                .unwrap();
+        }
+        db.mark_session_idle_excluded("idle").await.unwrap();
+
+        let report = db.audit_active_ms(false).await.unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].session_id, "drifted");
+        assert_eq!(report[0].stored_active_ms, 3_600_000);
+        assert_eq!(report[0].segment_active_ms, 1_800_000);
+        assert_eq!(report[0].segment_count, 1);
+        assert!(!report[0].repaired);
+        let stored = db.get_session("drifted").await.unwrap().unwrap();
+        assert_eq!(stored.active_ms, 3_600_000);
+
+        let repaired = db.audit_active_ms(true).await.unwrap();
+        assert_eq!(repaired.len(), 1);
+        assert!(repaired[0].repaired);
+        let stored = db.get_session("drifted").await.unwrap().unwrap();
+        assert_eq!(stored.active_ms, 1_800_000);
+        let stored = db.get_session("idle").await.unwrap().unwrap();
+        assert_eq!(stored.active_ms, 1_200_000);
+
+        assert!(db.audit_active_ms(false).await.unwrap().is_empty());
+    }
+}
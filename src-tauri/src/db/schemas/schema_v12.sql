@@ -0,0 +1,4 @@
+-- Migration to version 12: Add reading_activity_score column to segments
+
+-- Optional fifth confidence factor derived from OCR word counts
+ALTER TABLE segments ADD COLUMN reading_activity_score REAL;
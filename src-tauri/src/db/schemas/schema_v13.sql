@@ -0,0 +1,5 @@
+-- Migration to version 13: Re-add segment_type column to segments
+
+-- segment_type was dropped in v6; it is now stored again so merged/classified
+-- segments round-trip through the database. Existing rows default to 'Stable'.
+ALTER TABLE segments ADD COLUMN segment_type TEXT NOT NULL DEFAULT 'Stable';
@@ -0,0 +1,14 @@
+-- Migration to version 14: Add auto_label_rules table for dominant-app session labeling
+
+CREATE TABLE auto_label_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    bundle_id TEXT NOT NULL,
+    label_id INTEGER NOT NULL,
+    -- Share of the session (0-100) the app must reach for the rule to match
+    min_percentage REAL NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_auto_label_rules_label_id ON auto_label_rules(label_id);
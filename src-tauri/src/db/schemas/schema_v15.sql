@@ -0,0 +1,3 @@
+-- Migration to version 15: Add cancel_reason to sessions
+
+ALTER TABLE sessions ADD COLUMN cancel_reason TEXT;
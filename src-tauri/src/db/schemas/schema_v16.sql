@@ -0,0 +1,3 @@
+-- Migration to version 16: flag break sessions so they stay out of focus session lists
+
+ALTER TABLE sessions ADD COLUMN is_break INTEGER NOT NULL DEFAULT 0;
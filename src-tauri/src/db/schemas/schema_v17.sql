@@ -0,0 +1,7 @@
+-- Migration to version 17: record unclamped active time
+
+-- active_ms stays clamped to target_ms for countdowns; actual_active_ms keeps the
+-- true elapsed active time. Existing rows only ever stored the clamped value.
+ALTER TABLE sessions ADD COLUMN actual_active_ms INTEGER NOT NULL DEFAULT 0;
+
+UPDATE sessions SET actual_active_ms = active_ms;
@@ -0,0 +1,13 @@
+-- Migration to version 18: add segment_tags for free-form tags on individual segments
+
+-- Segments can carry any number of tags independent of the session's single label.
+-- Tags are removed with their segment.
+CREATE TABLE segment_tags (
+    segment_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (segment_id, tag),
+    FOREIGN KEY (segment_id) REFERENCES segments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_segment_tags_tag ON segment_tags(tag);
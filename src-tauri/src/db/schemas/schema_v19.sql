@@ -0,0 +1,5 @@
+-- Migration to version 19: Add per-label default timer settings
+
+ALTER TABLE labels ADD COLUMN default_target_ms INTEGER;
+ALTER TABLE labels ADD COLUMN default_mode TEXT;
+ALTER TABLE labels ADD COLUMN default_sound TEXT;
@@ -0,0 +1,5 @@
+-- Migration to version 20: Store textual change between consecutive OCR readings
+
+-- Jaccard distance between this reading's OCR word set and the previous reading's
+-- (0 = same words, 1 = no words in common). NULL when either reading lacks OCR.
+ALTER TABLE context_readings ADD COLUMN ocr_text_delta REAL;
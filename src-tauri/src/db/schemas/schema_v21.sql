@@ -0,0 +1,4 @@
+-- Migration to version 21: Store each segment's average OCR word count
+
+-- Mean ocr_word_count over the segment's readings that ran OCR; NULL when none did.
+ALTER TABLE segments ADD COLUMN avg_ocr_word_count REAL;
@@ -0,0 +1,15 @@
+-- Migration to version 22: add session_events, an append-only log of session lifecycle events
+
+-- One row per lifecycle event (started, ended, cancelled, interrupted, ...), kept
+-- separate from the app log so "why did this session end?" survives restarts.
+-- Events are removed with their session.
+CREATE TABLE session_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    detail TEXT,
+    at TEXT NOT NULL,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_session_events_session ON session_events(session_id, at);
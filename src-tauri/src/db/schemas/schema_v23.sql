@@ -0,0 +1,6 @@
+-- Migration to version 23: Flag sessions whose active time left out idle stretches
+
+-- Set once `active_requires_interaction` pauses accrual during the session. Such a
+-- session's active_ms is shorter than its segments by design, so audit_active_ms
+-- skips it.
+ALTER TABLE sessions ADD COLUMN idle_excluded INTEGER NOT NULL DEFAULT 0;
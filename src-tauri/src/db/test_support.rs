@@ -0,0 +1,90 @@
+//! Fixtures shared by database and segmentation tests.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::db::models::{ContextReading, Segment, SegmentType, Session, SessionStatus};
+use crate::macos_bridge::{WindowBounds, WindowMetadata};
+
+/// A fixed, whole-second instant so stored timestamps round-trip exactly.
+pub fn t0() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap()
+}
+
+/// A completed one-hour session starting at `started_at`.
+pub fn session(id: &str, started_at: DateTime<Utc>) -> Session {
+    let stopped_at = started_at + Duration::hours(1);
+    Session {
+        id: id.to_string(),
+        started_at,
+        stopped_at: Some(stopped_at),
+        status: SessionStatus::Completed,
+        target_ms: 3_600_000,
+        active_ms: 3_600_000,
+        actual_active_ms: 3_600_000,
+        label_id: None,
+        cancel_reason: None,
+        is_break: false,
+        created_at: started_at,
+        updated_at: stopped_at,
+    }
+}
+
+/// A stable, fully confident segment of `duration_secs` starting at `start_time`.
+pub fn segment(
+    session_id: &str,
+    bundle_id: &str,
+    start_time: DateTime<Utc>,
+    duration_secs: i64,
+) -> Segment {
+    Segment {
+        id: Uuid::new_v4().to_string(),
+        session_id: session_id.to_string(),
+        start_time,
+        end_time: start_time + Duration::seconds(duration_secs),
+        duration_secs,
+        bundle_id: bundle_id.to_string(),
+        app_name: Some(bundle_id.to_string()),
+        window_title: None,
+        segment_type: SegmentType::Stable,
+        confidence: 1.0,
+        duration_score: None,
+        stability_score: None,
+        visual_clarity_score: None,
+        ocr_quality_score: None,
+        reading_activity_score: None,
+        reading_count: 1,
+        unique_phash_count: None,
+        avg_ocr_word_count: None,
+        segment_summary: None,
+        icon_data_url: None,
+        icon_color: None,
+        tags: Vec::new(),
+    }
+}
+
+/// A reading of `bundle_id`'s front window at `timestamp`, with no OCR.
+pub fn reading(session_id: &str, timestamp: DateTime<Utc>, bundle_id: &str) -> ContextReading {
+    ContextReading {
+        id: None,
+        session_id: session_id.to_string(),
+        timestamp,
+        window_metadata: WindowMetadata {
+            window_id: 1,
+            bundle_id: bundle_id.to_string(),
+            title: format!("{bundle_id} window"),
+            owner_name: bundle_id.to_string(),
+            bounds: WindowBounds {
+                x: 0.0,
+                y: 0.0,
+                width: 1280.0,
+                height: 800.0,
+            },
+        },
+        phash: None,
+        ocr_text: None,
+        ocr_confidence: None,
+        ocr_word_count: None,
+        segment_id: None,
+    }
+}
@@ -1,16 +1,17 @@
 use tauri::State;
 
 use crate::{
-    db::models::{Label, LabelInput},
+    db::models::{
+        AutoLabelRule, AutoLabelRuleInput, Label, LabelDefaults, LabelInput, SessionSummary,
+    },
+    timer::commands::build_session_summaries,
     AppState,
 };
 
 #[tauri::command]
 pub async fn create_label(state: State<'_, AppState>, input: LabelInput) -> Result<Label, String> {
     let db = &state.db;
-    db.create_label(input.name, input.color)
-        .await
-        .map_err(|e| e.to_string())
+    db.create_label(input).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -25,9 +26,22 @@ pub async fn update_label(
     label_id: i64,
     name: Option<String>,
     color: Option<String>,
+    defaults: Option<LabelDefaults>,
 ) -> Result<Label, String> {
     let db = &state.db;
-    db.update_label(label_id, name, color)
+    db.update_label(label_id, name, color, defaults)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Timer settings to pre-fill when the label is selected.
+#[tauri::command]
+pub async fn get_label_defaults(
+    state: State<'_, AppState>,
+    label_id: i64,
+) -> Result<LabelDefaults, String> {
+    let db = &state.db;
+    db.get_label_defaults(label_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -40,14 +54,82 @@ pub async fn delete_label(state: State<'_, AppState>, label_id: i64) -> Result<(
         .map_err(|e| e.to_string())
 }
 
+/// Set (or clear with `None`) a session's label and return the refreshed summary.
 #[tauri::command]
 pub async fn update_session_label(
     state: State<'_, AppState>,
     session_id: String,
     label_id: Option<i64>,
-) -> Result<(), String> {
+) -> Result<SessionSummary, String> {
     let db = &state.db;
     db.update_session_label(&session_id, label_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let session = db
+        .get_session(&session_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+
+    build_session_summaries(
+        db,
+        vec![session],
+        state.settings.exclude_system_ui(),
+        state.settings.scattered_interruptions_per_hour(),
+    )
+    .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Session {} not found", session_id))
+}
+
+/// Assign (or clear) one label across many sessions. Returns the number updated.
+#[tauri::command]
+pub async fn bulk_set_session_label(
+    state: State<'_, AppState>,
+    session_ids: Vec<String>,
+    label_id: Option<i64>,
+) -> Result<usize, String> {
+    let db = &state.db;
+    db.set_label_for_sessions(session_ids, label_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_auto_label_rule(
+    state: State<'_, AppState>,
+    input: AutoLabelRuleInput,
+) -> Result<AutoLabelRule, String> {
+    let db = &state.db;
+    db.create_auto_label_rule(input)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_auto_label_rules(state: State<'_, AppState>) -> Result<Vec<AutoLabelRule>, String> {
+    let db = &state.db;
+    db.get_auto_label_rules().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_auto_label_rule(
+    state: State<'_, AppState>,
+    rule_id: i64,
+    input: AutoLabelRuleInput,
+) -> Result<AutoLabelRule, String> {
+    let db = &state.db;
+    db.update_auto_label_rule(rule_id, input)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_auto_label_rule(state: State<'_, AppState>, rule_id: i64) -> Result<(), String> {
+    let db = &state.db;
+    db.delete_auto_label_rule(rule_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -9,30 +9,46 @@ mod settings;
 mod timer;
 mod utils;
 
-use audio::AudioEngineHandle;
-use chrono::Utc;
-use db::Database;
+#[doc(hidden)]
+pub use utils::logging::module_enabled;
+
+use audio::{device::AudioDevice, AudioEngineHandle, AudioError, SoundSource};
+use chrono::NaiveDate;
+use db::{
+    models::{ActiveMsDiscrepancy, DailyFocus, DailyProgress, KnownApp},
+    Database,
+};
 use labels::commands::{
-    create_label, delete_label, get_labels, update_label, update_session_label,
+    bulk_set_session_label, create_auto_label_rule, create_label, delete_auto_label_rule,
+    delete_label, get_auto_label_rules, get_label_defaults, get_labels, update_auto_label_rule,
+    update_label, update_session_label,
 };
 use log::warn;
-use metrics::{MetricsCollector, MetricsSnapshot};
+use metrics::{MetricsCollector, MetricsSnapshot, SensingImpact, SessionMetrics};
 use macos_bridge::{
     get_active_window_metadata, WindowMetadata,
 };
 // DEPRECATED: Screenshot/OCR imports removed - functionality disabled
 // use macos_bridge::{capture_screenshot, run_ocr, OCRResult};
-use settings::{IslandSoundSettings, SettingsStore};
-use std::{env, process::Command};
+use sensing::{AppIconUpdate, ColorBackfillProgress, SensingConfig, SensingStatus};
+use settings::{AppearanceSettings, IslandSoundSettings, SettingsStore, SoundId};
+use std::{env, process::Command, sync::Arc};
 
 use tauri::{Emitter, Manager, State};
+use tauri_plugin_opener::OpenerExt;
 use timer::{
     commands::{
-        cancel_timer, end_timer, get_interruptions_for_segment, get_segments_for_session,
-        get_timer_state, get_window_titles_for_segment, list_sessions, list_sessions_paginated,
-        start_timer, get_app_details_in_time_range, delete_session,
+        cancel_timer, end_timer, get_interruptions_for_segment, get_interruptions_for_session,
+        get_segments_for_session, get_sensing_status, get_session, get_timer_state, get_window_titles_for_segment, list_sessions,
+        list_sessions_paginated, start_timer, get_app_details_in_time_range, get_app_all_time_titles,
+        delete_session, get_capture_gaps, get_low_confidence_duration, correct_segment_app,
+        merge_segments, split_segment, get_reading_ocr_text, add_segment_tag, remove_segment_tag,
+        get_segment_tags, get_segments_by_tag, compare_sessions, segment_unsegmented_sessions, list_sessions_by_status,
+        get_segment_timeline, suspend_sensing, resume_sensing,
+        get_longest_segments, get_session_events,
     },
-    TimerController,
+    recover_incomplete_session, segment_interrupted_session, StartupRecovery, TimerController,
+    TimerStatus,
 };
 
 
@@ -40,15 +56,31 @@ pub(crate) struct AppState {
     audio: AudioEngineHandle,
     pub(crate) db: Database,
     pub(crate) timer: TimerController,
-    pub(crate) settings: SettingsStore,
+    pub(crate) settings: Arc<SettingsStore>,
     pub(crate) metrics: MetricsCollector,
+    /// Outcome of crash recovery at launch, for windows that missed the event
+    startup_recovery: Option<StartupRecovery>,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SoundType {
     Binaural,
     BrownNoise,
     Rain,
+    Ocean,
+    Campfire,
+}
+
+impl SoundType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoundType::Binaural => "Binaural",
+            SoundType::BrownNoise => "BrownNoise",
+            SoundType::Rain => "Rain",
+            SoundType::Ocean => "Ocean",
+            SoundType::Campfire => "Campfire",
+        }
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -57,43 +89,141 @@ struct AutomationPermissionRequestResult {
     status: i32,
 }
 
+/// Result of `start_audio`. Focus sounds are optional, so a missing output device is
+/// reported here (and via `audio-unavailable`) instead of as an error.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudioStartResult {
+    audio_started: bool,
+    message: String,
+}
+
 #[tauri::command]
 fn start_audio(
     sound_type: SoundType,
     left_freq: Option<f32>,
     right_freq: Option<f32>,
+    auto_stop_ms: Option<u64>,
     state: State<AppState>,
-) -> Result<String, String> {
-    // Initialize new audio engine and add the appropriate source
-    state.audio.start()?;
+    app_handle: tauri::AppHandle,
+) -> Result<AudioStartResult, AudioError> {
+    let source = sound_source(sound_type, left_freq, right_freq);
+    start_sound(auto_stop_ms, &state, &app_handle, |audio, auto_stop| {
+        audio.append(source, auto_stop)
+    })
+}
 
-    match sound_type {
-        SoundType::Binaural => {
-            let left = left_freq.unwrap_or(200.0);
-            let right = right_freq.unwrap_or(204.0);
-            state.audio.append_binaural(left, right)?;
-        }
-        SoundType::BrownNoise => {
-            state.audio.append_brown_noise()?;
-        }
-        SoundType::Rain => {
-            state.audio.append_rain()?;
+/// Start a binaural beat whose beat frequency ramps from `start_beat` to `end_beat` Hz
+/// over `duration_ms` (e.g. alpha down to theta to wind down), then holds. Reports a
+/// missing output device like `start_audio`.
+#[tauri::command]
+fn append_binaural_sweep(
+    carrier: f32,
+    start_beat: f32,
+    end_beat: f32,
+    duration_ms: u64,
+    auto_stop_ms: Option<u64>,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AudioStartResult, AudioError> {
+    let sweep = std::time::Duration::from_millis(duration_ms);
+    start_sound(auto_stop_ms, &state, &app_handle, |audio, auto_stop| {
+        audio.append_binaural_sweep(carrier, start_beat, end_beat, sweep, auto_stop)
+    })
+}
+
+/// Reset the main sink, queue a source via `append` and play it, reporting a missing
+/// output device as `audio_started: false` rather than an error.
+fn start_sound(
+    auto_stop_ms: Option<u64>,
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+    append: impl FnOnce(&AudioEngineHandle, Option<std::time::Duration>) -> Result<(), AudioError>,
+) -> Result<AudioStartResult, AudioError> {
+    // Zero means "no limit", matching an unset auto-stop
+    let auto_stop = auto_stop_ms
+        .filter(|ms| *ms > 0)
+        .map(std::time::Duration::from_millis);
+
+    if !state.audio.has_audio_output_device() {
+        let err = AudioError::NoOutputDevice(
+            "no output device is connected; plug in headphones or speakers and start the \
+             sound again"
+                .to_string(),
+        );
+        warn!("{}; skipping audio start", err);
+        let _ = app_handle.emit("audio-no-device", &err);
+        return Ok(AudioStartResult {
+            audio_started: false,
+            message: err.to_string(),
+        });
+    }
+
+    let started = (|| {
+        // Initialize new audio engine and add the appropriate source
+        state.audio.start()?;
+        append(&state.audio, auto_stop)?;
+        state.audio.play()
+    })();
+
+    match started {
+        Ok(()) => Ok(AudioStartResult {
+            audio_started: true,
+            message: "Audio started".to_string(),
+        }),
+        Err(err @ AudioError::NoOutputDevice(_)) => {
+            warn!("Audio unavailable, continuing without sound: {}", err);
+            let _ = app_handle.emit("audio-unavailable", &err);
+            Ok(AudioStartResult {
+                audio_started: false,
+                message: err.to_string(),
+            })
         }
+        Err(err) => Err(err),
+    }
+}
+
+fn sound_source(
+    sound_type: SoundType,
+    left_freq: Option<f32>,
+    right_freq: Option<f32>,
+) -> SoundSource {
+    match sound_type {
+        SoundType::Binaural => SoundSource::Binaural {
+            left: left_freq.unwrap_or(200.0),
+            right: right_freq.unwrap_or(204.0),
+        },
+        SoundType::BrownNoise => SoundSource::BrownNoise,
+        SoundType::Rain => SoundSource::Rain,
+        SoundType::Ocean => SoundSource::Ocean,
+        SoundType::Campfire => SoundSource::Campfire,
     }
+}
 
-    state.audio.play()?;
+/// Longest preview the UI may request.
+const MAX_AUDIO_PREVIEW_MS: u64 = 30_000;
 
-    Ok("Audio started".to_string())
+#[tauri::command]
+fn preview_audio(
+    sound_type: SoundType,
+    duration_ms: u64,
+    left_freq: Option<f32>,
+    right_freq: Option<f32>,
+    state: State<AppState>,
+) -> Result<(), AudioError> {
+    let source = sound_source(sound_type, left_freq, right_freq);
+    let duration = std::time::Duration::from_millis(duration_ms.min(MAX_AUDIO_PREVIEW_MS));
+    state.audio.preview(source, duration)
 }
 
 #[tauri::command]
-fn stop_audio(state: State<AppState>) -> Result<String, String> {
+fn stop_audio(state: State<AppState>) -> Result<String, AudioError> {
     state.audio.stop()?;
     Ok("Audio stopped".to_string())
 }
 
 #[tauri::command]
-fn toggle_pause(state: State<AppState>) -> Result<bool, String> {
+fn toggle_pause(state: State<AppState>) -> Result<bool, AudioError> {
     let is_paused = state.audio.is_paused()?;
 
     if is_paused {
@@ -106,9 +236,48 @@ fn toggle_pause(state: State<AppState>) -> Result<bool, String> {
 }
 
 #[tauri::command]
-fn set_volume(volume: f32, state: State<AppState>) -> Result<String, String> {
-    state.audio.set_volume(volume)?;
-    Ok(format!("Volume set to {}", volume))
+fn set_volume(volume: f32, state: State<AppState>) -> Result<f32, AudioError> {
+    state.audio.set_volume(volume)
+}
+
+#[tauri::command]
+fn list_audio_devices() -> Result<Vec<AudioDevice>, AudioError> {
+    audio::device::list_output_devices()
+}
+
+/// Pick the output device for focus sounds (`None` = system default).
+#[tauri::command]
+fn set_audio_device(
+    id: Option<String>,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let active = state.audio.set_output_device(id.clone())?;
+    state
+        .settings
+        .update_audio_output_device(id)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("audio-device-changed", active)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_audio_sample_rate(state: State<AppState>) -> Result<u32, String> {
+    Ok(state.settings.audio_sample_rate())
+}
+
+#[tauri::command]
+fn set_audio_sample_rate(sample_rate: u32, state: State<AppState>) -> Result<(), String> {
+    // Validates against SUPPORTED_SAMPLE_RATES before anything is persisted
+    state.audio.set_sample_rate(sample_rate)?;
+    state
+        .settings
+        .update_audio_sample_rate(sample_rate)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -156,7 +325,7 @@ fn set_island_sound_settings(
 
     #[cfg(target_os = "macos")]
     {
-        macos_bridge::island_update_chime_preferences(settings.enabled, &settings.sound_id);
+        macos_bridge::island_update_chime_preferences(settings.enabled, settings.sound_id.as_str());
     }
 
     app_handle
@@ -166,14 +335,77 @@ fn set_island_sound_settings(
     Ok(())
 }
 
+#[tauri::command]
+fn get_appearance(state: State<AppState>) -> Result<AppearanceSettings, String> {
+    Ok(state.settings.appearance())
+}
+
+#[tauri::command]
+fn set_appearance(
+    settings: AppearanceSettings,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_appearance(settings.clone())
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("appearance-settings-updated", &settings)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_sensing_config(state: State<AppState>) -> Result<SensingConfig, String> {
+    Ok(state.settings.sensing_config())
+}
+
+/// Rejected unless the timer is idle: a session's readings must all be captured with
+/// one config, or segmentation's per-capture duration assumptions break. Re-sending
+/// the current config is always accepted.
+#[tauri::command]
+async fn set_sensing_config(
+    config: SensingConfig,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    if config != state.settings.sensing_config()
+        && state.timer.get_state().await.status != TimerStatus::Idle
+    {
+        return Err("cannot change sensing settings during an active session".to_string());
+    }
+
+    state
+        .settings
+        .update_sensing_config(config)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("sensing-config-updated", config)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Chime ids accepted by `set_island_sound_settings` and `preview_island_chime`.
+#[tauri::command]
+fn list_island_sounds() -> Result<Vec<SoundId>, String> {
+    Ok(SoundId::ALL.to_vec())
+}
+
 #[tauri::command]
 fn preview_island_chime(sound_id: Option<String>, sound_id_camel: Option<String>) -> Result<(), String> {
     let sound_id = sound_id
         .or(sound_id_camel)
-        .ok_or_else(|| "sound_id is required".to_string())?;
+        .ok_or_else(|| "sound_id is required".to_string())?
+        .parse::<SoundId>()
+        .map_err(|e| e.to_string())?;
     #[cfg(target_os = "macos")]
     {
-        macos_bridge::island_preview_chime(&sound_id);
+        macos_bridge::island_preview_chime(sound_id.as_str());
         return Ok(());
     }
 
@@ -212,6 +444,484 @@ fn set_island_visible(
     Ok(())
 }
 
+#[tauri::command]
+fn get_exclude_system_ui(state: State<AppState>) -> Result<bool, String> {
+    Ok(state.settings.exclude_system_ui())
+}
+
+#[tauri::command]
+fn set_exclude_system_ui(
+    exclude: bool,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_exclude_system_ui(exclude)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("exclude-system-ui-updated", exclude)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_auto_label_enabled(state: State<AppState>) -> Result<bool, String> {
+    Ok(state.settings.auto_label_enabled())
+}
+
+#[tauri::command]
+fn set_auto_label_enabled(
+    enabled: bool,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_auto_label_enabled(enabled)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("auto-label-enabled-updated", enabled)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_session_notifications_enabled(state: State<AppState>) -> Result<bool, String> {
+    Ok(state.settings.session_notifications_enabled())
+}
+
+#[tauri::command]
+fn set_session_notifications_enabled(
+    enabled: bool,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_session_notifications_enabled(enabled)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("session-notifications-enabled-updated", enabled)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_sense_during_breaks(state: State<AppState>) -> Result<bool, String> {
+    Ok(state.settings.sense_during_breaks())
+}
+
+#[tauri::command]
+fn set_sense_during_breaks(
+    enabled: bool,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_sense_during_breaks(enabled)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("sense-during-breaks-updated", enabled)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_distraction_list(state: State<AppState>) -> Result<Vec<String>, String> {
+    Ok(state.settings.distraction_list())
+}
+
+/// Replace the bundle IDs that trigger `distraction-detected`; returns the normalized list.
+#[tauri::command]
+fn set_distraction_list(
+    bundle_ids: Vec<String>,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    let list = state
+        .settings
+        .update_distraction_list(bundle_ids)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("distraction-list-updated", &list)
+        .map_err(|e| e.to_string())?;
+
+    Ok(list)
+}
+
+#[tauri::command]
+fn get_active_requires_interaction(state: State<AppState>) -> Result<bool, String> {
+    Ok(state.settings.active_requires_interaction())
+}
+
+/// When enabled, the timer stops accruing active time while the user is idle.
+#[tauri::command]
+fn set_active_requires_interaction(
+    enabled: bool,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_active_requires_interaction(enabled)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("active-requires-interaction-updated", enabled)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_daily_goal(state: State<AppState>) -> Result<u32, String> {
+    Ok(state.settings.daily_goal_minutes())
+}
+
+/// Set the daily focus goal in minutes; 0 turns it off.
+#[tauri::command]
+fn set_daily_goal(
+    minutes: u32,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_daily_goal_minutes(minutes)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("daily-goal-updated", minutes)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Today's focus time against the daily goal. `tz_offset_minutes` is minutes east
+/// of UTC (`-new Date().getTimezoneOffset()`); defaults to the system zone.
+#[tauri::command]
+async fn get_today_progress(
+    tz_offset_minutes: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<DailyProgress, String> {
+    let offset = tz_offset_minutes.unwrap_or_else(utils::time::local_offset_minutes);
+    state
+        .db
+        .get_today_progress(offset, state.settings.daily_goal_minutes())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Focus time per local day from `start` to `end` (inclusive, `YYYY-MM-DD`), with
+/// zero entries for days without sessions. `tz_offset_minutes` is as for
+/// `get_today_progress`.
+#[tauri::command]
+async fn get_focus_by_day(
+    start: NaiveDate,
+    end: NaiveDate,
+    tz_offset_minutes: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DailyFocus>, String> {
+    let offset = tz_offset_minutes.unwrap_or_else(utils::time::local_offset_minutes);
+    state
+        .db
+        .get_focus_by_day(start, end, offset)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Audit completed sessions' `active_ms` against their segment durations. With
+/// `repair`, mismatched sessions are updated to the segment total. Sessions without
+/// segments, or whose idle time was left out of `active_ms`, are skipped.
+#[tauri::command]
+async fn audit_active_ms(
+    repair: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<ActiveMsDiscrepancy>, String> {
+    let discrepancies = state
+        .db
+        .audit_active_ms(repair)
+        .await
+        .map_err(|e| e.to_string())?;
+    log::info!(
+        "active_ms audit found {} discrepancies{}",
+        discrepancies.len(),
+        if repair { " (repaired)" } else { "" }
+    );
+    Ok(discrepancies)
+}
+
+/// Limit tagged `log_*!` output to `modules` (e.g. `["loop_worker"]`); `None` enables all.
+#[tauri::command]
+fn set_log_modules(modules: Option<Vec<String>>) -> Result<(), String> {
+    utils::logging::set_enabled_modules(modules);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_log_modules() -> Result<Option<Vec<String>>, String> {
+    Ok(utils::logging::enabled_modules())
+}
+
+/// Recent log lines kept in memory, for the diagnostics screen. `level_filter` is a
+/// `log` level name such as `"warn"`; defaults to every buffered line.
+#[tauri::command]
+fn get_recent_logs(level_filter: Option<String>) -> Result<Vec<String>, String> {
+    let level_filter = match level_filter {
+        Some(level) => level
+            .parse::<log::LevelFilter>()
+            .map_err(|_| format!("Unknown log level: {}", level))?,
+        None => log::LevelFilter::Trace,
+    };
+    Ok(utils::logging::recent_logs(level_filter))
+}
+
+#[tauri::command]
+fn get_recovery_grace(state: State<AppState>) -> Result<u64, String> {
+    Ok(state.settings.recovery_grace_secs())
+}
+
+#[tauri::command]
+fn set_recovery_grace(
+    secs: u64,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_recovery_grace_secs(secs)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("recovery-grace-updated", secs)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_max_stopwatch_duration(state: State<AppState>) -> Result<u64, String> {
+    Ok(state.settings.max_stopwatch_secs())
+}
+
+/// Takes effect from the next timer start.
+#[tauri::command]
+fn set_max_stopwatch_duration(
+    secs: u64,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_max_stopwatch_secs(secs)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("max-stopwatch-duration-updated", secs)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_scattered_threshold(state: State<AppState>) -> Result<f64, String> {
+    Ok(state.settings.scattered_interruptions_per_hour())
+}
+
+/// Interruptions per active hour above which a session summary is flagged `scattered`.
+#[tauri::command]
+fn set_scattered_threshold(
+    per_hour: f64,
+    state: State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    state
+        .settings
+        .update_scattered_interruptions_per_hour(per_hour)
+        .map_err(|e| e.to_string())?;
+
+    app_handle
+        .emit("scattered-threshold-updated", per_hour)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn get_startup_recovery(state: State<AppState>) -> Result<Option<StartupRecovery>, String> {
+    Ok(state.startup_recovery.clone())
+}
+
+#[tauri::command]
+fn export_settings(state: State<AppState>) -> Result<String, String> {
+    state.settings.export_json().map_err(|e| e.to_string())
+}
+
+/// Export every user table as one JSON bundle; raw context readings only when
+/// `include_readings` is set, since they make up most of the database.
+#[tauri::command]
+async fn export_all_data(
+    state: State<'_, AppState>,
+    include_readings: Option<bool>,
+) -> Result<String, String> {
+    state
+        .db
+        .export_all_data(include_readings.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a bundle from `export_all_data` into an empty database.
+#[tauri::command]
+async fn import_all_data(state: State<'_, AppState>, json: String) -> Result<(), String> {
+    state
+        .db
+        .import_all_data(&json)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Like `set_sensing_config`, an import that changes the sensing config is rejected
+/// unless the timer is idle.
+#[tauri::command]
+async fn import_settings(
+    json: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let settings = &state.settings;
+    let sensing_locked = state.timer.get_state().await.status != TimerStatus::Idle;
+    settings
+        .import_json(&json, sensing_locked)
+        .map_err(|e| e.to_string())?;
+
+    if let Err(err) = state.audio.set_output_device(settings.audio_output_device()) {
+        warn!("Failed to apply imported audio device: {err}");
+    }
+    if let Err(err) = state.audio.set_sample_rate(settings.audio_sample_rate()) {
+        warn!("Failed to apply imported audio sample rate: {err}");
+    }
+
+    // Re-broadcast every section so open windows pick up the imported values
+    let island_sound = settings.island_sound();
+    let island_visible = settings.island_visible();
+
+    #[cfg(target_os = "macos")]
+    {
+        macos_bridge::island_update_chime_preferences(
+            island_sound.enabled,
+            island_sound.sound_id.as_str(),
+        );
+        macos_bridge::island_set_visible(island_visible);
+    }
+
+    app_handle
+        .emit("island-sound-settings-updated", &island_sound)
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("island-visible-updated", island_visible)
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("appearance-settings-updated", settings.appearance())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("exclude-system-ui-updated", settings.exclude_system_ui())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("auto-label-enabled-updated", settings.auto_label_enabled())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("sensing-config-updated", settings.sensing_config())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit(
+            "session-notifications-enabled-updated",
+            settings.session_notifications_enabled(),
+        )
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("sense-during-breaks-updated", settings.sense_during_breaks())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("recovery-grace-updated", settings.recovery_grace_secs())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("max-stopwatch-duration-updated", settings.max_stopwatch_secs())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("distraction-list-updated", settings.distraction_list())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit("daily-goal-updated", settings.daily_goal_minutes())
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit(
+            "scattered-threshold-updated",
+            settings.scattered_interruptions_per_hour(),
+        )
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .emit(
+            "active-requires-interaction-updated",
+            settings.active_requires_interaction(),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Derive colors for apps stored with an icon but no color, emitting
+/// `app-colors-progress` after each app.
+#[tauri::command]
+async fn recompute_app_colors(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<ColorBackfillProgress, String> {
+    sensing::recompute_app_colors(state.db.clone(), move |progress| {
+        if let Err(err) = app_handle.emit("app-colors-progress", progress) {
+            warn!("Failed to emit app-colors-progress: {err}");
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Re-fetch one app's icon and color, replacing whatever is stored, and emit
+/// `app-icon-updated`. Errors if the app can't be resolved.
+#[tauri::command]
+async fn refresh_app_icon(
+    bundle_id: String,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<AppIconUpdate, String> {
+    let update = sensing::refresh_app_icon(&state.db, &bundle_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Err(err) = app_handle.emit("app-icon-updated", &update) {
+        warn!("Failed to emit app-icon-updated: {err}");
+    }
+
+    Ok(update)
+}
+
+/// Every app the user has ever used, from the `apps` table, with lifetime usage.
+#[tauri::command]
+async fn list_known_apps(state: State<'_, AppState>) -> Result<Vec<KnownApp>, String> {
+    state.db.list_known_apps().await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn check_screen_recording_permissions() -> Result<bool, String> {
     #[cfg(target_os = "macos")]
@@ -332,6 +1042,11 @@ fn open_automation_settings() -> Result<(), String> {
 fn restart_app_instance(app_handle: tauri::AppHandle) -> Result<(), String> {
     let current_exe = env::current_exe().map_err(|e| format!("Failed to locate executable: {e}"))?;
 
+    // The new instance reads settings from disk, so skip the persist debounce
+    if let Some(state) = app_handle.try_state::<AppState>() {
+        state.settings.flush().map_err(|e| e.to_string())?;
+    }
+
     Command::new(&current_exe)
         .spawn()
         .map_err(|e| format!("Failed to relaunch LeFocus: {e}"))?;
@@ -340,17 +1055,214 @@ fn restart_app_instance(app_handle: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaInfo {
+    current_version: i32,
+    expected_version: i32,
+    up_to_date: bool,
+    /// The database came from a newer build and was opened read-only.
+    read_only: bool,
+}
+
+async fn schema_info(db: &Database) -> Result<SchemaInfo, String> {
+    let current_version = db.schema_version().await.map_err(|e| e.to_string())?;
+    Ok(SchemaInfo {
+        current_version,
+        expected_version: db::CURRENT_SCHEMA_VERSION,
+        up_to_date: current_version == db::CURRENT_SCHEMA_VERSION,
+        read_only: db.is_read_only(),
+    })
+}
+
+#[tauri::command]
+async fn get_schema_info(state: State<'_, AppState>) -> Result<SchemaInfo, String> {
+    schema_info(&state.db).await
+}
+
+/// Version details for bug reports.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildInfo {
+    version: &'static str,
+    /// Short git commit hash, or `"unknown"` when built outside a git checkout
+    commit: &'static str,
+    build_time: Option<chrono::DateTime<chrono::Utc>>,
+    schema_version: i32,
+}
+
+#[tauri::command]
+fn get_build_info() -> Result<BuildInfo, String> {
+    let build_time = env!("BUILD_TIME")
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+
+    Ok(BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        commit: env!("GIT_HASH"),
+        build_time,
+        schema_version: db::CURRENT_SCHEMA_VERSION,
+    })
+}
+
+/// Where the database lives and how much disk it takes, for backup and support UIs.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DatabaseInfo {
+    path: String,
+    size_bytes: u64,
+    /// Size of the `-wal` sidecar; 0 when it doesn't exist (e.g. after a checkpoint)
+    wal_size_bytes: u64,
+}
+
+#[tauri::command]
+fn get_database_info(state: State<AppState>) -> Result<DatabaseInfo, String> {
+    let path = state
+        .db
+        .path()
+        .ok_or_else(|| "Database is in memory and has no file".to_string())?;
+    let size_bytes = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+
+    let mut wal_path = path.as_os_str().to_owned();
+    wal_path.push("-wal");
+    let wal_size_bytes = match std::fs::metadata(&wal_path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(format!("Failed to read WAL file: {}", e)),
+    };
+
+    Ok(DatabaseInfo {
+        path: path.display().to_string(),
+        size_bytes,
+        wal_size_bytes,
+    })
+}
+
+/// Directory holding the database, `settings.json` and metrics, for bug reports.
+#[tauri::command]
+fn get_app_data_dir(app_handle: tauri::AppHandle) -> Result<String, String> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.display().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Open the app data directory in Finder (or the platform's file browser).
+#[tauri::command]
+fn reveal_app_data_dir(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let dir = get_app_data_dir(app_handle.clone())?;
+    app_handle
+        .opener()
+        .open_path(dir.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", dir, e))
+}
+
+/// Every subsystem's health in one payload, for the diagnostics screen and support.
+/// Probe failures are reported in the relevant field rather than failing the call.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemHealth {
+    schema: Option<SchemaInfo>,
+    /// `PRAGMA quick_check` passed
+    database_ok: bool,
+    /// `"ok"`, the first integrity problem, or why a probe failed
+    database_detail: String,
+    screen_recording_permission: bool,
+    accessibility_permission: bool,
+    audio_device_available: bool,
+    audio_default_device: Option<String>,
+    audio_error: Option<String>,
+    sensing: SensingStatus,
+    startup_recovery: Option<StartupRecovery>,
+}
+
+#[tauri::command]
+async fn get_system_health(state: State<'_, AppState>) -> Result<SystemHealth, String> {
+    let (schema, schema_error) = match schema_info(&state.db).await {
+        Ok(info) => (Some(info), None),
+        Err(err) => (None, Some(err)),
+    };
+    let (database_ok, database_detail) = match (schema_error, state.db.quick_check().await) {
+        (Some(err), _) => (false, err),
+        (None, Ok(result)) => (result == "ok", result),
+        (None, Err(err)) => (false, err.to_string()),
+    };
+
+    let (audio_default_device, audio_error) = match audio::device::list_output_devices() {
+        Ok(devices) => (
+            devices.into_iter().find(|d| d.is_default).map(|d| d.name),
+            None,
+        ),
+        Err(err) => (None, Some(err.to_string())),
+    };
+
+    Ok(SystemHealth {
+        schema,
+        database_ok,
+        database_detail,
+        screen_recording_permission: check_screen_recording_permissions()?,
+        accessibility_permission: check_accessibility_permissions()?,
+        audio_device_available: audio_default_device.is_some(),
+        audio_default_device,
+        audio_error,
+        sensing: state.timer.sensing_status().await,
+        startup_recovery: state.startup_recovery.clone(),
+    })
+}
+
 #[tauri::command]
 async fn get_metrics_snapshot(state: State<'_, AppState>) -> Result<MetricsSnapshot, String> {
     Ok(state.metrics.get_snapshot().await)
 }
 
+/// Projected CPU cost per hour of sensing at the current capture interval, based on
+/// this machine's recent captures.
+#[tauri::command]
+async fn estimate_sensing_impact(state: State<'_, AppState>) -> Result<SensingImpact, String> {
+    let capture_interval_secs = state.timer.sensing_status().await.capture_interval_secs;
+    Ok(state.metrics.estimate_impact(capture_interval_secs).await)
+}
+
+/// Capture metrics persisted for a past session, with aggregates.
+#[tauri::command]
+async fn get_session_metrics(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<SessionMetrics, String> {
+    state
+        .metrics
+        .session_metrics(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Emit `audio-level` every 100ms while the level is non-zero (plus one trailing zero).
+fn spawn_audio_level_emitter(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(100));
+        let mut last_level = 0.0f32;
+        loop {
+            interval.tick().await;
+            let level = app_handle.state::<AppState>().audio.level();
+            if level == 0.0 && last_level == 0.0 {
+                continue;
+            }
+            last_level = level;
+            if let Err(err) = app_handle.emit("audio-level", level) {
+                warn!("Failed to emit audio-level: {err}");
+            }
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize logging (reads RUST_LOG env var)
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
-        .init();
+    utils::logging::init(log::LevelFilter::Info).expect("logger initialized twice");
 
     log::info!("LeFocus starting up...");
 
@@ -365,44 +1277,36 @@ pub fn run() {
                 std::fs::create_dir_all(&app_data_dir)?;
 
                 let db_path = app_data_dir.join("lefocus.sqlite3");
-                let database = Database::new(db_path)?;
+                let database = match Database::new(db_path.clone()) {
+                    Ok(database) => database,
+                    // A downgrade left a newer schema behind; keep the app usable for
+                    // browsing and let the UI prompt for an update via get_schema_info.
+                    Err(err) if err.downcast_ref::<db::SchemaTooNewError>().is_some() => {
+                        log::error!("{err:#}; opening database read-only");
+                        Database::open_read_only(db_path)?
+                    }
+                    Err(err) => return Err(err),
+                };
 
-                // Finalize timers that were running when the app last crashed.
-                {
-                    let db_for_recovery = database.clone();
-                    tauri::async_runtime::block_on(async move {
-                        if let Some(session) = db_for_recovery.get_incomplete_session().await? {
-                            let now = Utc::now();
-                            warn!(
-                                "Recovered incomplete session {}; marking as Interrupted",
-                                session.id
-                            );
-                            db_for_recovery
-                                .mark_session_interrupted(&session.id, now)
-                                .await?;
-                        }
-                        Ok::<(), anyhow::Error>(())
-                    })?;
-                }
+                let settings_path = app_data_dir.join("settings.json");
+                let settings_store = Arc::new(SettingsStore::new(settings_path)?);
+                let initial_sound_settings = settings_store.island_sound();
 
-                let metrics_collector = MetricsCollector::new();
+                let metrics_collector = MetricsCollector::new(app_data_dir.join("metrics"));
                 let timer_controller = TimerController::new(
                     app.handle().clone(),
                     database.clone(),
                     metrics_collector.clone(),
+                    settings_store.clone(),
                 );
 
-                let settings_path = app_data_dir.join("settings.json");
-                let settings_store = SettingsStore::new(settings_path)?;
-                let initial_sound_settings = settings_store.island_sound();
-
-                app.manage(AppState {
-                    audio: AudioEngineHandle::new(),
-                    db: database,
-                    timer: timer_controller,
-                    settings: settings_store,
-                    metrics: metrics_collector,
-                });
+                let audio = AudioEngineHandle::new(app.handle().clone());
+                if let Err(err) = audio.set_output_device(settings_store.audio_output_device()) {
+                    warn!("Failed to apply saved audio device: {err}");
+                }
+                if let Err(err) = audio.set_sample_rate(settings_store.audio_sample_rate()) {
+                    warn!("Failed to apply saved audio sample rate: {err}");
+                }
 
                 // Initialize the island window on macOS to show "00:00" when idle
                 #[cfg(target_os = "macos")]
@@ -413,10 +1317,49 @@ pub fn run() {
                     macos_bridge::audio_start_monitoring();
                     macos_bridge::island_update_chime_preferences(
                         initial_sound_settings.enabled,
-                        &initial_sound_settings.sound_id,
+                        initial_sound_settings.sound_id.as_str(),
                     );
                 }
 
+                // Resume or finalize a timer that was running when the app last crashed.
+                // Runs after island init so a resumed session can drive the island.
+                let startup_recovery = if database.is_read_only() {
+                    None
+                } else {
+                    tauri::async_runtime::block_on(recover_incomplete_session(
+                        &database,
+                        &timer_controller,
+                        settings_store.recovery_grace_secs(),
+                    ))?
+                };
+                if let Some(recovery) = &startup_recovery {
+                    // The UI may not be listening yet; it can also ask via get_startup_recovery
+                    if let Err(err) = app.handle().emit(recovery.event_name(), recovery) {
+                        warn!("Failed to emit {}: {err}", recovery.event_name());
+                    }
+                }
+
+                app.manage(AppState {
+                    audio,
+                    db: database,
+                    timer: timer_controller,
+                    settings: settings_store,
+                    metrics: metrics_collector,
+                    startup_recovery,
+                });
+
+                // Segmenting a long interrupted session can take a while; do it once the
+                // app is up rather than holding launch back
+                let state = app.state::<AppState>();
+                if let Some(recovery) = state.startup_recovery.clone() {
+                    let db = state.db.clone();
+                    tauri::async_runtime::spawn(async move {
+                        segment_interrupted_session(&db, &recovery).await;
+                    });
+                }
+
+                spawn_audio_level_emitter(app.handle().clone());
+
                 Ok(())
             })();
 
@@ -424,32 +1367,109 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             start_audio,
+            append_binaural_sweep,
             stop_audio,
+            preview_audio,
             toggle_pause,
             set_volume,
+            list_audio_devices,
+            set_audio_device,
+            get_audio_sample_rate,
+            set_audio_sample_rate,
             test_get_window,
             // DEPRECATED: test_capture_screenshot, test_run_ocr - screenshot/OCR disabled
             get_timer_state,
+            get_sensing_status,
+            suspend_sensing,
+            resume_sensing,
             start_timer,
             end_timer,
             cancel_timer,
             get_segments_for_session,
+            get_low_confidence_duration,
+            correct_segment_app,
+            merge_segments,
+            split_segment,
+            add_segment_tag,
+            remove_segment_tag,
+            get_segment_tags,
+            get_segments_by_tag,
+            get_longest_segments,
+            get_session_events,
             get_interruptions_for_segment,
+            get_interruptions_for_session,
             get_window_titles_for_segment,
+            get_reading_ocr_text,
+            get_segment_timeline,
             get_app_details_in_time_range,
+            get_app_all_time_titles,
+            get_capture_gaps,
             list_sessions,
             list_sessions_paginated,
+            list_sessions_by_status,
+            get_session,
+            compare_sessions,
+            segment_unsegmented_sessions,
             create_label,
             get_labels,
             update_label,
+            get_label_defaults,
             delete_label,
             update_session_label,
+            bulk_set_session_label,
+            create_auto_label_rule,
+            get_auto_label_rules,
+            update_auto_label_rule,
+            delete_auto_label_rule,
             delete_session,
             get_island_sound_settings,
             set_island_sound_settings,
+            get_appearance,
+            set_appearance,
+            get_sensing_config,
+            set_sensing_config,
+            list_island_sounds,
             preview_island_chime,
             get_island_visible,
             set_island_visible,
+            get_exclude_system_ui,
+            set_exclude_system_ui,
+            get_auto_label_enabled,
+            set_auto_label_enabled,
+            get_session_notifications_enabled,
+            set_session_notifications_enabled,
+            get_sense_during_breaks,
+            set_sense_during_breaks,
+            get_log_modules,
+            get_recent_logs,
+            get_build_info,
+            get_database_info,
+            get_app_data_dir,
+            reveal_app_data_dir,
+            audit_active_ms,
+            set_log_modules,
+            recompute_app_colors,
+            refresh_app_icon,
+            list_known_apps,
+            get_distraction_list,
+            set_distraction_list,
+            get_active_requires_interaction,
+            set_active_requires_interaction,
+            get_daily_goal,
+            set_daily_goal,
+            get_today_progress,
+            get_focus_by_day,
+            get_recovery_grace,
+            set_recovery_grace,
+            get_startup_recovery,
+            get_max_stopwatch_duration,
+            set_max_stopwatch_duration,
+            get_scattered_threshold,
+            set_scattered_threshold,
+            export_settings,
+            import_settings,
+            export_all_data,
+            import_all_data,
         // Permission checking commands
         check_screen_recording_permissions,
         request_screen_recording_permission,
@@ -461,7 +1481,21 @@ pub fn run() {
         open_automation_settings,
         restart_app_instance,
         get_metrics_snapshot,
+        estimate_sensing_impact,
+        get_session_metrics,
+        get_schema_info,
+        get_system_health,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Settings are written on a debounce; don't lose the last changes
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    if let Err(err) = state.settings.flush() {
+                        warn!("Failed to flush settings on exit: {err:#}");
+                    }
+                }
+            }
+        });
 }
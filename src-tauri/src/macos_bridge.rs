@@ -62,6 +62,8 @@ extern "C" {
     fn macos_sensing_island_update_chime_preferences(enabled: bool, sound_id: *const c_char);
     fn macos_sensing_island_preview_chime(sound_id: *const c_char);
     fn macos_sensing_island_set_visible(visible: bool);
+    fn macos_sensing_post_notification(title: *const c_char, body: *const c_char);
+    fn macos_sensing_get_idle_seconds() -> f64;
 
     // Permission checking
     fn macos_sensing_check_screen_recording_permission() -> bool;
@@ -81,6 +83,7 @@ extern "C" {
     // fn macos_sensing_swift_get_app_icon(bundle_id: *const c_char) -> *mut c_char; // Unused - replaced by get_app_icon_and_color
     fn macos_sensing_swift_get_app_icon_and_color(bundle_id: *const c_char) -> *mut c_char;
     fn macos_sensing_swift_free_string(ptr: *mut c_char);
+    fn macos_sensing_app_display_name(bundle_id: *const c_char) -> *mut c_char;
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -218,6 +221,11 @@ pub fn audio_start_monitoring() {
     }
 }
 
+/// Seconds since the user last touched the keyboard, mouse, or trackpad.
+pub fn get_idle_seconds() -> f64 {
+    unsafe { macos_sensing_get_idle_seconds() }
+}
+
 #[cfg(target_os = "macos")]
 pub fn island_update_chime_preferences(enabled: bool, sound_id: &str) {
     unsafe {
@@ -249,6 +257,18 @@ pub fn island_set_visible(visible: bool) {
     }
 }
 
+/// Post a native user notification (e.g. when the main window is hidden at session end).
+#[cfg(target_os = "macos")]
+pub fn post_notification(title: &str, body: &str) {
+    let (Ok(c_title), Ok(c_body)) = (CString::new(title), CString::new(body)) else {
+        log::warn!("post_notification: title or body contains null byte; skipping");
+        return;
+    };
+    unsafe {
+        macos_sensing_post_notification(c_title.as_ptr(), c_body.as_ptr());
+    }
+}
+
 #[cfg(not(target_os = "macos"))]
 pub fn island_update_chime_preferences(_enabled: bool, _sound_id: &str) {}
 
@@ -258,6 +278,9 @@ pub fn island_preview_chime(_sound_id: &str) {}
 #[cfg(not(target_os = "macos"))]
 pub fn island_set_visible(_visible: bool) {}
 
+#[cfg(not(target_os = "macos"))]
+pub fn post_notification(_title: &str, _body: &str) {}
+
 // Permission checking functions
 pub fn check_screen_recording_permission() -> bool {
     unsafe {
@@ -369,7 +392,7 @@ pub fn handle_island_cancel_timer() {
         if let Some(state) = app_handle.try_state::<crate::AppState>() {
             let timer = state.timer.clone();
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = timer.cancel_timer().await {
+                if let Err(e) = timer.cancel_timer(None).await {
                     log::error!("Failed to cancel timer from island: {}", e);
                 }
             });
@@ -507,3 +530,27 @@ pub fn get_app_icon_and_color(bundle_id: &str) -> Option<(String, String)> {
         Some((icon_and_color.icon, icon_and_color.color))
     }
 }
+
+/// Localized display name of the app with `bundle_id` (e.g. "Safari" for
+/// "com.apple.Safari"). Returns None if the app is not installed.
+#[cfg(target_os = "macos")]
+pub fn get_app_display_name(bundle_id: &str) -> Option<String> {
+    unsafe {
+        let c_bundle_id = CString::new(bundle_id).ok()?;
+        let ptr = macos_sensing_app_display_name(c_bundle_id.as_ptr());
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        let name = CStr::from_ptr(ptr).to_str().ok().map(String::from);
+        macos_sensing_swift_free_string(ptr);
+
+        name.filter(|name| !name.trim().is_empty())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn get_app_display_name(_bundle_id: &str) -> Option<String> {
+    None
+}
@@ -1,7 +1,11 @@
+mod persist;
 mod types;
 
-pub use types::{CaptureMetrics, MetricsSnapshot, SystemMetrics};
+pub use types::{CaptureMetrics, MetricsSnapshot, SensingImpact, SessionMetrics, SystemMetrics};
 
+use anyhow::{Context, Result};
+use log::warn;
+use std::path::PathBuf;
 use std::sync::Arc;
 use sysinfo::{Pid, ProcessesToUpdate, System};
 use tokio::sync::Mutex;
@@ -17,12 +21,17 @@ struct MetricsState {
     capture_count: u64,
     ocr_count: u64,
     ocr_skip_count: u64,
+    capture_skip_count: u64,
     system: System,
     pid: Pid,
+    /// Directory holding one `<session_id>.jsonl` per sensed session
+    persist_dir: PathBuf,
+    /// Metrics file of the session being sensed
+    session_file: Option<PathBuf>,
 }
 
 impl MetricsCollector {
-    pub fn new() -> Self {
+    pub fn new(persist_dir: PathBuf) -> Self {
         let mut system = System::new();
         let pid = Pid::from_u32(std::process::id());
         
@@ -35,8 +44,11 @@ impl MetricsCollector {
                 capture_count: 0,
                 ocr_count: 0,
                 ocr_skip_count: 0,
+                capture_skip_count: 0,
                 system,
                 pid,
+                persist_dir,
+                session_file: None,
             })),
         }
     }
@@ -63,12 +75,20 @@ impl MetricsCollector {
         
         state.capture_count += 1;
         
-        if metrics.ocr_ms.is_some() {
+        if metrics.skipped_reason.is_some() {
+            state.capture_skip_count += 1;
+        } else if metrics.ocr_ms.is_some() {
             state.ocr_count += 1;
         } else if metrics.ocr_skipped_reason.is_some() {
             state.ocr_skip_count += 1;
         }
         
+        if let Some(path) = state.session_file.as_deref() {
+            if let Err(err) = persist::append_capture(path, &metrics) {
+                warn!("Failed to persist capture metrics: {err:#}");
+            }
+        }
+
         state.recent_captures.push(metrics);
         
         if state.recent_captures.len() > MAX_RECENT_CAPTURES {
@@ -101,19 +121,87 @@ impl MetricsCollector {
             capture_count: state.capture_count,
             ocr_count: state.ocr_count,
             ocr_skip_count: state.ocr_skip_count,
+            capture_skip_count: state.capture_skip_count,
         }
     }
 
-    pub async fn reset(&self) {
+    /// Reset live counters and start appending captures to `session_id`'s metrics file.
+    pub async fn begin_session(&self, session_id: &str) {
         let mut state = self.inner.lock().await;
         let pid = state.pid;
+        state.session_file = match persist::session_metrics_path(&state.persist_dir, session_id) {
+            Ok(path) => Some(path),
+            Err(err) => {
+                warn!("Not persisting metrics for session: {err:#}");
+                None
+            }
+        };
         state.recent_captures.clear();
         state.capture_count = 0;
         state.ocr_count = 0;
         state.ocr_skip_count = 0;
+        state.capture_skip_count = 0;
         // Re-establish baseline for CPU after reset
         state.system.refresh_processes(ProcessesToUpdate::Some(&[pid]));
     }
+
+    /// Project sensing's CPU cost per hour at `capture_interval_secs` from the recent
+    /// captures' CPU and timing.
+    pub async fn estimate_impact(&self, capture_interval_secs: u64) -> SensingImpact {
+        let snapshot = self.get_snapshot().await;
+        project_impact(
+            &snapshot.recent_captures,
+            snapshot.system.cpu_percent,
+            capture_interval_secs,
+        )
+    }
+
+    /// Replay a session's persisted captures with aggregates. Sessions sensed before
+    /// metrics were persisted (or never sensed) return an empty result.
+    pub async fn session_metrics(&self, session_id: &str) -> Result<SessionMetrics> {
+        let path = {
+            let state = self.inner.lock().await;
+            persist::session_metrics_path(&state.persist_dir, session_id)?
+        };
+        tokio::task::spawn_blocking(move || persist::read_session_metrics(&path))
+            .await
+            .context("metrics reader join failed")?
+    }
+}
+
+/// Charge each capture the CPU it used while running (its wall time at the sampled
+/// process CPU) and multiply by the captures in an hour. Skipped captures are left
+/// out; with none left, `fallback_cpu_percent` stands in and the projection is zero.
+fn project_impact(
+    recent_captures: &[CaptureMetrics],
+    fallback_cpu_percent: f32,
+    capture_interval_secs: u64,
+) -> SensingImpact {
+    let captures: Vec<&CaptureMetrics> = recent_captures
+        .iter()
+        .filter(|c| c.skipped_reason.is_none())
+        .collect();
+    let sample_count = captures.len();
+
+    let (avg_cpu_percent, avg_capture_ms) = if sample_count == 0 {
+        (fallback_cpu_percent, 0.0)
+    } else {
+        let cpu = captures.iter().map(|c| c.cpu_percent).sum::<f32>() / sample_count as f32;
+        let ms = captures.iter().map(|c| c.total_ms as f64).sum::<f64>() / sample_count as f64;
+        (cpu, ms)
+    };
+
+    let captures_per_hour = 3600.0 / capture_interval_secs.max(1) as f64;
+    let cpu_seconds_per_capture = avg_capture_ms / 1000.0 * f64::from(avg_cpu_percent) / 100.0;
+
+    SensingImpact {
+        capture_interval_secs,
+        captures_per_hour,
+        avg_cpu_percent,
+        avg_capture_ms,
+        projected_cpu_seconds_per_hour: captures_per_hour * cpu_seconds_per_capture,
+        sample_count,
+    }
 }
 
 impl Clone for MetricsCollector {
@@ -123,3 +211,54 @@ impl Clone for MetricsCollector {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn capture(total_ms: u64, cpu_percent: f32) -> CaptureMetrics {
+        CaptureMetrics {
+            timestamp: Utc::now(),
+            metadata_ms: total_ms,
+            screenshot_ms: 0,
+            screenshot_bytes: 0,
+            phash_ms: 0,
+            ocr_ms: None,
+            ocr_skipped_reason: None,
+            skipped_reason: None,
+            db_write_ms: 0,
+            total_ms,
+            cpu_percent,
+            memory_mb: 100.0,
+        }
+    }
+
+    #[test]
+    fn shorter_interval_raises_projected_cost() {
+        let captures = vec![capture(40, 50.0), capture(60, 50.0)];
+
+        let every_5s = project_impact(&captures, 0.0, 5);
+        let every_1s = project_impact(&captures, 0.0, 1);
+
+        // 50ms per capture at 50% CPU is 0.025 CPU-seconds, 720 times an hour
+        assert!((every_5s.projected_cpu_seconds_per_hour - 18.0).abs() < 1e-9);
+        assert!((every_1s.projected_cpu_seconds_per_hour - 90.0).abs() < 1e-9);
+        assert_eq!(every_5s.sample_count, 2);
+    }
+
+    #[test]
+    fn skipped_captures_are_left_out() {
+        let mut skipped = capture(5_000, 100.0);
+        skipped.skipped_reason = Some("metadata_stuck".to_string());
+
+        let impact = project_impact(&[skipped.clone()], 12.0, 5);
+        assert_eq!(impact.sample_count, 0);
+        assert_eq!(impact.avg_cpu_percent, 12.0);
+        assert_eq!(impact.projected_cpu_seconds_per_hour, 0.0);
+
+        let impact = project_impact(&[skipped, capture(50, 50.0)], 12.0, 5);
+        assert_eq!(impact.sample_count, 1);
+        assert_eq!(impact.avg_capture_ms, 50.0);
+    }
+}
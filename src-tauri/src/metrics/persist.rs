@@ -0,0 +1,106 @@
+//! Per-session capture metrics, appended as JSON lines to
+//! `<app_data>/metrics/<session_id>.jsonl` so past sessions can be analyzed.
+
+use anyhow::{bail, Context, Result};
+use log::warn;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use super::types::{CaptureMetrics, SessionMetrics, SessionMetricsSummary};
+
+/// Path of a session's metrics file. Session ids are UUIDs; anything else is rejected
+/// so a caller can't point outside `dir`.
+pub fn session_metrics_path(dir: &Path, session_id: &str) -> Result<PathBuf> {
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        bail!("invalid session id {session_id:?}");
+    }
+    Ok(dir.join(format!("{session_id}.jsonl")))
+}
+
+pub fn append_capture(path: &Path, metrics: &CaptureMetrics) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut line = serde_json::to_string(metrics)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to append to {}", path.display()))
+}
+
+/// Read a session's metrics file. A missing file yields an empty result; lines that
+/// don't parse (e.g. a write cut short by a crash) are skipped.
+pub fn read_session_metrics(path: &Path) -> Result<SessionMetrics> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+    };
+
+    let mut captures = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<CaptureMetrics>(line) {
+            Ok(metrics) => captures.push(metrics),
+            Err(err) => warn!("skipping line {} of {}: {err}", index + 1, path.display()),
+        }
+    }
+
+    let summary = summarize(&captures);
+    Ok(SessionMetrics { captures, summary })
+}
+
+fn summarize(captures: &[CaptureMetrics]) -> SessionMetricsSummary {
+    let mut totals: Vec<u64> = captures.iter().map(|c| c.total_ms).collect();
+    totals.sort_unstable();
+
+    let skipped = captures
+        .iter()
+        .filter(|c| c.skipped_reason.is_some())
+        .count();
+    let completed = captures.len() - skipped;
+    let ocr_runs = captures
+        .iter()
+        .filter(|c| c.skipped_reason.is_none() && c.ocr_ms.is_some())
+        .count();
+
+    SessionMetricsSummary {
+        capture_count: captures.len(),
+        skipped_count: skipped,
+        avg_total_ms: if totals.is_empty() {
+            0.0
+        } else {
+            totals.iter().sum::<u64>() as f64 / totals.len() as f64
+        },
+        p50_total_ms: percentile(&totals, 50.0),
+        p95_total_ms: percentile(&totals, 95.0),
+        max_total_ms: totals.last().copied().unwrap_or(0),
+        ocr_rate: if completed == 0 {
+            0.0
+        } else {
+            ocr_runs as f64 / completed as f64
+        },
+    }
+}
+
+/// Nearest-rank percentile of sorted values; 0 when empty.
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
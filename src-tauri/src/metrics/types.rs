@@ -10,12 +10,52 @@ pub struct CaptureMetrics {
     pub phash_ms: u64,
     pub ocr_ms: Option<u64>,
     pub ocr_skipped_reason: Option<String>,
+    /// Set when the whole capture was skipped (no reading persisted), as opposed
+    /// to `ocr_skipped_reason` which only covers the OCR stage.
+    pub skipped_reason: Option<String>,
     pub db_write_ms: u64,
     pub total_ms: u64,
     pub cpu_percent: f32,
     pub memory_mb: f64,
 }
 
+/// Aggregates over a session's persisted captures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMetricsSummary {
+    pub capture_count: usize,
+    pub skipped_count: usize,
+    pub avg_total_ms: f64,
+    pub p50_total_ms: u64,
+    pub p95_total_ms: u64,
+    pub max_total_ms: u64,
+    /// Share of non-skipped captures that ran OCR
+    pub ocr_rate: f64,
+}
+
+/// A past session's capture metrics, replayed from its JSONL file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMetrics {
+    pub captures: Vec<CaptureMetrics>,
+    pub summary: SessionMetricsSummary,
+}
+
+/// Projected cost of sensing at the current capture interval, from recent captures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensingImpact {
+    pub capture_interval_secs: u64,
+    pub captures_per_hour: f64,
+    /// Process CPU sampled at each recent capture, or the current reading when there
+    /// are no recent captures
+    pub avg_cpu_percent: f32,
+    /// Mean wall time of a capture
+    pub avg_capture_ms: f64,
+    /// CPU time per hour: each capture is charged its wall time at the sampled
+    /// process CPU, so a shorter interval costs proportionally more
+    pub projected_cpu_seconds_per_hour: f64,
+    /// Recent (non-skipped) captures the averages are based on
+    pub sample_count: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemMetrics {
     pub cpu_percent: f32,
@@ -29,6 +69,7 @@ pub struct MetricsSnapshot {
     pub capture_count: u64,
     pub ocr_count: u64,
     pub ocr_skip_count: u64,
+    pub capture_skip_count: u64,
 }
 
 impl Default for MetricsSnapshot {
@@ -42,6 +83,7 @@ impl Default for MetricsSnapshot {
             capture_count: 0,
             ocr_count: 0,
             ocr_skip_count: 0,
+            capture_skip_count: 0,
         }
     }
 }
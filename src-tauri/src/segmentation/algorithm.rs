@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 
 use crate::db::models::ContextReading;
 use crate::segmentation::config::SegmentationConfig;
+use crate::segmentation::summary::summarize_segment;
 
 /// A group of consecutive readings with the same bundle_id.
 #[derive(Debug, Clone)]
@@ -34,15 +35,22 @@ struct SegmentWithReadings {
 }
 
 /// Main segmentation function: transforms readings into segments.
+///
+/// `session_end` is when the session was stopped; the final segment is extended to it
+/// so the tail after the last reading isn't lost.
 pub fn segment_session(
     readings: Vec<ContextReading>,
+    session_end: Option<DateTime<Utc>>,
     config: &SegmentationConfig,
 ) -> Result<(
     Vec<crate::db::models::Segment>,
     Vec<crate::db::models::Interruption>,
 )> {
     use crate::db::models::Segment;
-    use crate::segmentation::{merge::sandwich_merge, scoring::compute_unique_phash_count};
+    use crate::segmentation::{
+        merge::{merge_to_cap, sandwich_merge},
+        scoring::compute_unique_phash_count,
+    };
 
     // Edge case: empty readings
     if readings.is_empty() {
@@ -51,14 +59,15 @@ pub fn segment_session(
 
     let session_id = readings[0].session_id.clone();
     let session_start = readings[0].timestamp;
-    let session_end = readings.last().unwrap().timestamp;
-    let session_duration = (session_end - session_start).num_seconds() as u64;
+    let last_reading_at = readings.last().unwrap().timestamp;
+    let session_duration = (last_reading_at - session_start).num_seconds() as u64;
 
     // Edge case: very short session (<30s) - create single segment
     if session_duration < config.min_segment_duration_secs {
-        return Ok(create_single_segment_for_session(
-            readings, session_id, config,
-        ));
+        let (mut segments, interruptions) =
+            create_single_segment_for_session(readings, session_id, config);
+        extend_final_segment(&mut segments, session_end);
+        return Ok((segments, interruptions));
     }
 
     // Edge case: no switches (all same bundle_id)
@@ -66,9 +75,10 @@ pub fn segment_session(
         .iter()
         .all(|r| r.window_metadata.bundle_id == readings[0].window_metadata.bundle_id);
     if all_same_bundle {
-        return Ok(create_single_segment_for_session(
-            readings, session_id, config,
-        ));
+        let (mut segments, interruptions) =
+            create_single_segment_for_session(readings, session_id, config);
+        extend_final_segment(&mut segments, session_end);
+        return Ok((segments, interruptions));
     }
 
     // Step 1: Group readings by bundle_id
@@ -86,7 +96,12 @@ pub fn segment_session(
     // Step 4: Sandwich merge
     let merge_result = sandwich_merge(segments, config);
     let mut final_segments = merge_result.segments;
-    let interruptions = merge_result.interruptions;
+    let mut interruptions = merge_result.interruptions;
+
+    // Step 4b: Fold choppy sessions down to the segment cap
+    if let Some(max_segments) = config.max_segments {
+        merge_to_cap(&mut final_segments, &mut interruptions, max_segments);
+    }
 
     // Step 5: Rebuild readings mapping for merged segments
     // After merge, we need to map readings back to segments by time range
@@ -108,28 +123,50 @@ pub fn segment_session(
             segment_readings.iter().map(|r| (*r).clone()).collect();
         let unique_phash_count = compute_unique_phash_count(&segment_readings_vec);
         segment.unique_phash_count = Some(unique_phash_count);
+        segment.avg_ocr_word_count =
+            crate::segmentation::scoring::compute_avg_ocr_word_count(&segment_readings_vec);
 
         // Update reading_count based on actual readings in this segment (accounts for merged segments)
         segment.reading_count = segment_readings.len() as i64;
 
         // Compute confidence scores
-        let (confidence, duration_score, stability_score, visual_score, ocr_score) =
-            crate::segmentation::scoring::compute_confidence(
-                segment,
-                &segment_readings_vec,
-                config,
-            );
+        let (confidence, duration_score, stability_score, visual_score, ocr_score, reading_score) =
+            crate::segmentation::scoring::compute_confidence(segment, &segment_readings_vec, config);
 
         segment.confidence = confidence;
         segment.duration_score = Some(duration_score);
         segment.stability_score = Some(stability_score);
         segment.visual_clarity_score = Some(visual_score);
         segment.ocr_quality_score = Some(ocr_score);
+        segment.reading_activity_score = Some(reading_score);
+
+        segment.segment_summary = summarize_segment(&segment_readings_vec);
     }
 
+    extend_final_segment(&mut final_segments, session_end);
+
     Ok((final_segments, interruptions))
 }
 
+/// Stretch the last segment to the session's stop time. Its duration only grows: the
+/// capture-interval padding already covers a stop shortly after the last reading.
+fn extend_final_segment(
+    segments: &mut [crate::db::models::Segment],
+    session_end: Option<DateTime<Utc>>,
+) {
+    let (Some(last), Some(session_end)) = (segments.last_mut(), session_end) else {
+        return;
+    };
+    if session_end <= last.end_time {
+        return;
+    }
+
+    last.end_time = session_end;
+    last.duration_secs = last
+        .duration_secs
+        .max((session_end - last.start_time).num_seconds());
+}
+
 /// Create a single segment for very short sessions or no-switch sessions.
 fn create_single_segment_for_session(
     readings: Vec<ContextReading>,
@@ -139,8 +176,8 @@ fn create_single_segment_for_session(
     Vec<crate::db::models::Segment>,
     Vec<crate::db::models::Interruption>,
 ) {
-    use crate::db::models::Segment;
-    use crate::segmentation::scoring::compute_unique_phash_count;
+    use crate::db::models::{Segment, SegmentType};
+    use crate::segmentation::scoring::{compute_avg_ocr_word_count, compute_unique_phash_count};
     use uuid::Uuid;
 
     if readings.is_empty() {
@@ -165,20 +202,24 @@ fn create_single_segment_for_session(
         bundle_id: first.window_metadata.bundle_id.clone(),
         app_name: Some(first.window_metadata.owner_name.clone()),
         window_title,
+        segment_type: SegmentType::Stable,
         confidence: 0.95, // High confidence for single-app session
         duration_score: None,
         stability_score: None,
         visual_clarity_score: None,
         ocr_quality_score: None,
+        reading_activity_score: None,
         reading_count: readings.len() as i64,
         unique_phash_count: Some(unique_phash_count),
-        segment_summary: None,
+        avg_ocr_word_count: compute_avg_ocr_word_count(&readings),
+        segment_summary: summarize_segment(&readings),
         icon_data_url: None, // Populated later by database query
         icon_color: None,    // Populated later by database query
+        tags: Vec::new(),
     };
 
     // Compute scores
-    let (confidence, duration_score, stability_score, visual_score, ocr_score) =
+    let (confidence, duration_score, stability_score, visual_score, ocr_score, reading_score) =
         crate::segmentation::scoring::compute_confidence(&segment, &readings, config);
 
     segment.confidence = confidence;
@@ -186,6 +227,7 @@ fn create_single_segment_for_session(
     segment.stability_score = Some(stability_score);
     segment.visual_clarity_score = Some(visual_score);
     segment.ocr_quality_score = Some(ocr_score);
+    segment.reading_activity_score = Some(reading_score);
 
     (vec![segment], Vec::new())
 }
@@ -232,7 +274,7 @@ pub fn group_readings(readings: Vec<ContextReading>) -> Vec<ReadingGroup> {
 
 /// Convert ReadingGroups to Segments with readings tracked.
 fn create_initial_segments_with_readings(groups: Vec<ReadingGroup>) -> Vec<SegmentWithReadings> {
-    use crate::db::models::Segment;
+    use crate::db::models::{Segment, SegmentType};
     use uuid::Uuid;
 
     if groups.is_empty() {
@@ -255,16 +297,20 @@ fn create_initial_segments_with_readings(groups: Vec<ReadingGroup>) -> Vec<Segme
                     bundle_id: group.bundle_id.clone(),
                     app_name: Some(group.app_name.clone()),
                     window_title,
+                    segment_type: SegmentType::Stable,
                     confidence: 0.0, // Will be computed later
                     duration_score: None,
                     stability_score: None,
                     visual_clarity_score: None,
                     ocr_quality_score: None,
+                    reading_activity_score: None,
                     reading_count: group.reading_count() as i64,
                     unique_phash_count: None, // Will be computed later
+                    avg_ocr_word_count: None, // Will be computed later
                     segment_summary: None,
                     icon_data_url: None, // Populated later by database query
                     icon_color: None,    // Populated later by database query
+                    tags: Vec::new(),
                 },
                 readings: group.readings.clone(),
             }
@@ -273,7 +319,7 @@ fn create_initial_segments_with_readings(groups: Vec<ReadingGroup>) -> Vec<Segme
 }
 
 /// Find the most common window title in a slice of readings.
-fn most_common_window_title(readings: &[ContextReading]) -> Option<String> {
+pub(crate) fn most_common_window_title(readings: &[ContextReading]) -> Option<String> {
     use std::collections::HashMap;
 
     if readings.is_empty() {
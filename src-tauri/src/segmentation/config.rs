@@ -7,11 +7,29 @@ pub struct SegmentationConfig {
     /// Sandwich merge: A→B→A where B is this short gets merged
     pub sandwich_max_duration_secs: u64,
 
+    /// Upper bound on sandwich merge passes (each pass rescans all segments)
+    pub max_merge_passes: usize,
+
+    /// Sandwiched switches shorter than this are absorbed into the parent without
+    /// being recorded as interruptions (0 records every one)
+    pub min_interruption_duration_secs: u64,
+
+    /// Cap on segments per session; above it the shortest segments are merged into
+    /// their neighbours (`None` leaves the count uncapped)
+    pub max_segments: Option<usize>,
+
     /// Confidence scoring weights
     pub weight_duration: f64,
     pub weight_stability: f64,
     pub weight_visual: f64,
     pub weight_ocr: f64,
+    /// Optional fifth factor derived from OCR word counts (0 disables it)
+    pub weight_reading_activity: f64,
+
+    /// Readings whose OCR confidence is below this are left out of the OCR quality
+    /// average, so one poor OCR of a mostly-image screen doesn't sink the segment
+    /// (0 keeps every reading)
+    pub ocr_min_confidence: f64,
 }
 
 impl Default for SegmentationConfig {
@@ -19,10 +37,15 @@ impl Default for SegmentationConfig {
         Self {
             min_segment_duration_secs: 30,
             sandwich_max_duration_secs: 12,
+            max_merge_passes: usize::MAX,
+            min_interruption_duration_secs: 0,
+            max_segments: None,
             weight_duration: 0.30,
             weight_stability: 0.40,
             weight_visual: 0.15,
             weight_ocr: 0.15,
+            weight_reading_activity: 0.0,
+            ocr_min_confidence: 0.0,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::db::models::{Interruption, Segment};
+use crate::db::models::{Interruption, Segment, SegmentType};
 use crate::segmentation::config::SegmentationConfig;
 use uuid::Uuid;
 
@@ -9,15 +9,28 @@ pub struct SandwichMergeResult {
 }
 
 /// Detect A→B→A pattern where B duration ≤ threshold and merge into A with B as interruption.
-/// Handles recursive merges for multiple brief interruptions.
+/// Handles recursive merges for multiple brief interruptions, up to `config.max_merge_passes`.
+/// B segments under `config.min_interruption_duration_secs` are absorbed without an
+/// interruption being recorded.
 pub fn sandwich_merge(
     mut segments: Vec<Segment>,
     config: &SegmentationConfig,
 ) -> SandwichMergeResult {
     let mut all_interruptions = Vec::new();
+    let mut passes: usize = 0;
 
-    // Keep merging until no more merges are possible
+    // Keep merging until no more merges are possible (or the pass cap is reached)
     loop {
+        if passes >= config.max_merge_passes {
+            log::warn!(
+                "Sandwich merge stopped after {} passes (max_merge_passes reached); {} segments remain",
+                passes,
+                segments.len()
+            );
+            break;
+        }
+        passes += 1;
+
         let mut merged = false;
         let mut result = Vec::new();
         let mut i = 0;
@@ -36,6 +49,8 @@ pub fn sandwich_merge(
                     // Merge: extend A to C's end, add B as interruption
                     let mut merged_segment = a.clone();
                     merged_segment.end_time = c.end_time;
+                    // Absorbing a brief interruption keeps the parent a stable focus block
+                    merged_segment.segment_type = SegmentType::Stable;
                     // Duration includes the capture interval after the last reading
                     const CAPTURE_INTERVAL_SECS: i64 = 5;
                     merged_segment.duration_secs =
@@ -43,19 +58,21 @@ pub fn sandwich_merge(
                     // Update reading_count to sum readings from both A and C segments
                     merged_segment.reading_count = a.reading_count + c.reading_count;
 
-                    // Create interruption from B
-                    let interruption = Interruption {
-                        id: Uuid::new_v4().to_string(),
-                        segment_id: merged_segment.id.clone(),
-                        bundle_id: b.bundle_id.clone(),
-                        app_name: b.app_name.clone(),
-                        timestamp: b.start_time,
-                        duration_secs: b.duration_secs,
-                        icon_data_url: None, // Will be populated when fetched from database
-                        icon_color: None,    // Will be populated when fetched from database
-                    };
-
-                    all_interruptions.push(interruption);
+                    // Create interruption from B, unless it's a micro-switch below the floor
+                    if b.duration_secs >= config.min_interruption_duration_secs as i64 {
+                        let interruption = Interruption {
+                            id: Uuid::new_v4().to_string(),
+                            segment_id: merged_segment.id.clone(),
+                            bundle_id: b.bundle_id.clone(),
+                            app_name: b.app_name.clone(),
+                            timestamp: b.start_time,
+                            duration_secs: b.duration_secs,
+                            icon_data_url: None, // Will be populated when fetched from database
+                            icon_color: None,    // Will be populated when fetched from database
+                        };
+
+                        all_interruptions.push(interruption);
+                    }
                     result.push(merged_segment);
                     i += 3;
                     merged = true;
@@ -79,3 +96,160 @@ pub fn sandwich_merge(
         interruptions: all_interruptions,
     }
 }
+
+/// Merge the shortest segment into its shorter neighbour until at most `max_segments`
+/// remain (never fewer than one).
+///
+/// The longer of each merged pair keeps its app; a pair spanning two apps becomes a
+/// `Transitioning` segment. Durations are summed, so the session total is unchanged,
+/// and interruptions of an absorbed segment move to the segment that absorbed it.
+pub fn merge_to_cap(
+    segments: &mut Vec<Segment>,
+    interruptions: &mut [Interruption],
+    max_segments: usize,
+) {
+    let max_segments = max_segments.max(1);
+    if segments.len() <= max_segments {
+        return;
+    }
+    let before = segments.len();
+
+    while segments.len() > max_segments {
+        let shortest = segments
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, s)| s.duration_secs)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        // Pair with whichever neighbour is shorter, so the smallest pieces go first
+        let left = if shortest == 0 {
+            0
+        } else if shortest + 1 == segments.len()
+            || segments[shortest - 1].duration_secs <= segments[shortest + 1].duration_secs
+        {
+            shortest - 1
+        } else {
+            shortest
+        };
+
+        let b = segments.remove(left + 1);
+        let a = &mut segments[left];
+        let (kept, absorbed) = if b.duration_secs > a.duration_secs {
+            let absorbed = std::mem::replace(a, b);
+            (a, absorbed)
+        } else {
+            (a, b)
+        };
+
+        if kept.bundle_id != absorbed.bundle_id {
+            kept.segment_type = SegmentType::Transitioning;
+        }
+        kept.start_time = kept.start_time.min(absorbed.start_time);
+        kept.end_time = kept.end_time.max(absorbed.end_time);
+        kept.duration_secs += absorbed.duration_secs;
+        kept.reading_count += absorbed.reading_count;
+
+        for interruption in interruptions.iter_mut() {
+            if interruption.segment_id == absorbed.id {
+                interruption.segment_id = kept.id.clone();
+            }
+        }
+    }
+
+    log::info!(
+        "Merged {} segments down to the cap of {}",
+        before,
+        segments.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{segment, t0};
+    use chrono::Duration;
+
+    /// `count` back-to-back segments alternating between two apps.
+    fn alternating(count: usize, duration_secs: impl Fn(usize) -> i64) -> Vec<Segment> {
+        let mut start = t0();
+        (0..count)
+            .map(|i| {
+                let bundle = if i % 2 == 0 { "com.a" } else { "com.b" };
+                let seg = segment("s", bundle, start, duration_secs(i));
+                start += Duration::seconds(seg.duration_secs);
+                seg
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_to_cap_holds_cap_and_conserves_duration() {
+        let mut segments = alternating(500, |i| 5 + (i as i64 * 7) % 90);
+        let total: i64 = segments.iter().map(|s| s.duration_secs).sum();
+        let mut interruptions = vec![Interruption {
+            id: "i".to_string(),
+            segment_id: segments[250].id.clone(),
+            bundle_id: "com.c".to_string(),
+            app_name: None,
+            timestamp: segments[250].start_time,
+            duration_secs: 5,
+            icon_data_url: None,
+            icon_color: None,
+        }];
+
+        merge_to_cap(&mut segments, &mut interruptions, 20);
+
+        assert_eq!(segments.len(), 20);
+        assert_eq!(segments.iter().map(|s| s.duration_secs).sum::<i64>(), total);
+        assert!(segments
+            .windows(2)
+            .all(|w| w[0].end_time <= w[1].start_time));
+        // The interruption follows its segment into whichever one absorbed it
+        assert!(segments.iter().any(|s| s.id == interruptions[0].segment_id));
+    }
+
+    #[test]
+    fn sandwich_merge_absorbs_micro_switch_below_interruption_floor() {
+        let start = t0();
+        let segments = vec![
+            segment("s", "com.a", start, 60),
+            segment("s", "com.b", start + Duration::seconds(60), 5),
+            segment("s", "com.a", start + Duration::seconds(65), 60),
+        ];
+
+        let config = SegmentationConfig {
+            min_interruption_duration_secs: 10,
+            ..SegmentationConfig::default()
+        };
+        let result = sandwich_merge(segments.clone(), &config);
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].bundle_id, "com.a");
+        assert!(result.interruptions.is_empty());
+
+        // With no floor the same switch is recorded
+        let result = sandwich_merge(segments, &SegmentationConfig::default());
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.interruptions.len(), 1);
+        assert_eq!(result.interruptions[0].bundle_id, "com.b");
+    }
+
+    #[test]
+    fn sandwich_merge_stops_at_max_merge_passes() {
+        let segments = alternating(3000, |_| 5);
+        let config = SegmentationConfig {
+            // Let every pass find merges, so only the pass cap ends the loop
+            sandwich_max_duration_secs: 1_000_000,
+            max_merge_passes: 3,
+            ..SegmentationConfig::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = sandwich_merge(segments, &config);
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+
+        // Each pass folds every A-B-A triple into one: 3000 -> 1000 -> 334 -> 112
+        assert_eq!(result.segments.len(), 112);
+        assert_eq!(result.interruptions.len(), 1000 + 333 + 111);
+    }
+}
@@ -1,7 +1,12 @@
 pub mod algorithm;
 pub mod config;
 pub mod merge;
+pub mod persist;
 pub mod scoring;
+pub mod split;
+pub mod summary;
+pub mod text_delta;
 
 pub use algorithm::segment_session;
 pub use config::SegmentationConfig;
+pub use persist::{segment_and_store, segment_unsegmented_sessions};
@@ -0,0 +1,180 @@
+//! Segmenting a stored session and saving the result.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{error, info};
+
+use crate::db::Database;
+use crate::segmentation::{segment_session, text_delta::ocr_text_deltas, SegmentationConfig};
+
+/// Segment a session's stored readings, then save the segments and interruptions and
+/// link each reading to its segment. Returns how many segments were created.
+pub async fn segment_and_store(
+    db: &Database,
+    session_id: &str,
+    stopped_at: Option<DateTime<Utc>>,
+) -> Result<usize> {
+    let readings = db
+        .get_context_readings_for_session(session_id)
+        .await
+        .context("failed to load readings for segmentation")?;
+    let text_deltas = ocr_text_deltas(&readings);
+    let (segments, interruptions) =
+        segment_session(readings, stopped_at, &SegmentationConfig::default())
+            .context("segmentation failed")?;
+
+    // Segments and interruptions go in one transaction so interruptions never
+    // reference a segment that isn't there
+    db.insert_segments_and_interruptions(session_id, &segments, &interruptions)
+        .await
+        .with_context(|| {
+            format!(
+                "failed to insert {} segments and {} interruptions",
+                segments.len(),
+                interruptions.len()
+            )
+        })?;
+
+    let segment_ranges: Vec<(String, DateTime<Utc>, DateTime<Utc>)> = segments
+        .iter()
+        .map(|s| (s.id.clone(), s.start_time, s.end_time))
+        .collect();
+    db.update_readings_with_segment_ids(session_id, &segment_ranges)
+        .await
+        .context("failed to update readings with segment_ids")?;
+
+    db.update_ocr_text_deltas(&text_deltas)
+        .await
+        .context("failed to store OCR text deltas")?;
+
+    info!(
+        "Created {} segments and {} interruptions for session {}",
+        segments.len(),
+        interruptions.len(),
+        session_id
+    );
+    Ok(segments.len())
+}
+
+/// Segment every finished session that has readings but no segments. A session
+/// that fails is logged and skipped. Returns how many sessions were segmented.
+///
+/// `timer_session_id` is the session the timer currently owns, if any. It is
+/// skipped: `end_timer` marks a session `Completed` before segmenting it, so it
+/// can look unsegmented here while the timer is still finalizing it.
+pub async fn segment_unsegmented_sessions(
+    db: &Database,
+    timer_session_id: Option<&str>,
+) -> Result<usize> {
+    let sessions = db.list_unsegmented_sessions().await?;
+
+    let mut segmented = 0;
+    for session in sessions {
+        if timer_session_id == Some(session.id.as_str()) {
+            continue;
+        }
+        match segment_and_store(db, &session.id, session.stopped_at).await {
+            Ok(_) => segmented += 1,
+            Err(err) => error!("Failed to segment session {}: {err:#}", session.id),
+        }
+    }
+    Ok(segmented)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::SegmentType;
+    use crate::db::test_support::{reading, session, t0};
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn brief_switch_is_merged_and_recorded_as_interruption() {
+        let db = Database::new_in_memory().unwrap();
+        let start = t0();
+        let stopped_at = start + Duration::seconds(45);
+        let mut record = session("session-1", start);
+        record.stopped_at = Some(stopped_at);
+        db.insert_session(&record).await.unwrap();
+
+        // A, A, B, A, A at 10s spacing: B is a single 5s reading between two A runs
+        let bundles = ["com.a", "com.a", "com.b", "com.a", "com.a"];
+        let readings: Vec<_> = bundles
+            .iter()
+            .enumerate()
+            .map(|(i, bundle)| {
+                reading(
+                    "session-1",
+                    start + Duration::seconds(i as i64 * 10),
+                    bundle,
+                )
+            })
+            .collect();
+        db.insert_context_readings_batch(&readings).await.unwrap();
+
+        let created = segment_and_store(&db, "session-1", Some(stopped_at))
+            .await
+            .unwrap();
+        assert_eq!(created, 1);
+
+        let segments = db
+            .get_segments_for_session("session-1", None)
+            .await
+            .unwrap();
+        assert_eq!(segments.len(), 1);
+        let segment = &segments[0];
+        assert_eq!(segment.bundle_id, "com.a");
+        assert_eq!(segment.segment_type, SegmentType::Stable);
+        assert_eq!(segment.start_time, start);
+        assert_eq!(segment.end_time, stopped_at);
+        assert_eq!(segment.duration_secs, 45);
+        assert_eq!(segment.reading_count, 5);
+
+        let interruptions = db
+            .get_all_interruptions_for_session("session-1")
+            .await
+            .unwrap();
+        assert_eq!(interruptions.len(), 1);
+        assert_eq!(interruptions[0].segment_id, segment.id);
+        assert_eq!(interruptions[0].bundle_id, "com.b");
+        assert_eq!(interruptions[0].timestamp, start + Duration::seconds(20));
+        assert_eq!(interruptions[0].duration_secs, 5);
+
+        let stored = db
+            .get_context_readings_for_session("session-1")
+            .await
+            .unwrap();
+        assert!(stored
+            .iter()
+            .all(|r| r.segment_id.as_deref() == Some(segment.id.as_str())));
+    }
+
+    #[tokio::test]
+    async fn unsegmented_sweep_skips_the_timer_session() {
+        let db = Database::new_in_memory().unwrap();
+        for (i, id) in ["done", "finalizing"].iter().enumerate() {
+            let start = t0() + Duration::hours(i as i64 * 2);
+            db.insert_session(&session(id, start)).await.unwrap();
+            db.insert_context_readings_batch(&[reading(id, start, "com.a")])
+                .await
+                .unwrap();
+        }
+
+        let segmented = segment_unsegmented_sessions(&db, Some("finalizing"))
+            .await
+            .unwrap();
+        assert_eq!(segmented, 1);
+        assert_eq!(
+            db.get_segments_for_session("done", None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(db
+            .get_segments_for_session("finalizing", None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
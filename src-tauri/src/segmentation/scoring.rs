@@ -2,21 +2,27 @@ use crate::db::models::{ContextReading, Segment};
 use crate::segmentation::config::SegmentationConfig;
 use std::collections::HashSet;
 
-/// Compute confidence score using 4-factor weighted average.
+/// Word count at which the reading activity level term reaches 0.5.
+const READING_ACTIVITY_HALF_WORDS: f64 = 150.0;
+
+/// Compute confidence score using a weighted average of the scoring factors.
+/// The reading activity factor only contributes when `weight_reading_activity` > 0.
 pub fn compute_confidence(
     segment: &Segment,
     readings: &[ContextReading],
     config: &SegmentationConfig,
-) -> (f64, f64, f64, f64, f64) {
+) -> (f64, f64, f64, f64, f64, f64) {
     let duration_score = score_duration(segment.duration_secs);
     let stability_score = score_stability(segment, readings);
     let visual_score = score_visual_clarity(segment);
-    let ocr_score = score_ocr_quality(segment, readings);
+    let ocr_score = score_ocr_quality(segment, readings, config.ocr_min_confidence);
+    let reading_activity_score = score_reading_activity(readings);
 
     let confidence = config.weight_duration * duration_score
         + config.weight_stability * stability_score
         + config.weight_visual * visual_score
-        + config.weight_ocr * ocr_score;
+        + config.weight_ocr * ocr_score
+        + config.weight_reading_activity * reading_activity_score;
 
     (
         confidence,
@@ -24,6 +30,7 @@ pub fn compute_confidence(
         stability_score,
         visual_score,
         ocr_score,
+        reading_activity_score,
     )
 }
 
@@ -63,7 +70,8 @@ fn score_visual_clarity(segment: &Segment) -> f64 {
 }
 
 /// Score OCR quality: Average OCR confidence from readings, default 0.5 if None.
-fn score_ocr_quality(_segment: &Segment, readings: &[ContextReading]) -> f64 {
+/// Confidences below `min_confidence` are ignored; if none qualify the default applies.
+fn score_ocr_quality(_segment: &Segment, readings: &[ContextReading], min_confidence: f64) -> f64 {
     if readings.is_empty() {
         return 0.5; // Default if no readings
     }
@@ -72,7 +80,10 @@ fn score_ocr_quality(_segment: &Segment, readings: &[ContextReading]) -> f64 {
     let mut count = 0;
 
     for reading in readings {
-        if let Some(confidence) = reading.ocr_confidence {
+        if let Some(confidence) = reading
+            .ocr_confidence
+            .filter(|confidence| *confidence >= min_confidence)
+        {
             total_confidence += confidence;
             count += 1;
         }
@@ -85,6 +96,42 @@ fn score_ocr_quality(_segment: &Segment, readings: &[ContextReading]) -> f64 {
     }
 }
 
+/// Score reading activity from OCR word counts, default 0.5 if None.
+/// Sustained text-heavy screens score high: a saturating level term on the mean word count
+/// is scaled by consistency (1 - coefficient of variation), so erratic counts score lower.
+fn score_reading_activity(readings: &[ContextReading]) -> f64 {
+    let counts: Vec<f64> = readings
+        .iter()
+        .filter_map(|r| r.ocr_word_count)
+        .map(|count| count as f64)
+        .collect();
+
+    if counts.is_empty() {
+        return 0.5; // Default if no OCR data
+    }
+
+    let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+    if mean <= 0.0 {
+        return 0.0;
+    }
+
+    let variance = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+    let coefficient_of_variation = variance.sqrt() / mean;
+
+    let level = mean / (mean + READING_ACTIVITY_HALF_WORDS);
+    let consistency = 1.0 - coefficient_of_variation.min(1.0);
+    level * consistency
+}
+
+/// Mean `ocr_word_count` over the readings that have one, or `None` if none do.
+pub fn compute_avg_ocr_word_count(readings: &[ContextReading]) -> Option<f64> {
+    let counts: Vec<u64> = readings.iter().filter_map(|r| r.ocr_word_count).collect();
+    if counts.is_empty() {
+        return None;
+    }
+    Some(counts.iter().sum::<u64>() as f64 / counts.len() as f64)
+}
+
 /// Count unique pHash values in a slice of readings.
 pub fn compute_unique_phash_count(readings: &[ContextReading]) -> i64 {
     let mut unique_phashes = HashSet::new();
@@ -95,3 +142,35 @@ pub fn compute_unique_phash_count(readings: &[ContextReading]) -> i64 {
     }
     unique_phashes.len() as i64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{reading, segment, t0};
+    use chrono::Duration;
+
+    fn ocr_score(readings: &[ContextReading], ocr_min_confidence: f64) -> f64 {
+        let config = SegmentationConfig {
+            ocr_min_confidence,
+            ..SegmentationConfig::default()
+        };
+        compute_confidence(&segment("s", "com.a", t0(), 60), readings, &config).4
+    }
+
+    #[test]
+    fn ocr_quality_ignores_readings_below_min_confidence() {
+        let readings: Vec<ContextReading> = [Some(0.9), Some(0.1), Some(0.7), None]
+            .into_iter()
+            .enumerate()
+            .map(|(i, confidence)| ContextReading {
+                ocr_confidence: confidence,
+                ..reading("s", t0() + Duration::seconds(i as i64 * 5), "com.a")
+            })
+            .collect();
+
+        assert!((ocr_score(&readings, 0.0) - (0.9 + 0.1 + 0.7) / 3.0).abs() < 1e-9);
+        assert!((ocr_score(&readings, 0.5) - 0.8).abs() < 1e-9);
+        // Nothing qualifies, so the no-OCR default applies
+        assert_eq!(ocr_score(&readings, 0.95), 0.5);
+    }
+}
@@ -0,0 +1,85 @@
+//! Manual segment splitting: cut one segment in two at a user-chosen timestamp.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::models::{ContextReading, Segment};
+use crate::segmentation::{
+    algorithm::most_common_window_title,
+    config::SegmentationConfig,
+    scoring::{compute_avg_ocr_word_count, compute_confidence, compute_unique_phash_count},
+    summary::summarize_segment,
+};
+
+/// Split `segment` at `at` into children covering `[start, at)` and `[at, end]`.
+///
+/// `readings` are the segment's readings; each child keeps those before / from `at`
+/// and has its counts, title, summary, and scores recomputed from them. The
+/// children's durations add up to the original's. Callers must ensure `at` lies
+/// strictly inside the segment.
+pub fn split_segment(
+    segment: &Segment,
+    at: DateTime<Utc>,
+    readings: &[ContextReading],
+    config: &SegmentationConfig,
+) -> (Segment, Segment) {
+    let split_index = readings.partition_point(|r| r.timestamp < at);
+    let (before, after) = readings.split_at(split_index);
+
+    let first_duration = (at - segment.start_time).num_seconds();
+    let second_duration =
+        (segment.duration_secs - first_duration).max((segment.end_time - at).num_seconds());
+
+    let first = child_segment(
+        segment,
+        segment.start_time,
+        at,
+        first_duration,
+        before,
+        config,
+    );
+    let second = child_segment(
+        segment,
+        at,
+        segment.end_time,
+        second_duration,
+        after,
+        config,
+    );
+    (first, second)
+}
+
+fn child_segment(
+    parent: &Segment,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    duration_secs: i64,
+    readings: &[ContextReading],
+    config: &SegmentationConfig,
+) -> Segment {
+    let mut child = Segment {
+        id: Uuid::new_v4().to_string(),
+        start_time,
+        end_time,
+        duration_secs,
+        window_title: most_common_window_title(readings).or_else(|| parent.window_title.clone()),
+        reading_count: readings.len() as i64,
+        unique_phash_count: Some(compute_unique_phash_count(readings)),
+        avg_ocr_word_count: compute_avg_ocr_word_count(readings),
+        segment_summary: summarize_segment(readings),
+        icon_data_url: None,
+        icon_color: None,
+        ..parent.clone()
+    };
+
+    let (confidence, duration_score, stability_score, visual_score, ocr_score, reading_score) =
+        compute_confidence(&child, readings, config);
+
+    child.confidence = confidence;
+    child.duration_score = Some(duration_score);
+    child.stability_score = Some(stability_score);
+    child.visual_clarity_score = Some(visual_score);
+    child.ocr_quality_score = Some(ocr_score);
+    child.reading_activity_score = Some(reading_score);
+    child
+}
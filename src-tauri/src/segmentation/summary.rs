@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::db::models::ContextReading;
+
+/// Number of keywords included in a summary.
+const MAX_KEYWORDS: usize = 5;
+/// Hard cap on summary length, in characters.
+const MAX_SUMMARY_CHARS: usize = 120;
+/// Shorter tokens are mostly OCR noise or fragments.
+const MIN_KEYWORD_CHARS: usize = 3;
+
+const STOPWORDS: &[&str] = &[
+    "about", "after", "all", "also", "and", "any", "are", "but", "can", "could", "did", "does",
+    "for", "from", "had", "has", "have", "her", "his", "how", "into", "its", "just", "like",
+    "more", "new", "not", "now", "one", "only", "our", "out", "over", "she", "should", "some",
+    "than", "that", "the", "their", "them", "then", "there", "these", "they", "this", "those",
+    "use", "was", "were", "what", "when", "where", "which", "who", "why", "will", "with",
+    "would", "you", "your",
+];
+
+/// Summarize a segment as its most frequent OCR keywords, e.g. `"invoice, budget, q3"`.
+///
+/// Keywords are ranked by term frequency across all readings after stopword removal,
+/// with ties broken alphabetically so the output is deterministic. Returns `None` when
+/// the readings carry no usable OCR text.
+pub fn summarize_segment(readings: &[ContextReading]) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for text in readings.iter().filter_map(|r| r.ocr_text.as_deref()) {
+        for token in text.split(|c: char| !c.is_alphanumeric()) {
+            if token.chars().count() < MIN_KEYWORD_CHARS
+                || !token.chars().any(|c| c.is_alphabetic())
+            {
+                continue;
+            }
+            let word = token.to_lowercase();
+            if STOPWORDS.contains(&word.as_str()) {
+                continue;
+            }
+            *counts.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|(a_word, a_count), (b_word, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_word.cmp(b_word))
+    });
+
+    let mut summary = String::new();
+    for (word, _) in ranked.into_iter().take(MAX_KEYWORDS) {
+        let separator = if summary.is_empty() { "" } else { ", " };
+        if summary.chars().count() + separator.len() + word.chars().count() > MAX_SUMMARY_CHARS {
+            break;
+        }
+        summary.push_str(separator);
+        summary.push_str(&word);
+    }
+
+    if summary.is_empty() {
+        None
+    } else {
+        Some(summary)
+    }
+}
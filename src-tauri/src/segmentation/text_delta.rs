@@ -0,0 +1,40 @@
+//! Textual change between consecutive OCR readings, complementing pHash's visual change.
+
+use std::collections::HashSet;
+
+use crate::db::models::ContextReading;
+
+fn word_set(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Jaccard distance between the word sets of two texts: 0.0 when they share every
+/// word, 1.0 when they share none. Two texts without words count as unchanged.
+pub fn ocr_text_delta(previous: &str, current: &str) -> f64 {
+    let previous = word_set(previous);
+    let current = word_set(current);
+
+    let union = previous.union(&current).count();
+    if union == 0 {
+        return 0.0;
+    }
+    let shared = previous.intersection(&current).count();
+    1.0 - shared as f64 / union as f64
+}
+
+/// `(reading_id, delta)` for each stored reading whose previous reading (by time)
+/// also has OCR text. Readings must be in timestamp order.
+pub fn ocr_text_deltas(readings: &[ContextReading]) -> Vec<(i64, f64)> {
+    readings
+        .windows(2)
+        .filter_map(|pair| {
+            let id = pair[1].id?;
+            let previous = pair[0].ocr_text.as_deref()?;
+            let current = pair[1].ocr_text.as_deref()?;
+            Some((id, ocr_text_delta(previous, current)))
+        })
+        .collect()
+}
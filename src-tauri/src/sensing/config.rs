@@ -0,0 +1,67 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Largest capture jitter allowed. Keeps the longest jittered interval (1.5x) under
+/// the 2x threshold `get_capture_gaps` treats as missing data.
+pub const MAX_JITTER_PERCENT: u32 = 50;
+
+/// Default cap on stored OCR text per reading (roughly a screenful of prose).
+pub const DEFAULT_MAX_OCR_CHARS: u32 = 4000;
+
+/// Longest system UI debounce allowed, in captures (a minute at the 5s interval).
+pub const MAX_SYSTEM_UI_DEBOUNCE_CAPTURES: u32 = 12;
+
+/// Tuning for the sensing loop. The OCR fields (`max_ocr_chars`, `redact_ocr`) are
+/// stored and validated but have no effect while screenshot/OCR capture is disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensingConfig {
+    /// Randomize each capture interval by up to ±this percent so captures don't
+    /// phase-lock with periodic animations. Segmentation still credits each reading
+    /// with the nominal 5s; the jitter averages out over a segment, but individual
+    /// durations can be off by up to this fraction of one interval. 0 disables jitter.
+    pub jitter_percent: u32,
+    /// OCR text longer than this is truncated on a word boundary before it is stored.
+    /// `ocr_word_count` still reflects the full text.
+    pub max_ocr_chars: u32,
+    /// Redact emails, URLs, and long numbers from OCR text before it is stored.
+    pub redact_ocr: bool,
+    /// System UI ("com.apple.system") readings are only recorded once system UI stays
+    /// in front for more than this many consecutive captures, so notification banners
+    /// and Spotlight don't split segments. 0 records every system reading.
+    pub system_ui_debounce_captures: u32,
+}
+
+impl Default for SensingConfig {
+    fn default() -> Self {
+        Self {
+            jitter_percent: 0,
+            max_ocr_chars: DEFAULT_MAX_OCR_CHARS,
+            redact_ocr: false,
+            system_ui_debounce_captures: 1,
+        }
+    }
+}
+
+impl SensingConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.jitter_percent > MAX_JITTER_PERCENT {
+            bail!(
+                "Capture jitter {}% exceeds the maximum of {}%",
+                self.jitter_percent,
+                MAX_JITTER_PERCENT
+            );
+        }
+        if self.max_ocr_chars == 0 {
+            bail!("max_ocr_chars must be greater than zero");
+        }
+        if self.system_ui_debounce_captures > MAX_SYSTEM_UI_DEBOUNCE_CAPTURES {
+            bail!(
+                "System UI debounce of {} captures exceeds the maximum of {}",
+                self.system_ui_debounce_captures,
+                MAX_SYSTEM_UI_DEBOUNCE_CAPTURES
+            );
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,37 @@
 use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
 use log::info;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::Emitter;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
 use crate::db::Database;
 use crate::macos_bridge;
 use crate::metrics::MetricsCollector;
+use crate::settings::SettingsStore;
 
 use super::icon_manager::IconManager;
-use super::loop_worker::sensing_loop;
+use super::loop_worker::{sensing_loop, CAPTURE_INTERVAL_SECS, OCR_ENABLED};
+use super::status::{SensingState, SensingStatus, SensingStatusHandle};
+
+/// Payload of `sensing-suspended` and `sensing-resumed`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SensingSuspensionEvent {
+    session_id: Option<String>,
+    at: DateTime<Utc>,
+}
 
 pub struct SensingController {
     handle: Option<JoinHandle<()>>,
     cancel_token: Option<CancellationToken>,
+    status: SensingStatusHandle,
+    app_handle: Option<tauri::AppHandle>,
+    /// Set while the user has paused captures; the loop keeps running and skips them
+    suspended: Arc<AtomicBool>,
 }
 
 impl SensingController {
@@ -20,15 +39,23 @@ impl SensingController {
         Self {
             handle: None,
             cancel_token: None,
+            status: SensingStatusHandle::new(CAPTURE_INTERVAL_SECS, OCR_ENABLED),
+            app_handle: None,
+            suspended: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    pub fn status(&self) -> SensingStatus {
+        self.status.snapshot()
+    }
+
     pub async fn start_sensing(
         &mut self,
         session_id: String,
         db: Database,
         metrics: MetricsCollector,
         app_handle: tauri::AppHandle,
+        settings: Arc<SettingsStore>,
     ) -> Result<()> {
         if self.handle.is_some() {
             bail!("sensing already active");
@@ -40,7 +67,7 @@ impl SensingController {
         macos_bridge::clear_cache();
 
         // Reset metrics for new session
-        metrics.reset().await;
+        metrics.begin_session(&session_id).await;
 
         // Create icon manager for pre-fetching icons during the session
         let icon_manager = IconManager::new(db.clone());
@@ -49,6 +76,10 @@ impl SensingController {
         let cancel_token = CancellationToken::new();
         let token_clone = cancel_token.clone();
 
+        self.status.start(session_id.clone(), &app_handle);
+        self.app_handle = Some(app_handle.clone());
+        self.suspended.store(false, Ordering::SeqCst);
+
         let handle = tokio::spawn(sensing_loop(
             session_id,
             db,
@@ -56,6 +87,9 @@ impl SensingController {
             token_clone,
             metrics,
             app_handle,
+            self.status.clone(),
+            settings,
+            self.suspended.clone(),
         ));
 
         self.handle = Some(handle);
@@ -67,14 +101,57 @@ impl SensingController {
         if let Some(token) = self.cancel_token.take() {
             token.cancel();
         }
+        self.suspended.store(false, Ordering::SeqCst);
 
-        if let Some(handle) = self.handle.take() {
+        let result = if let Some(handle) = self.handle.take() {
             handle
                 .await
                 .context("sensing loop task failed to join")
                 .map(|_| ())
         } else {
             Ok(())
+        };
+
+        if let Some(app_handle) = self.app_handle.as_ref() {
+            self.status.set_state(SensingState::Stopped, app_handle);
         }
+
+        result
+    }
+
+    /// Pause captures without ending the session. Unlike `stop_sensing`, the loop and
+    /// its caches stay alive; each skipped capture is recorded as `suspended`.
+    pub fn suspend(&self) -> Result<()> {
+        self.set_suspended(true)
+    }
+
+    /// Resume captures paused by `suspend`.
+    pub fn resume(&self) -> Result<()> {
+        self.set_suspended(false)
+    }
+
+    fn set_suspended(&self, suspended: bool) -> Result<()> {
+        let (Some(_), Some(app_handle)) = (self.handle.as_ref(), self.app_handle.as_ref()) else {
+            bail!("sensing is not active");
+        };
+        if self.suspended.swap(suspended, Ordering::SeqCst) == suspended {
+            return Ok(());
+        }
+
+        let (state, event) = if suspended {
+            (SensingState::Suspended, "sensing-suspended")
+        } else {
+            (SensingState::Running, "sensing-resumed")
+        };
+        self.status.set_state(state, app_handle);
+
+        let payload = SensingSuspensionEvent {
+            session_id: self.status.snapshot().session_id,
+            at: Utc::now(),
+        };
+        if let Err(err) = app_handle.emit(event, payload) {
+            log::warn!("Failed to emit {event}: {err}");
+        }
+        Ok(())
     }
 }
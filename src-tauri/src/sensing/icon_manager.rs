@@ -1,7 +1,75 @@
 use crate::db::Database;
+use crate::utils::color::compute_dominant_color;
+use serde::Serialize;
 use std::collections::HashSet;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+
+/// Swift icon lookups allowed at once, shared by session prefetch, the session-end
+/// fetch and color backfill
+const ICON_FETCH_CONCURRENCY: usize = 4;
+
+/// Blocking lookup of an app's icon data URL and dominant color.
+type IconLookup = Arc<dyn Fn(&str) -> Option<(String, String)> + Send + Sync>;
+
+static SHARED_ICON_FETCHER: OnceLock<IconFetcher> = OnceLock::new();
+
+/// Runs icon lookups on the blocking pool, at most `ICON_FETCH_CONCURRENCY` at once,
+/// so bursts of new apps don't pile up concurrent FFI calls.
+pub struct IconFetcher {
+    permits: Semaphore,
+    lookup: IconLookup,
+}
+
+impl IconFetcher {
+    fn new(lookup: IconLookup) -> Self {
+        Self {
+            permits: Semaphore::new(ICON_FETCH_CONCURRENCY),
+            lookup,
+        }
+    }
+
+    /// The app-wide fetcher backed by the Swift bridge.
+    pub fn shared() -> &'static IconFetcher {
+        SHARED_ICON_FETCHER
+            .get_or_init(|| Self::new(Arc::new(crate::macos_bridge::get_app_icon_and_color)))
+    }
+
+    /// Wait for a permit, then look up `bundle_id`'s icon and color.
+    pub async fn fetch(&self, bundle_id: &str) -> Option<(String, String)> {
+        let _permit = self.permits.acquire().await.ok()?;
+        let lookup = Arc::clone(&self.lookup);
+        let bundle_id = bundle_id.to_string();
+        tokio::task::spawn_blocking(move || lookup(&bundle_id))
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+/// Fetch an app's icon and dominant color through the shared `IconFetcher`.
+pub async fn fetch_app_icon_and_color(bundle_id: &str) -> Option<(String, String)> {
+    IconFetcher::shared().fetch(bundle_id).await
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorBackfillProgress {
+    pub total: usize,
+    pub processed: usize,
+    pub updated: usize,
+}
+
+/// A freshly fetched icon, returned by `refresh_app_icon` and emitted as
+/// `app-icon-updated`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppIconUpdate {
+    pub bundle_id: String,
+    pub icon_data_url: String,
+    pub icon_color: Option<String>,
+}
 
 /// Manages pre-fetching of app icons during active sessions.
 /// This helps ensure icons are ready when the session summary view loads,
@@ -84,6 +152,11 @@ async fn prefetch_icon_for_app(
     // First, ensure the app exists in the database
     db.ensure_app_exists(bundle_id, app_name).await?;
 
+    // Background helpers often have no owner name; resolve one from the bundle
+    if !db.app_has_name(bundle_id).await? {
+        fill_app_display_name(&db, bundle_id).await;
+    }
+
     // Check if the app already has an icon AND a color
     let has_icon = db.app_has_icon(bundle_id).await?;
     let has_color = db.app_has_color(bundle_id).await?;
@@ -107,7 +180,7 @@ async fn prefetch_icon_for_app(
         log::debug!("Pre-fetching icon for {} during session", bundle_id);
     }
 
-    match crate::macos_bridge::get_app_icon_and_color(bundle_id) {
+    match fetch_app_icon_and_color(bundle_id).await {
         Some((icon_data_url, icon_color)) => {
             // Store the icon and color in the database
             let color_opt = if icon_color.is_empty() {
@@ -139,3 +212,145 @@ async fn prefetch_icon_for_app(
 
     Ok(())
 }
+
+/// Re-fetch one app's icon and color and store them, even if an icon is already
+/// stored. Errors when the bundle can't be resolved (e.g. the app isn't installed).
+pub async fn refresh_app_icon(db: &Database, bundle_id: &str) -> anyhow::Result<AppIconUpdate> {
+    let (icon_data_url, icon_color) = fetch_app_icon_and_color(bundle_id)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Could not fetch an icon for {}", bundle_id))?;
+    let icon_color = (!icon_color.is_empty()).then_some(icon_color);
+
+    db.ensure_app_exists(bundle_id, None).await?;
+    db.update_app_icon(bundle_id, &icon_data_url, icon_color.as_deref())
+        .await?;
+    log::info!("Refreshed icon for {}", bundle_id);
+
+    Ok(AppIconUpdate {
+        bundle_id: bundle_id.to_string(),
+        icon_data_url,
+        icon_color,
+    })
+}
+
+/// Resolve the localized display name for `bundle_id` and store it if the app has none.
+pub(crate) async fn fill_app_display_name(db: &Database, bundle_id: &str) {
+    let lookup_bundle_id = bundle_id.to_string();
+    let display_name = tokio::task::spawn_blocking(move || {
+        crate::macos_bridge::get_app_display_name(&lookup_bundle_id)
+    })
+    .await
+    .ok()
+    .flatten();
+
+    match display_name {
+        Some(name) => match db.fill_missing_app_name(bundle_id, &name).await {
+            Ok(()) => log::debug!("Resolved app name for {}: {}", bundle_id, name),
+            Err(e) => log::warn!("Failed to store app name for {}: {}", bundle_id, e),
+        },
+        None => log::debug!("Could not resolve a display name for {}", bundle_id),
+    }
+}
+
+/// Backfill colors for apps whose icon was stored before colors were extracted.
+///
+/// Re-fetches through the Swift bridge first and falls back to computing the color from
+/// the stored icon (e.g. when the app is no longer installed). `on_progress` runs after
+/// each app.
+pub async fn recompute_app_colors<F>(
+    db: Database,
+    on_progress: F,
+) -> anyhow::Result<ColorBackfillProgress>
+where
+    F: Fn(ColorBackfillProgress),
+{
+    let apps = db.get_apps_missing_color().await?;
+    let mut progress = ColorBackfillProgress {
+        total: apps.len(),
+        ..Default::default()
+    };
+    on_progress(progress);
+
+    let mut tasks = JoinSet::new();
+
+    for app in apps {
+        let Some(stored_icon) = app.icon_data_url else {
+            continue;
+        };
+        let db = db.clone();
+
+        tasks.spawn(async move {
+            let bundle_id = app.bundle_id;
+
+            let (icon, color) = match fetch_app_icon_and_color(&bundle_id).await {
+                Some((icon, color)) if !color.is_empty() => (icon, color),
+                _ => tokio::task::spawn_blocking(move || {
+                    compute_dominant_color(&stored_icon).map(|color| (stored_icon, color))
+                })
+                .await
+                .ok()??,
+            };
+
+            match db.update_app_icon(&bundle_id, &icon, Some(&color)).await {
+                Ok(()) => Some(bundle_id),
+                Err(e) => {
+                    log::warn!("Failed to store backfilled color for {}: {}", bundle_id, e);
+                    None
+                }
+            }
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        progress.processed += 1;
+        if let Ok(Some(bundle_id)) = result {
+            progress.updated += 1;
+            log::debug!("Backfilled color for {}", bundle_id);
+        }
+        on_progress(progress);
+    }
+
+    log::info!(
+        "Recomputed app colors: {} of {} updated",
+        progress.updated,
+        progress.total
+    );
+
+    Ok(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn fetches_never_exceed_the_permit_count() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (counter, high_water) = (Arc::clone(&in_flight), Arc::clone(&peak));
+        let fetcher = Arc::new(IconFetcher::new(Arc::new(move |bundle_id: &str| {
+            let now = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            high_water.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            counter.fetch_sub(1, Ordering::SeqCst);
+            Some((format!("data:{bundle_id}"), "#000000".to_string()))
+        })));
+
+        let mut tasks = JoinSet::new();
+        for i in 0..ICON_FETCH_CONCURRENCY * 3 {
+            let fetcher = Arc::clone(&fetcher);
+            tasks.spawn(async move { fetcher.fetch(&format!("com.app{i}")).await });
+        }
+        let mut fetched = 0;
+        while let Some(result) = tasks.join_next().await {
+            assert!(result.unwrap().is_some());
+            fetched += 1;
+        }
+
+        assert_eq!(fetched, ICON_FETCH_CONCURRENCY * 3);
+        assert!(peak.load(Ordering::SeqCst) <= ICON_FETCH_CONCURRENCY);
+        assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,29 +1,52 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter};
-use tokio::time::{Duration, Instant, MissedTickBehavior};
+use tokio::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
     db::{ContextReading, Database},
-    macos_bridge::get_active_window_metadata,
+    macos_bridge::{self, get_active_window_metadata},
     metrics::{CaptureMetrics, MetricsCollector},
+    settings::SettingsStore,
 };
 
 use super::icon_manager::IconManager;
+use super::status::{SensingState, SensingStatusHandle};
 
 // DEPRECATED: Screenshot + pHash + OCR imports removed
-// use std::sync::Arc;
 // use anyhow::Context;
 // use crate::macos_bridge::{capture_screenshot, run_ocr};
 // use super::phash::{compute_hamming_distance, compute_phash};
+// use super::config::SensingConfig;
+// use super::redact::scrub_ocr_text;
 
-const ENABLE_LOGS: bool = true;
+const LOG_TAG: &str = "loop_worker";
 
 use crate::{log_error, log_info, log_warn};
 
-const CAPTURE_INTERVAL_SECS: u64 = 5;
+pub(super) const CAPTURE_INTERVAL_SECS: u64 = 5;
+
+/// Synthetic bundle ID recorded for windows without one (notification banners,
+/// Spotlight, the menu bar, ...).
+const SYSTEM_UI_BUNDLE_ID: &str = "com.apple.system";
+
 const CAPTURE_TIMEOUT_SECS: u64 = 10;
+/// OCR is disabled while capture is metadata-only (see DEPRECATED block below)
+pub(super) const OCR_ENABLED: bool = false;
+/// Consecutive failed captures before sensing is reported as degraded
+const DEGRADED_AFTER_FAILURES: u32 = 3;
+/// Readings are buffered and written in batches to cut DB wakeups (6 captures = 30s)
+const READING_BATCH_SIZE: usize = 6;
+/// Oldest buffered readings are dropped past this if the DB keeps failing
+const MAX_PENDING_READINGS: usize = READING_BATCH_SIZE * 20;
+/// Consecutive captures skipped behind a stuck metadata call before the Swift sensing
+/// cache is reset, as `start_sensing` does for a fresh session
+const STALLS_BEFORE_RESET: u32 = 3;
 
 // DEPRECATED: OCR tuning constants no longer used
 // const OCR_COOLDOWN_SECS: u64 = 20;
@@ -36,50 +59,361 @@ pub async fn sensing_loop(
     cancel_token: CancellationToken,
     metrics: MetricsCollector,
     app_handle: AppHandle,
+    status: SensingStatusHandle,
+    settings: Arc<SettingsStore>,
+    suspended: Arc<AtomicBool>,
 ) {
-    let mut ticker = tokio::time::interval(Duration::from_secs(CAPTURE_INTERVAL_SECS));
-    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // First capture fires immediately, like `tokio::time::interval`
+    let mut next_capture = Instant::now();
 
     // DEPRECATED: pHash/OCR state tracking removed
     // let mut last_sampled_phash: Option<String> = None;
     // let mut last_ocr_phash: Option<String> = None;
     // let mut last_ocr_time: Option<Instant> = None;
 
+    let mut pending_readings: Vec<ContextReading> = Vec::with_capacity(READING_BATCH_SIZE);
+    let mut consecutive_failures: u32 = 0;
+    // `timeout` can't cancel the blocking metadata call, so track it across captures
+    let in_flight = InFlightCapture::default();
+    let mut consecutive_stalls: u32 = 0;
+    // Distracting app currently in the foreground, so each stretch is reported once
+    let mut distracted_by: Option<String> = None;
+    let mut system_ui = SystemUiDebounce::default();
+
     loop {
         tokio::select! {
-            _ = ticker.tick() => {
+            _ = tokio::time::sleep_until(next_capture) => {
                 let timestamp = Utc::now();
-                let fut = perform_capture(
-                    &session_id,
-                    timestamp,
-                    &db,
-                    &icon_manager,
-                    &metrics,
-                    &app_handle,
-                );
 
-                match tokio::time::timeout(Duration::from_secs(CAPTURE_TIMEOUT_SECS), fut).await {
-                    Ok(Ok(())) => {},
-                    Ok(Err(err)) => log_error!("sensing capture failed for session {}: {err:?}", session_id),
-                    Err(_) => log_warn!("sensing capture timeout (> {}s) session {}", CAPTURE_TIMEOUT_SECS, session_id),
+                if suspended.load(Ordering::SeqCst) {
+                    // Paused by the user: keep the schedule, capture nothing
+                    let capture_metrics =
+                        skipped_capture_metrics(timestamp, "suspended", 0, 0, 0.0, 0.0);
+                    record_capture_metrics(&metrics, &app_handle, capture_metrics).await;
+                    next_capture = (next_capture + capture_interval(settings.sensing_config().jitter_percent))
+                        .max(Instant::now());
+                    continue;
+                }
+
+                let succeeded = if let Some(running_for) = in_flight.running_for() {
+                    // Never stack a second blocking call behind one that hasn't returned
+                    consecutive_stalls += 1;
+                    report_stalled_capture(
+                        &session_id,
+                        timestamp,
+                        running_for,
+                        &mut consecutive_stalls,
+                        &metrics,
+                        &app_handle,
+                    )
+                    .await;
+                    false
+                } else {
+                    consecutive_stalls = 0;
+                    let fut = perform_capture(
+                        &session_id,
+                        timestamp,
+                        &mut pending_readings,
+                        &mut system_ui,
+                        settings.sensing_config().system_ui_debounce_captures,
+                        &icon_manager,
+                        &metrics,
+                        &app_handle,
+                        &in_flight,
+                    );
+
+                    match tokio::time::timeout(Duration::from_secs(CAPTURE_TIMEOUT_SECS), fut).await {
+                        Ok(Ok(())) => true,
+                        Ok(Err(err)) => {
+                            log_error!(tag: LOG_TAG, "sensing capture failed for session {}: {err:?}", session_id);
+                            false
+                        }
+                        Err(_) => {
+                            log_warn!(tag: LOG_TAG, "sensing capture timeout (> {}s) session {}", CAPTURE_TIMEOUT_SECS, session_id);
+                            let capture_metrics = skipped_capture_metrics(
+                                timestamp,
+                                "timeout",
+                                0,
+                                CAPTURE_TIMEOUT_SECS * 1000,
+                                0.0,
+                                0.0,
+                            );
+                            record_capture_metrics(&metrics, &app_handle, capture_metrics).await;
+                            false
+                        }
+                    }
+                };
+
+                if succeeded {
+                    consecutive_failures = 0;
+                    status.record_capture(timestamp);
+                    // A capture that finished after `suspend` mustn't clear the indicator
+                    if !suspended.load(Ordering::SeqCst) {
+                        status.set_state(SensingState::Running, &app_handle);
+                    }
+                    if let Some(reading) = pending_readings.last() {
+                        check_distraction(reading, &settings, &mut distracted_by, &app_handle);
+                    }
+                } else {
+                    consecutive_failures += 1;
+                    if consecutive_failures >= DEGRADED_AFTER_FAILURES {
+                        status.set_state(SensingState::Degraded, &app_handle);
+                    }
+                }
+
+                if pending_readings.len() >= READING_BATCH_SIZE {
+                    flush_readings(&db, &mut pending_readings, &session_id).await;
                 }
+
+                // Same as `MissedTickBehavior::Delay`: a late capture fires right away and
+                // the schedule shifts from there, rather than bursting to catch up
+                next_capture = (next_capture + capture_interval(settings.sensing_config().jitter_percent))
+                    .max(Instant::now());
             }
             _ = cancel_token.cancelled() => {
-                log_info!("sensing loop shutting down");
+                log_info!(tag: LOG_TAG, "sensing loop shutting down");
+                // stop_sensing awaits this task, so segmentation sees every reading
+                flush_readings(&db, &mut pending_readings, &session_id).await;
                 break;
             }
         }
     }
 }
 
+/// Write buffered readings in one transaction. On failure they stay buffered for the
+/// next flush, bounded by `MAX_PENDING_READINGS`.
+async fn flush_readings(db: &Database, pending: &mut Vec<ContextReading>, session_id: &str) {
+    if pending.is_empty() {
+        return;
+    }
+
+    let db_start = Instant::now();
+    match db.insert_context_readings_batch(pending).await {
+        Ok(()) => {
+            log_info!(
+                tag: LOG_TAG,
+                "Flushed {} readings in {}ms for session {}",
+                pending.len(),
+                db_start.elapsed().as_millis(),
+                session_id
+            );
+            pending.clear();
+        }
+        Err(err) => {
+            log_error!(
+                tag: LOG_TAG,
+                "failed to persist {} readings for session {}: {err:?}",
+                pending.len(),
+                session_id
+            );
+            if pending.len() > MAX_PENDING_READINGS {
+                let overflow = pending.len() - MAX_PENDING_READINGS;
+                pending.drain(..overflow);
+                log_warn!(
+                    tag: LOG_TAG,
+                    "dropped {} oldest buffered readings for session {}",
+                    overflow,
+                    session_id
+                );
+            }
+        }
+    }
+}
+
+/// Start time of the blocking metadata call while it runs. A capture that times out
+/// leaves its `spawn_blocking` task behind, so this outlives the capture future.
+#[derive(Clone, Default)]
+struct InFlightCapture {
+    started_at: Arc<Mutex<Option<Instant>>>,
+}
+
+/// Clears `InFlightCapture` when the blocking call returns (or unwinds).
+struct InFlightGuard(InFlightCapture);
+
+impl InFlightCapture {
+    fn running_for(&self) -> Option<Duration> {
+        let started_at = *self.lock();
+        started_at.map(|started_at| started_at.elapsed())
+    }
+
+    fn begin(&self) -> InFlightGuard {
+        *self.lock() = Some(Instant::now());
+        InFlightGuard(self.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        self.started_at
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        *self.0.lock() = None;
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SensingStalledEvent {
+    session_id: String,
+    /// How long the previous capture's metadata call has been running
+    stalled_for_ms: u64,
+    consecutive_stalls: u32,
+    /// Whether this stall triggered a reset of the Swift sensing cache
+    cache_reset: bool,
+}
+
+/// Skip a capture because the previous metadata call is still stuck in Swift: record it
+/// in metrics, emit `sensing-stalled`, and reset the Swift sensing cache after
+/// `STALLS_BEFORE_RESET` stalls in a row.
+async fn report_stalled_capture(
+    session_id: &str,
+    timestamp: DateTime<Utc>,
+    running_for: Duration,
+    consecutive_stalls: &mut u32,
+    metrics: &MetricsCollector,
+    app_handle: &AppHandle,
+) {
+    let stalled_for_ms = running_for.as_millis() as u64;
+    log_warn!(
+        tag: LOG_TAG,
+        "previous capture still running after {}ms; skipping capture for session {} ({} in a row)",
+        stalled_for_ms,
+        session_id,
+        consecutive_stalls
+    );
+
+    let capture_metrics = skipped_capture_metrics(timestamp, "stalled", 0, 0, 0.0, 0.0);
+    record_capture_metrics(metrics, app_handle, capture_metrics).await;
+
+    let cache_reset = *consecutive_stalls >= STALLS_BEFORE_RESET;
+    let event = SensingStalledEvent {
+        session_id: session_id.to_string(),
+        stalled_for_ms,
+        consecutive_stalls: *consecutive_stalls,
+        cache_reset,
+    };
+    if let Err(err) = app_handle.emit("sensing-stalled", event) {
+        log_warn!(tag: LOG_TAG, "failed to emit sensing-stalled: {err}");
+    }
+
+    if cache_reset {
+        log_warn!(tag: LOG_TAG, "resetting sensing cache after {} stalled captures", consecutive_stalls);
+        *consecutive_stalls = 0;
+        // The Swift side may be wedged, so don't block the loop on the reset itself
+        tokio::task::spawn_blocking(macos_bridge::clear_cache);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DistractionDetectedEvent {
+    session_id: String,
+    bundle_id: String,
+    app_name: String,
+    detected_at: DateTime<Utc>,
+}
+
+/// Emit `distraction-detected` when an app on the distraction list is in the foreground.
+/// Fires once per continuous stretch: the app has to leave the foreground (or another
+/// listed app take over) before it is reported again.
+fn check_distraction(
+    reading: &ContextReading,
+    settings: &SettingsStore,
+    distracted_by: &mut Option<String>,
+    app_handle: &AppHandle,
+) {
+    let bundle_id = &reading.window_metadata.bundle_id;
+    if !settings.is_distraction(bundle_id) {
+        *distracted_by = None;
+        return;
+    }
+    if distracted_by.as_deref() == Some(bundle_id.as_str()) {
+        return;
+    }
+
+    *distracted_by = Some(bundle_id.clone());
+    let event = DistractionDetectedEvent {
+        session_id: reading.session_id.clone(),
+        bundle_id: bundle_id.clone(),
+        app_name: reading.window_metadata.owner_name.clone(),
+        detected_at: reading.timestamp,
+    };
+    if let Err(err) = app_handle.emit("distraction-detected", event) {
+        log_warn!(tag: LOG_TAG, "failed to emit distraction-detected: {err}");
+    }
+}
+
+/// Delay until the next capture: `CAPTURE_INTERVAL_SECS`, randomized by up to
+/// ±`jitter_percent` percent.
+fn capture_interval(jitter_percent: u32) -> Duration {
+    let interval = Duration::from_secs(CAPTURE_INTERVAL_SECS);
+    if jitter_percent == 0 {
+        return interval;
+    }
+
+    let max_offset = f64::from(jitter_percent) / 100.0;
+    let factor = 1.0 + rand::thread_rng().gen_range(-max_offset..=max_offset);
+    interval.mul_f64(factor)
+}
+
+/// Holds back system UI readings until system UI has stayed in front for more than
+/// the configured number of consecutive captures. A banner or Spotlight that is gone
+/// by then leaves no reading; one that stays is recorded from its first capture.
+#[derive(Default)]
+struct SystemUiDebounce {
+    held: Vec<ContextReading>,
+    /// The current system UI stretch outlasted the debounce; record it as it comes
+    committed: bool,
+}
+
+impl SystemUiDebounce {
+    fn admit(
+        &mut self,
+        reading: ContextReading,
+        debounce_captures: u32,
+        pending_readings: &mut Vec<ContextReading>,
+    ) {
+        if reading.window_metadata.bundle_id != SYSTEM_UI_BUNDLE_ID {
+            if !self.held.is_empty() {
+                log_info!(
+                    tag: LOG_TAG,
+                    "Dropped {} transient system UI reading(s)",
+                    self.held.len()
+                );
+                self.held.clear();
+            }
+            self.committed = false;
+            pending_readings.push(reading);
+            return;
+        }
+
+        if self.committed {
+            pending_readings.push(reading);
+            return;
+        }
+        self.held.push(reading);
+        if self.held.len() > debounce_captures as usize {
+            pending_readings.append(&mut self.held);
+            self.committed = true;
+        }
+    }
+}
+
 /// Simplified capture: only metadata, no screenshot/pHash/OCR
+#[allow(clippy::too_many_arguments)]
 async fn perform_capture(
     session_id: &str,
     timestamp: DateTime<Utc>,
-    db: &Database,
+    pending_readings: &mut Vec<ContextReading>,
+    system_ui: &mut SystemUiDebounce,
+    system_ui_debounce_captures: u32,
     icon_manager: &IconManager,
     metrics_collector: &MetricsCollector,
     app_handle: &AppHandle,
+    in_flight: &InFlightCapture,
 ) -> Result<()> {
     let capture_start = Instant::now();
 
@@ -87,26 +421,47 @@ async fn perform_capture(
     let (cpu_percent, memory_mb) = metrics_collector.sample_system_metrics().await;
 
     // Get active window metadata - this is all we need!
+    // On a blocking thread so a hung Swift call can't stall the runtime; `in_flight`
+    // stays set until it actually returns, even if this capture times out first
     let metadata_start = Instant::now();
-    let mut metadata = get_active_window_metadata()
-        .map_err(|err| anyhow!("active window metadata failed: {err}"))?;
+    let guard = in_flight.begin();
+    let metadata_result = tokio::task::spawn_blocking(move || {
+        let _guard = guard;
+        get_active_window_metadata()
+    })
+    .await
+    .unwrap_or_else(|err| Err(anyhow!("metadata task failed: {err}")));
     let metadata_duration_ms = metadata_start.elapsed().as_millis() as u64;
+    let mut metadata = match metadata_result {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            let capture_metrics = skipped_capture_metrics(
+                timestamp,
+                "metadata_failed",
+                metadata_duration_ms,
+                capture_start.elapsed().as_millis() as u64,
+                cpu_percent,
+                memory_mb,
+            );
+            record_capture_metrics(metrics_collector, app_handle, capture_metrics).await;
+            return Err(anyhow!("active window metadata failed: {err}"));
+        }
+    };
 
     // Handle system windows (empty bundle_id)
     if metadata.bundle_id.is_empty() {
-        metadata.bundle_id = "com.apple.system".to_string();
+        metadata.bundle_id = SYSTEM_UI_BUNDLE_ID.to_string();
         metadata.owner_name = "System UI".to_string();
     }
 
     // Ensure icon is cached for this app
-    if !metadata.bundle_id.is_empty() && metadata.bundle_id != "com.apple.system" {
+    if !metadata.bundle_id.is_empty() && metadata.bundle_id != SYSTEM_UI_BUNDLE_ID {
         icon_manager
             .ensure_icon(&metadata.bundle_id, Some(&metadata.owner_name))
             .await;
     }
 
-    // Store the reading (no screenshot, no pHash, no OCR)
-    let db_start = Instant::now();
+    // Buffer the reading (no screenshot, no pHash, no OCR); sensing_loop writes it in a batch
     let reading = ContextReading {
         id: None,
         session_id: session_id.to_string(),
@@ -118,19 +473,15 @@ async fn perform_capture(
         ocr_word_count: None,  // DEPRECATED: No longer computed
         segment_id: None,
     };
-
-    db.insert_context_reading(&reading)
-        .await
-        .map_err(|err| anyhow!("failed to persist context reading: {err}"))?;
-    let db_duration_ms = db_start.elapsed().as_millis() as u64;
+    system_ui.admit(reading, system_ui_debounce_captures, pending_readings);
 
     let capture_duration_ms = capture_start.elapsed().as_millis() as u64;
     log_info!(
-        "Capture completed in {}ms for session {} (metadata: {}ms, db: {}ms) - {}",
+        tag: LOG_TAG,
+        "Capture completed in {}ms for session {} (metadata: {}ms) - {}",
         capture_duration_ms,
         session_id,
         metadata_duration_ms,
-        db_duration_ms,
         metadata.bundle_id
     );
 
@@ -143,15 +494,71 @@ async fn perform_capture(
         phash_ms: 0,           // DEPRECATED: No longer computed
         ocr_ms: None,          // DEPRECATED: No longer computed
         ocr_skipped_reason: Some("disabled".to_string()),
-        db_write_ms: db_duration_ms,
+        skipped_reason: None,
+        db_write_ms: 0,        // Written later by flush_readings
         total_ms: capture_duration_ms,
         cpu_percent,
         memory_mb,
     };
+    record_capture_metrics(metrics_collector, app_handle, capture_metrics).await;
+
+    Ok(())
+}
+
+/// Build metrics for a capture that was skipped entirely (no reading persisted).
+/// Stages that never ran are zeroed so the recent-captures window stays comparable.
+fn skipped_capture_metrics(
+    timestamp: DateTime<Utc>,
+    reason: &str,
+    metadata_ms: u64,
+    total_ms: u64,
+    cpu_percent: f32,
+    memory_mb: f64,
+) -> CaptureMetrics {
+    CaptureMetrics {
+        timestamp,
+        metadata_ms,
+        screenshot_ms: 0,
+        screenshot_bytes: 0,
+        phash_ms: 0,
+        ocr_ms: None,
+        ocr_skipped_reason: None,
+        skipped_reason: Some(reason.to_string()),
+        db_write_ms: 0,
+        total_ms,
+        cpu_percent,
+        memory_mb,
+    }
+}
+
+async fn record_capture_metrics(
+    metrics_collector: &MetricsCollector,
+    app_handle: &AppHandle,
+    capture_metrics: CaptureMetrics,
+) {
     metrics_collector.record_capture(capture_metrics.clone()).await;
     let _ = app_handle.emit("sensing-metrics", capture_metrics);
+}
 
-    Ok(())
+/// Marker appended to OCR text cut by `truncate_ocr_text`.
+const OCR_TRUNCATION_MARKER: &str = "…";
+
+/// Cap OCR text at `max_chars` characters, cutting at the last whitespace so words
+/// aren't split, and mark the cut. Callers keep the word count from the full text.
+#[allow(dead_code)] // Unused until OCR capture is re-enabled
+fn truncate_ocr_text(text: String, max_chars: u32) -> String {
+    let max_chars = max_chars as usize;
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+
+    let kept = &text[..cut];
+    let kept = match kept.rfind(char::is_whitespace) {
+        Some(boundary) if boundary > 0 => &kept[..boundary],
+        // A single word longer than the cap; cut mid-word
+        _ => kept,
+    };
+    format!("{}{}", kept.trim_end(), OCR_TRUNCATION_MARKER)
 }
 
 // =============================================================================
@@ -170,6 +577,7 @@ async fn perform_capture_with_ocr(
     last_sampled_phash: &mut Option<String>,
     last_ocr_phash: &mut Option<String>,
     last_ocr_time: &mut Option<Instant>,
+    sensing_config: SensingConfig,
     metrics_collector: &MetricsCollector,
     app_handle: &AppHandle,
 ) -> Result<()> {
@@ -192,6 +600,7 @@ async fn perform_capture_with_ocr(
     let is_system_window = metadata.bundle_id.is_empty();
     if is_system_window {
         log_info!(
+            tag: LOG_TAG,
             "Detected system window (window_id={}), recording as System UI - took {}ms",
             metadata.window_id,
             metadata_duration_ms
@@ -219,6 +628,7 @@ async fn perform_capture_with_ocr(
 
         let capture_duration_ms = capture_start.elapsed().as_millis() as u64;
         log_info!(
+            tag: LOG_TAG,
             "System window captured in {}ms (metadata only)",
             capture_duration_ms
         );
@@ -231,6 +641,7 @@ async fn perform_capture_with_ocr(
             phash_ms: 0,
             ocr_ms: None,
             ocr_skipped_reason: Some("system_window".to_string()),
+            skipped_reason: None,
             db_write_ms: db_duration_ms,
             total_ms: capture_duration_ms,
             cpu_percent,
@@ -253,12 +664,24 @@ async fn perform_capture_with_ocr(
 
     if png_bytes.len() < 1000 {
         let capture_duration_ms = capture_start.elapsed().as_millis() as u64;
-        log_warn!("Warning: Screenshot too small ({} bytes) for window_id={} ({}), likely hidden/minimized - skipping (took {}ms, screenshot: {}ms)", 
+        log_warn!(tag: LOG_TAG, "Warning: Screenshot too small ({} bytes) for window_id={} ({}), likely hidden/minimized - skipping (took {}ms, screenshot: {}ms)", 
             png_bytes.len(), metadata.window_id, metadata.bundle_id, capture_duration_ms, screenshot_duration_ms);
+        let mut capture_metrics = skipped_capture_metrics(
+            timestamp,
+            "screenshot_too_small",
+            metadata_duration_ms,
+            capture_duration_ms,
+            cpu_percent,
+            memory_mb,
+        );
+        capture_metrics.screenshot_ms = screenshot_duration_ms;
+        capture_metrics.screenshot_bytes = screenshot_bytes;
+        record_capture_metrics(metrics_collector, app_handle, capture_metrics).await;
         return Ok(());
     }
 
     log_info!(
+        tag: LOG_TAG,
         "Screenshot: {} bytes, window_id={}, bundle={}, screenshot_time={}ms",
         png_bytes.len(),
         metadata.window_id,
@@ -278,6 +701,7 @@ async fn perform_capture_with_ocr(
     let phash_duration_ms = phash_start.elapsed().as_millis() as u64;
 
     log_info!(
+        tag: LOG_TAG,
         "Computed pHash: {}, total_phash_time={}ms",
         phash,
         phash_duration_ms
@@ -298,6 +722,7 @@ async fn perform_capture_with_ocr(
             Ok(result) => {
                 let ocr_ms = ocr_start.elapsed().as_millis() as u64;
                 log_info!(
+                    tag: LOG_TAG,
                     "OCR completed: {} words, confidence={:.2}, ocr_time={}ms",
                     result.word_count,
                     result.confidence,
@@ -305,8 +730,15 @@ async fn perform_capture_with_ocr(
                 );
                 *last_ocr_time = Some(Instant::now());
                 *last_ocr_phash = Some(phash.clone());
+                // Word count comes from the original result so scoring ignores
+                // redaction and truncation
+                let text = if sensing_config.redact_ocr {
+                    scrub_ocr_text(&result.text)
+                } else {
+                    result.text
+                };
                 (
-                    Some(result.text),
+                    Some(truncate_ocr_text(text, sensing_config.max_ocr_chars)),
                     Some(result.confidence),
                     Some(result.word_count),
                     Some(ocr_ms),
@@ -314,7 +746,7 @@ async fn perform_capture_with_ocr(
             }
             Err(err) => {
                 let ocr_ms = ocr_start.elapsed().as_millis() as u64;
-                log_warn!("ocr failed after {}ms: {err}", ocr_ms);
+                log_warn!(tag: LOG_TAG, "ocr failed after {}ms: {err}", ocr_ms);
                 (None, None, None, Some(ocr_ms))
             }
         }
@@ -343,7 +775,7 @@ async fn perform_capture_with_ocr(
     let db_duration_ms = db_start.elapsed().as_millis() as u64;
 
     let capture_duration_ms = capture_start.elapsed().as_millis() as u64;
-    log_info!("Capture completed in {}ms for session {} (metadata: {}ms, screenshot: {}ms, phash: {}ms, db: {}ms)", 
+    log_info!(tag: LOG_TAG, "Capture completed in {}ms for session {} (metadata: {}ms, screenshot: {}ms, phash: {}ms, db: {}ms)", 
         capture_duration_ms, session_id, metadata_duration_ms, screenshot_duration_ms, phash_duration_ms, db_duration_ms);
 
     let capture_metrics = CaptureMetrics {
@@ -354,6 +786,7 @@ async fn perform_capture_with_ocr(
         phash_ms: phash_duration_ms,
         ocr_ms: ocr_duration_ms,
         ocr_skipped_reason: ocr_skip_reason,
+        skipped_reason: None,
         db_write_ms: db_duration_ms,
         total_ms: capture_duration_ms,
         cpu_percent,
@@ -392,3 +825,38 @@ fn cooldown_elapsed(last_ocr_time: Option<&Instant>) -> bool {
         .unwrap_or(true)
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_ocr_text_caps_chars_at_a_word_boundary() {
+        let text = "alpha beta gamma delta epsilon".to_string();
+        let truncated = truncate_ocr_text(text.clone(), 13);
+
+        assert!(truncated.ends_with(OCR_TRUNCATION_MARKER));
+        let kept = truncated.trim_end_matches(OCR_TRUNCATION_MARKER);
+        assert!(kept.chars().count() <= 13);
+        // Only whole words survive, in their original order
+        let original: Vec<&str> = text.split_whitespace().collect();
+        let kept_words: Vec<&str> = kept.split_whitespace().collect();
+        assert_eq!(kept_words, original[..kept_words.len()]);
+        assert_eq!(kept_words, ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn truncate_ocr_text_leaves_short_text_and_its_word_count_alone() {
+        let text = "short text stays whole".to_string();
+        let truncated = truncate_ocr_text(text.clone(), 100);
+        assert_eq!(truncated, text);
+        assert_eq!(truncated.split_whitespace().count(), 4);
+    }
+
+    #[test]
+    fn truncate_ocr_text_counts_chars_not_bytes() {
+        let text = "ééééé ééééé".to_string();
+        let truncated = truncate_ocr_text(text, 8);
+        assert_eq!(truncated, format!("ééééé{OCR_TRUNCATION_MARKER}"));
+    }
+}
@@ -1,8 +1,18 @@
+mod config;
 mod controller;
 mod icon_manager;
 mod loop_worker;
+mod redact;
+mod status;
 
 // DEPRECATED: pHash module no longer used
 // mod phash;
 
+pub use config::SensingConfig;
 pub use controller::SensingController;
+pub(crate) use icon_manager::fill_app_display_name;
+pub use icon_manager::{
+    fetch_app_icon_and_color, recompute_app_colors, refresh_app_icon, AppIconUpdate,
+    ColorBackfillProgress,
+};
+pub use status::{SensingState, SensingStatus};
@@ -0,0 +1,91 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SensingState {
+    /// No session has started sensing since launch
+    Idle,
+    Running,
+    /// Running, but recent captures keep failing or timing out
+    Degraded,
+    /// Session still running, but captures are paused by the user
+    Suspended,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensingStatus {
+    pub state: SensingState,
+    pub session_id: Option<String>,
+    pub last_capture_at: Option<DateTime<Utc>>,
+    pub capture_interval_secs: u64,
+    pub ocr_enabled: bool,
+}
+
+/// Shared view of sensing status, updated by the controller and the capture loop.
+/// Every state transition is emitted as `sensing-status-changed`.
+#[derive(Clone)]
+pub struct SensingStatusHandle {
+    inner: Arc<Mutex<SensingStatus>>,
+}
+
+impl SensingStatusHandle {
+    pub fn new(capture_interval_secs: u64, ocr_enabled: bool) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SensingStatus {
+                state: SensingState::Idle,
+                session_id: None,
+                last_capture_at: None,
+                capture_interval_secs,
+                ocr_enabled,
+            })),
+        }
+    }
+
+    pub fn snapshot(&self) -> SensingStatus {
+        self.lock().clone()
+    }
+
+    pub fn start(&self, session_id: String, app_handle: &AppHandle) {
+        let status = {
+            let mut guard = self.lock();
+            guard.state = SensingState::Running;
+            guard.session_id = Some(session_id);
+            guard.last_capture_at = None;
+            guard.clone()
+        };
+        emit(app_handle, &status);
+    }
+
+    pub fn set_state(&self, state: SensingState, app_handle: &AppHandle) {
+        let status = {
+            let mut guard = self.lock();
+            if guard.state == state {
+                return;
+            }
+            guard.state = state;
+            guard.clone()
+        };
+        emit(app_handle, &status);
+    }
+
+    pub fn record_capture(&self, timestamp: DateTime<Utc>) {
+        self.lock().last_capture_at = Some(timestamp);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SensingStatus> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn emit(app_handle: &AppHandle, status: &SensingStatus) {
+    if let Err(err) = app_handle.emit("sensing-status-changed", status) {
+        log::warn!("Failed to emit sensing-status-changed: {err}");
+    }
+}
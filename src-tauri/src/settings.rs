@@ -1,40 +1,200 @@
-use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
-use std::{fs, path::PathBuf, sync::RwLock};
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use log::warn;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::sensing::SensingConfig;
+
+/// Chimes the Swift island player can synthesize. Serialized as the string id it
+/// expects; the player quietly falls back to the default chime for anything else.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoundId {
+    #[default]
+    #[serde(rename = "island_default")]
+    IslandDefault,
+    #[serde(rename = "island_soft")]
+    IslandSoft,
+    #[serde(rename = "island_elevator")]
+    IslandElevator,
+    #[serde(rename = "island_404")]
+    Island404,
+    #[serde(rename = "island_runaway")]
+    IslandRunaway,
+}
+
+impl SoundId {
+    pub const ALL: [SoundId; 5] = [
+        SoundId::IslandDefault,
+        SoundId::IslandSoft,
+        SoundId::IslandElevator,
+        SoundId::Island404,
+        SoundId::IslandRunaway,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoundId::IslandDefault => "island_default",
+            SoundId::IslandSoft => "island_soft",
+            SoundId::IslandElevator => "island_elevator",
+            SoundId::Island404 => "island_404",
+            SoundId::IslandRunaway => "island_runaway",
+        }
+    }
+}
+
+impl FromStr for SoundId {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match SoundId::ALL.into_iter().find(|id| id.as_str() == value) {
+            Some(id) => Ok(id),
+            None => bail!("Unknown island sound id: {value}"),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IslandSoundSettings {
     pub enabled: bool,
-    pub sound_id: String,
+    pub sound_id: SoundId,
 }
 
 impl Default for IslandSoundSettings {
     fn default() -> Self {
         Self {
             enabled: true,
-            sound_id: "island_default".into(),
+            sound_id: SoundId::default(),
         }
     }
 }
 
+/// Stored settings with a chime id this build doesn't know (e.g. written by a newer
+/// build) fall back to the default chime instead of discarding the whole file.
+fn island_sound_or_default<'de, D>(
+    deserializer: D,
+) -> std::result::Result<IslandSoundSettings, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).unwrap_or_else(|err| {
+        warn!("Ignoring stored island sound settings: {err}");
+        IslandSoundSettings::default()
+    }))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppearanceSettings {
+    pub theme: Theme,
+}
+
+pub const DEFAULT_RECOVERY_GRACE_SECS: u64 = 30;
+pub const DEFAULT_MAX_STOPWATCH_SECS: u64 = 24 * 60 * 60;
+pub const MAX_DAILY_GOAL_MINUTES: u32 = 24 * 60;
+pub const DEFAULT_SCATTERED_INTERRUPTIONS_PER_HOUR: f64 = 12.0;
+
+/// Bump when `UserSettings` changes in a way older builds can't read.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct UserSettings {
+    schema_version: u32,
+    #[serde(deserialize_with = "island_sound_or_default")]
     island_sound: IslandSoundSettings,
     island_visible: bool,
+    appearance: AppearanceSettings,
+    /// Preferred audio output device name; `None` follows the system default.
+    audio_output_device: Option<String>,
+    /// Sample rate for generated audio; one of `audio::SUPPORTED_SAMPLE_RATES`.
+    audio_sample_rate: u32,
+    /// Hide the synthetic `com.apple.system` ("System UI") app from stats queries.
+    exclude_system_ui: bool,
+    /// Apply `auto_label_rules` to unlabeled sessions when they end.
+    auto_label_enabled: bool,
+    sensing: SensingConfig,
+    /// Post a native notification when a focus session completes.
+    session_notifications_enabled: bool,
+    /// Keep sensing during Break sessions. When off, breaks still record a session row
+    /// but capture nothing and produce no segments. Applies to every break regardless
+    /// of how it was started, so a Pomodoro flow that chains a break after a countdown
+    /// inherits it.
+    sense_during_breaks: bool,
+    /// A session left running by a crash is resumed if its last heartbeat is at most
+    /// this many seconds old at startup; otherwise it is marked Interrupted.
+    recovery_grace_secs: u64,
+    /// Stopwatch sessions are ended automatically once they have run this long.
+    max_stopwatch_secs: u64,
+    /// Bundle IDs that trigger `distraction-detected` when they take the foreground
+    /// during a session.
+    distraction_list: Vec<String>,
+    /// Only accrue active time while the user is interacting (keyboard/mouse idle
+    /// below a threshold), so a session left running while away stops counting.
+    active_requires_interaction: bool,
+    /// Focus minutes to aim for each local day; 0 means no goal.
+    daily_goal_minutes: u32,
+    /// Local date `daily-goal-reached` last fired, so it fires once per day.
+    daily_goal_reached_on: Option<NaiveDate>,
+    /// Sessions with more interruptions per active hour than this are flagged
+    /// `scattered` in their summary.
+    scattered_interruptions_per_hour: f64,
 }
 
 impl Default for UserSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             island_sound: IslandSoundSettings::default(),
             island_visible: true,
+            appearance: AppearanceSettings::default(),
+            audio_output_device: None,
+            audio_sample_rate: crate::audio::DEFAULT_SAMPLE_RATE,
+            exclude_system_ui: false,
+            auto_label_enabled: false,
+            sensing: SensingConfig::default(),
+            session_notifications_enabled: true,
+            sense_during_breaks: false,
+            recovery_grace_secs: DEFAULT_RECOVERY_GRACE_SECS,
+            max_stopwatch_secs: DEFAULT_MAX_STOPWATCH_SECS,
+            distraction_list: Vec::new(),
+            active_requires_interaction: false,
+            daily_goal_minutes: 0,
+            daily_goal_reached_on: None,
+            scattered_interruptions_per_hour: DEFAULT_SCATTERED_INTERRUPTIONS_PER_HOUR,
         }
     }
 }
 
+/// How long settings must stay unchanged before a batch of updates is written to disk.
+const PERSIST_DEBOUNCE: Duration = Duration::from_millis(500);
+
 pub struct SettingsStore {
     path: PathBuf,
-    data: RwLock<UserSettings>,
+    data: Arc<RwLock<UserSettings>>,
+    /// Serializes writes to `path` between the writer thread and `flush`.
+    file_lock: Arc<Mutex<()>>,
+    /// Wakes the writer thread after a change; dropping it triggers the final flush.
+    persist_tx: Mutex<Option<Sender<()>>>,
+    writer: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl SettingsStore {
@@ -47,43 +207,361 @@ impl SettingsStore {
             UserSettings::default()
         };
 
+        let data = Arc::new(RwLock::new(data));
+        let file_lock = Arc::new(Mutex::new(()));
+        let (persist_tx, persist_rx) = mpsc::channel();
+        let writer = spawn_writer(
+            path.clone(),
+            Arc::clone(&data),
+            Arc::clone(&file_lock),
+            persist_rx,
+        )?;
+
         Ok(Self {
             path,
-            data: RwLock::new(data),
+            data,
+            file_lock,
+            persist_tx: Mutex::new(Some(persist_tx)),
+            writer: Mutex::new(Some(writer)),
         })
     }
 
+    fn read(&self) -> RwLockReadGuard<'_, UserSettings> {
+        read_settings(&self.data)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, UserSettings> {
+        self.data
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     pub fn island_sound(&self) -> IslandSoundSettings {
-        self.data.read().unwrap().island_sound.clone()
+        self.read().island_sound.clone()
     }
 
     pub fn update_island_sound(&self, settings: IslandSoundSettings) -> Result<()> {
         {
-            let mut guard = self.data.write().unwrap();
+            let mut guard = self.write();
             guard.island_sound = settings;
-            self.persist(&guard)?;
+            self.schedule_persist();
         }
         Ok(())
     }
 
     pub fn island_visible(&self) -> bool {
-        self.data.read().unwrap().island_visible
+        self.read().island_visible
     }
 
     pub fn update_island_visible(&self, visible: bool) -> Result<()> {
         {
-            let mut guard = self.data.write().unwrap();
+            let mut guard = self.write();
             guard.island_visible = visible;
-            self.persist(&guard)?;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn appearance(&self) -> AppearanceSettings {
+        self.read().appearance.clone()
+    }
+
+    pub fn update_appearance(&self, settings: AppearanceSettings) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.appearance = settings;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn audio_output_device(&self) -> Option<String> {
+        self.read().audio_output_device.clone()
+    }
+
+    pub fn update_audio_output_device(&self, device: Option<String>) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.audio_output_device = device;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn audio_sample_rate(&self) -> u32 {
+        self.read().audio_sample_rate
+    }
+
+    pub fn update_audio_sample_rate(&self, rate: u32) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.audio_sample_rate = rate;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn exclude_system_ui(&self) -> bool {
+        self.read().exclude_system_ui
+    }
+
+    pub fn update_exclude_system_ui(&self, exclude: bool) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.exclude_system_ui = exclude;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn auto_label_enabled(&self) -> bool {
+        self.read().auto_label_enabled
+    }
+
+    pub fn update_auto_label_enabled(&self, enabled: bool) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.auto_label_enabled = enabled;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn session_notifications_enabled(&self) -> bool {
+        self.read().session_notifications_enabled
+    }
+
+    pub fn update_session_notifications_enabled(&self, enabled: bool) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.session_notifications_enabled = enabled;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn sense_during_breaks(&self) -> bool {
+        self.read().sense_during_breaks
+    }
+
+    pub fn update_sense_during_breaks(&self, enabled: bool) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.sense_during_breaks = enabled;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn recovery_grace_secs(&self) -> u64 {
+        self.read().recovery_grace_secs
+    }
+
+    pub fn update_recovery_grace_secs(&self, secs: u64) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.recovery_grace_secs = secs;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn max_stopwatch_secs(&self) -> u64 {
+        self.read().max_stopwatch_secs
+    }
+
+    pub fn update_max_stopwatch_secs(&self, secs: u64) -> Result<()> {
+        if secs == 0 {
+            bail!("max_stopwatch_secs must be greater than zero");
+        }
+        {
+            let mut guard = self.write();
+            guard.max_stopwatch_secs = secs;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn distraction_list(&self) -> Vec<String> {
+        self.read().distraction_list.clone()
+    }
+
+    pub fn is_distraction(&self, bundle_id: &str) -> bool {
+        self.read()
+            .distraction_list
+            .iter()
+            .any(|id| id == bundle_id)
+    }
+
+    /// Replace the distraction list; IDs are trimmed, and blanks and duplicates dropped.
+    pub fn update_distraction_list(&self, bundle_ids: Vec<String>) -> Result<Vec<String>> {
+        let mut list: Vec<String> = Vec::with_capacity(bundle_ids.len());
+        for id in bundle_ids {
+            let id = id.trim();
+            if !id.is_empty() && !list.iter().any(|existing| existing == id) {
+                list.push(id.to_string());
+            }
+        }
+        {
+            let mut guard = self.write();
+            guard.distraction_list = list.clone();
+            self.schedule_persist();
+        }
+        Ok(list)
+    }
+
+    pub fn active_requires_interaction(&self) -> bool {
+        self.read().active_requires_interaction
+    }
+
+    pub fn update_active_requires_interaction(&self, enabled: bool) -> Result<()> {
+        {
+            let mut guard = self.write();
+            guard.active_requires_interaction = enabled;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn daily_goal_minutes(&self) -> u32 {
+        self.read().daily_goal_minutes
+    }
+
+    pub fn update_daily_goal_minutes(&self, minutes: u32) -> Result<()> {
+        if minutes > MAX_DAILY_GOAL_MINUTES {
+            bail!("daily_goal_minutes must be at most {MAX_DAILY_GOAL_MINUTES}");
+        }
+        {
+            let mut guard = self.write();
+            guard.daily_goal_minutes = minutes;
+            self.schedule_persist();
         }
         Ok(())
     }
 
-    fn persist(&self, data: &UserSettings) -> Result<()> {
-        let serialized = serde_json::to_string_pretty(data)?;
-        fs::write(&self.path, serialized)
-            .with_context(|| format!("Failed to write settings to {}", self.path.display()))
+    /// Record that the goal was reached on `date`. Returns false if it was already
+    /// recorded for that day.
+    pub fn mark_daily_goal_reached(&self, date: NaiveDate) -> bool {
+        let mut guard = self.write();
+        if guard.daily_goal_reached_on == Some(date) {
+            return false;
+        }
+        guard.daily_goal_reached_on = Some(date);
+        self.schedule_persist();
+        true
+    }
+
+    pub fn scattered_interruptions_per_hour(&self) -> f64 {
+        self.read().scattered_interruptions_per_hour
     }
+
+    pub fn update_scattered_interruptions_per_hour(&self, per_hour: f64) -> Result<()> {
+        if !per_hour.is_finite() || per_hour <= 0.0 {
+            bail!("scattered_interruptions_per_hour must be a positive number");
+        }
+        {
+            let mut guard = self.write();
+            guard.scattered_interruptions_per_hour = per_hour;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    pub fn sensing_config(&self) -> SensingConfig {
+        self.read().sensing
+    }
+
+    pub fn update_sensing_config(&self, config: SensingConfig) -> Result<()> {
+        config.validate()?;
+        {
+            let mut guard = self.write();
+            guard.sensing = config;
+            self.schedule_persist();
+        }
+        Ok(())
+    }
+
+    /// Queue a write of the current settings once updates go quiet for `PERSIST_DEBOUNCE`.
+    fn schedule_persist(&self) {
+        let tx = self.persist_tx.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some(tx) = tx.as_ref() {
+            let _ = tx.send(());
+        }
+    }
+
+    /// Write the current settings to disk immediately, for callers that can't wait
+    /// for the debounce (shutdown, relaunch).
+    pub fn flush(&self) -> Result<()> {
+        let guard = self.read();
+        write_settings(&self.path, &self.file_lock, &guard)
+    }
+}
+
+impl Drop for SettingsStore {
+    fn drop(&mut self) {
+        // Closing the channel makes the writer flush anything pending and exit
+        self.persist_tx
+            .get_mut()
+            .unwrap_or_else(|p| p.into_inner())
+            .take();
+        let writer = self
+            .writer
+            .get_mut()
+            .unwrap_or_else(|p| p.into_inner())
+            .take();
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+    }
+}
+
+// Updates only assign whole fields, so settings behind a poisoned lock are still
+// valid; recover them instead of panicking the whole process.
+fn read_settings(data: &RwLock<UserSettings>) -> RwLockReadGuard<'_, UserSettings> {
+    data.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Callers hold the settings lock first, then `file_lock`, so writes never interleave.
+fn write_settings(path: &Path, file_lock: &Mutex<()>, data: &UserSettings) -> Result<()> {
+    let serialized = serde_json::to_string_pretty(data)?;
+    let _file = file_lock.lock().unwrap_or_else(|p| p.into_inner());
+    fs::write(path, serialized)
+        .with_context(|| format!("Failed to write settings to {}", path.display()))
+}
+
+/// Background writer that batches updates: each change restarts the quiet period, and
+/// the latest settings are written once it elapses or the store is dropped.
+fn spawn_writer(
+    path: PathBuf,
+    data: Arc<RwLock<UserSettings>>,
+    file_lock: Arc<Mutex<()>>,
+    rx: Receiver<()>,
+) -> Result<JoinHandle<()>> {
+    thread::Builder::new()
+        .name("settings-writer".to_string())
+        .spawn(move || {
+            // Blocks until the first change of a batch; exits once the store is dropped
+            while rx.recv().is_ok() {
+                let disconnected = loop {
+                    match rx.recv_timeout(PERSIST_DEBOUNCE) {
+                        Ok(()) => continue,
+                        Err(RecvTimeoutError::Timeout) => break false,
+                        Err(RecvTimeoutError::Disconnected) => break true,
+                    }
+                };
+
+                let guard = read_settings(&data);
+                if let Err(err) = write_settings(&path, &file_lock, &guard) {
+                    warn!("Failed to persist settings: {err:#}");
+                }
+                drop(guard);
+
+                if disconnected {
+                    break;
+                }
+            }
+        })
+        .context("Failed to spawn settings writer thread")
 }
 
 impl SettingsStore {
@@ -91,8 +569,144 @@ impl SettingsStore {
     pub fn reload(&self) -> Result<()> {
         let contents = fs::read_to_string(&self.path)?;
         let data: UserSettings = serde_json::from_str(&contents)?;
-        let mut guard = self.data.write().unwrap();
+        self.replace(data);
+        Ok(())
+    }
+
+    /// Serialize the current settings for transfer to another machine.
+    pub fn export_json(&self) -> Result<String> {
+        let guard = self.read();
+        Ok(serde_json::to_string_pretty(&*guard)?)
+    }
+
+    /// Validate and adopt settings produced by `export_json`, writing them to disk
+    /// immediately rather than through the debounced writer. With `sensing_locked`
+    /// (a session is in progress), an import that would change the sensing config
+    /// is rejected.
+    pub fn import_json(&self, json: &str, sensing_locked: bool) -> Result<()> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).context("Settings import is not valid JSON")?;
+        let version = value
+            .get("schema_version")
+            .and_then(|v| v.as_u64())
+            .context("Settings import is missing schema_version")?;
+        if version > u64::from(SETTINGS_SCHEMA_VERSION) {
+            bail!(
+                "Settings were exported by a newer version of LeFocus (schema {}, supported up to {})",
+                version,
+                SETTINGS_SCHEMA_VERSION
+            );
+        }
+
+        let mut data: UserSettings =
+            serde_json::from_value(value).context("Settings import has an invalid shape")?;
+        data.schema_version = SETTINGS_SCHEMA_VERSION;
+        data.sensing.validate()?;
+        if !crate::audio::SUPPORTED_SAMPLE_RATES.contains(&data.audio_sample_rate) {
+            bail!(
+                "Settings import has unsupported audio_sample_rate {} (expected one of {:?})",
+                data.audio_sample_rate,
+                crate::audio::SUPPORTED_SAMPLE_RATES
+            );
+        }
+        if data.max_stopwatch_secs == 0 {
+            bail!("Settings import has max_stopwatch_secs of zero");
+        }
+        if data.daily_goal_minutes > MAX_DAILY_GOAL_MINUTES {
+            bail!("Settings import has daily_goal_minutes above {MAX_DAILY_GOAL_MINUTES}");
+        }
+        if !data.scattered_interruptions_per_hour.is_finite()
+            || data.scattered_interruptions_per_hour <= 0.0
+        {
+            bail!("Settings import has a non-positive scattered_interruptions_per_hour");
+        }
+
+        let mut guard = self.write();
+        if sensing_locked && data.sensing != guard.sensing {
+            bail!("cannot change sensing settings during an active session");
+        }
+        write_settings(&self.path, &self.file_lock, &data)?;
         *guard = data;
         Ok(())
     }
+
+    fn replace(&self, data: UserSettings) {
+        let mut guard = self.write();
+        *guard = data;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A store backed by a fresh file under the system temp dir.
+    fn temp_store() -> (SettingsStore, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("lefocus-settings-{}.json", uuid::Uuid::new_v4()));
+        (SettingsStore::new(path.clone()).unwrap(), path)
+    }
+
+    #[test]
+    fn poisoned_lock_still_serves_reads_and_writes() {
+        let (store, path) = temp_store();
+        store.update_island_visible(false).unwrap();
+
+        let data = Arc::clone(&store.data);
+        let panicked = thread::spawn(move || {
+            let _guard = data.write().unwrap();
+            panic!("poison the settings lock");
+        })
+        .join();
+        assert!(panicked.is_err());
+        assert!(store.data.is_poisoned());
+
+        assert!(!store.island_visible());
+        store.update_island_visible(true).unwrap();
+        assert!(store.island_visible());
+        store.flush().unwrap();
+
+        drop(store);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn import_cannot_change_sensing_while_locked() {
+        let (store, path) = temp_store();
+        let mut exported: serde_json::Value =
+            serde_json::from_str(&store.export_json().unwrap()).unwrap();
+
+        // Re-importing the current sensing config is always allowed
+        store.import_json(&exported.to_string(), true).unwrap();
+
+        exported["sensing"]["jitter_percent"] = serde_json::json!(10);
+        let err = store.import_json(&exported.to_string(), true).unwrap_err();
+        assert!(err.to_string().contains("active session"));
+        assert_eq!(store.sensing_config().jitter_percent, 0);
+
+        store.import_json(&exported.to_string(), false).unwrap();
+        assert_eq!(store.sensing_config().jitter_percent, 10);
+
+        drop(store);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn import_rejects_unsupported_sample_rate() {
+        let (store, path) = temp_store();
+        let mut exported: serde_json::Value =
+            serde_json::from_str(&store.export_json().unwrap()).unwrap();
+
+        exported["audio_sample_rate"] = serde_json::json!(96_000);
+        let err = store.import_json(&exported.to_string(), false).unwrap_err();
+        assert!(err.to_string().contains("audio_sample_rate"));
+        assert_eq!(store.audio_sample_rate(), crate::audio::DEFAULT_SAMPLE_RATE);
+
+        exported["audio_sample_rate"] = serde_json::json!(48_000);
+        store.import_json(&exported.to_string(), false).unwrap();
+        assert_eq!(store.audio_sample_rate(), 48_000);
+
+        drop(store);
+        let _ = fs::remove_file(path);
+    }
 }
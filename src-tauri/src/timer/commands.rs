@@ -2,10 +2,17 @@ use tauri::State;
 
 use crate::{
     db::{
-        models::{Interruption, Segment, SessionSummary},
-        SessionInfo,
+        models::{
+            CaptureGap, Interruption, Segment, SegmentHighlight, SessionComparison,
+            SessionComparisonSide, SessionEvent, SessionSummary, TimelineReading, TopApp,
+        },
+        repositories::segments::interruptions_per_active_hour,
+        Database, Session, SessionInfo, SessionStatus,
     },
-    timer::{TimerController, TimerMode, TimerSnapshot, TimerState},
+    segmentation,
+    sensing::SensingStatus,
+    timer::{compare, StartTimerResult, TimerController, TimerMode, TimerSnapshot},
+    utils::color::color_or_fallback,
 };
 
 use crate::AppState;
@@ -20,13 +27,32 @@ pub async fn get_timer_state(state: State<'_, AppState>) -> Result<TimerSnapshot
     Ok(controller.get_snapshot().await)
 }
 
+#[tauri::command]
+pub async fn get_sensing_status(state: State<'_, AppState>) -> Result<SensingStatus, String> {
+    let controller = controller_from_state(&state);
+    Ok(controller.sensing_status().await)
+}
+
+/// Stop capturing temporarily (e.g. while typing a password) without pausing the timer.
+#[tauri::command]
+pub async fn suspend_sensing(state: State<'_, AppState>) -> Result<(), String> {
+    let controller = controller_from_state(&state);
+    controller.suspend_sensing().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_sensing(state: State<'_, AppState>) -> Result<(), String> {
+    let controller = controller_from_state(&state);
+    controller.resume_sensing().await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn start_timer(
     state: State<'_, AppState>,
     target_ms: u64,
     mode: Option<TimerMode>,
     label_id: Option<i64>,
-) -> Result<TimerState, String> {
+) -> Result<StartTimerResult, String> {
     let controller = controller_from_state(&state);
 
     controller
@@ -42,18 +68,162 @@ pub async fn end_timer(state: State<'_, AppState>) -> Result<SessionInfo, String
 }
 
 #[tauri::command]
-pub async fn cancel_timer(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn cancel_timer(
+    state: State<'_, AppState>,
+    reason: Option<String>,
+) -> Result<(), String> {
     let controller = controller_from_state(&state);
-    controller.cancel_timer().await.map_err(|e| e.to_string())
+    controller.cancel_timer(reason).await.map_err(|e| e.to_string())
 }
 
+/// Segments of a session. With `min_confidence`, low-confidence segments are
+/// dropped; see `get_low_confidence_duration` for how much time that hides.
 #[tauri::command]
 pub async fn get_segments_for_session(
     state: State<'_, AppState>,
     session_id: String,
+    min_confidence: Option<f64>,
 ) -> Result<Vec<Segment>, String> {
     let db = &state.db;
-    db.get_segments_for_session(&session_id)
+    db.get_segments_for_session(&session_id, min_confidence)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Seconds of a session's activity in segments below `min_confidence`.
+#[tauri::command]
+pub async fn get_low_confidence_duration(
+    state: State<'_, AppState>,
+    session_id: String,
+    min_confidence: f64,
+) -> Result<i64, String> {
+    state
+        .db
+        .get_low_confidence_duration(&session_id, min_confidence)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Manually correct which app a segment is attributed to.
+#[tauri::command]
+pub async fn correct_segment_app(
+    state: State<'_, AppState>,
+    segment_id: String,
+    bundle_id: String,
+    app_name: Option<String>,
+) -> Result<(), String> {
+    state
+        .db
+        .update_segment_app(&segment_id, &bundle_id, app_name.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merge two adjacent segments of a session, returning the merged segment.
+#[tauri::command]
+pub async fn merge_segments(
+    state: State<'_, AppState>,
+    segment_id_a: String,
+    segment_id_b: String,
+) -> Result<Segment, String> {
+    state
+        .db
+        .merge_segments(&segment_id_a, &segment_id_b)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Split a segment at `at` (RFC 3339), returning the two resulting segments.
+#[tauri::command]
+pub async fn split_segment(
+    state: State<'_, AppState>,
+    segment_id: String,
+    at: String,
+) -> Result<Vec<Segment>, String> {
+    let at = chrono::DateTime::parse_from_rfc3339(&at)
+        .map_err(|e| e.to_string())?
+        .with_timezone(&chrono::Utc);
+
+    let (first, second) = state
+        .db
+        .split_segment(&segment_id, at)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(vec![first, second])
+}
+
+#[tauri::command]
+pub async fn add_segment_tag(
+    state: State<'_, AppState>,
+    segment_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    state
+        .db
+        .add_segment_tag(&segment_id, &tag)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_segment_tag(
+    state: State<'_, AppState>,
+    segment_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    state
+        .db
+        .remove_segment_tag(&segment_id, &tag)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_segment_tags(
+    state: State<'_, AppState>,
+    segment_id: String,
+) -> Result<Vec<String>, String> {
+    state
+        .db
+        .get_segment_tags(&segment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Segments carrying `tag` across all sessions, newest first.
+#[tauri::command]
+pub async fn get_segments_by_tag(
+    state: State<'_, AppState>,
+    tag: String,
+) -> Result<Vec<Segment>, String> {
+    state
+        .db
+        .get_segments_by_tag(&tag)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Highlights returned by `get_longest_segments` when no limit is given.
+const DEFAULT_LONGEST_SEGMENTS_LIMIT: usize = 10;
+
+/// Longest stable segments across history ("deep work highlights"), optionally
+/// filtered by confidence and by segment start time in `[start, end)` (RFC 3339).
+#[tauri::command]
+pub async fn get_longest_segments(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+    min_confidence: Option<f64>,
+    start: Option<chrono::DateTime<chrono::Utc>>,
+    end: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<SegmentHighlight>, String> {
+    state
+        .db
+        .get_longest_segments(
+            limit.unwrap_or(DEFAULT_LONGEST_SEGMENTS_LIMIT),
+            min_confidence,
+            start,
+            end,
+        )
         .await
         .map_err(|e| e.to_string())
 }
@@ -69,6 +239,17 @@ pub async fn get_interruptions_for_segment(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_interruptions_for_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<Interruption>, String> {
+    let db = &state.db;
+    db.get_all_interruptions_for_session(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_window_titles_for_segment(
     state: State<'_, AppState>,
@@ -80,6 +261,45 @@ pub async fn get_window_titles_for_segment(
         .map_err(|e| e.to_string())
 }
 
+/// A segment's readings in order, with pHash and OCR text change per reading.
+#[tauri::command]
+pub async fn get_segment_timeline(
+    state: State<'_, AppState>,
+    segment_id: String,
+) -> Result<Vec<TimelineReading>, String> {
+    state
+        .db
+        .get_segment_timeline(&segment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// A session's lifecycle events (started, ended, cancelled, ...), oldest first.
+#[tauri::command]
+pub async fn get_session_events(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<SessionEvent>, String> {
+    state
+        .db
+        .get_session_events(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// OCR text captured by a single reading; `None` if OCR was skipped.
+#[tauri::command]
+pub async fn get_reading_ocr_text(
+    state: State<'_, AppState>,
+    reading_id: i64,
+) -> Result<Option<String>, String> {
+    state
+        .db
+        .get_reading_ocr_text(reading_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[derive(serde::Serialize)]
 pub struct AppDetails {
     pub window_titles: Vec<(String, i64)>,
@@ -110,14 +330,56 @@ pub async fn get_app_details_in_time_range(
     })
 }
 
-/// Kept for backward compatibility with StatsView.
+/// Stretches of a session where sensing missed captures. Defaults to the sensing
+/// loop's capture interval when `expected_interval_secs` is omitted.
 #[tauri::command]
-pub async fn list_sessions(state: State<'_, AppState>) -> Result<Vec<SessionSummary>, String> {
-    use std::collections::{HashMap, HashSet};
-    let db = &state.db;
+pub async fn get_capture_gaps(
+    state: State<'_, AppState>,
+    session_id: String,
+    expected_interval_secs: Option<u64>,
+) -> Result<Vec<CaptureGap>, String> {
+    let interval_secs = match expected_interval_secs {
+        Some(secs) => secs,
+        None => state.timer.sensing_status().await.capture_interval_secs,
+    };
+
+    state
+        .db
+        .get_capture_gaps(&session_id, interval_secs)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    // Get all sessions (completed + interrupted)
-    let sessions = db.list_sessions().await.map_err(|e| e.to_string())?;
+/// Titles returned by `get_app_all_time_titles` when the caller gives no limit.
+const DEFAULT_ALL_TIME_TITLE_LIMIT: u32 = 50;
+
+#[tauri::command]
+pub async fn get_app_all_time_titles(
+    state: State<'_, AppState>,
+    bundle_id: String,
+    limit: Option<u32>,
+) -> Result<AppDetails, String> {
+    let window_titles = state
+        .db
+        .get_window_titles_for_app_all_time(
+            &bundle_id,
+            limit.unwrap_or(DEFAULT_ALL_TIME_TITLE_LIMIT),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(AppDetails { window_titles })
+}
+
+/// Attach top apps plus the shared icon/color maps to each session, flagging those
+/// whose interruption rate exceeds `scattered_threshold` (interruptions per active hour).
+pub(crate) async fn build_session_summaries(
+    db: &Database,
+    sessions: Vec<Session>,
+    exclude_system_ui: bool,
+    scattered_threshold: f64,
+) -> Result<Vec<SessionSummary>, String> {
+    use std::collections::{HashMap, HashSet};
 
     // For each session, get top 3 apps
     let mut summaries = Vec::new();
@@ -125,7 +387,7 @@ pub async fn list_sessions(state: State<'_, AppState>) -> Result<Vec<SessionSumm
 
     for session in sessions {
         let top_apps = db
-            .get_top_apps_for_session(&session.id, 3)
+            .get_top_apps_for_session(&session.id, 3, exclude_system_ui)
             .await
             .map_err(|e| e.to_string())?;
 
@@ -134,6 +396,14 @@ pub async fn list_sessions(state: State<'_, AppState>) -> Result<Vec<SessionSumm
             all_bundle_ids.insert(app.bundle_id.clone());
         }
 
+        let interruption_count = db
+            .get_all_interruptions_for_session(&session.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .len();
+        let scattered = interruptions_per_active_hour(interruption_count, session.active_ms)
+            > scattered_threshold;
+
         summaries.push(SessionSummary {
             id: session.id,
             started_at: session.started_at,
@@ -145,21 +415,28 @@ pub async fn list_sessions(state: State<'_, AppState>) -> Result<Vec<SessionSumm
             top_apps,
             app_icons: HashMap::new(),  // Will be populated below
             app_colors: HashMap::new(), // Will be populated below
+            scattered,
         });
     }
 
     // Fetch all app icons and colors in one go
-    let app_icons_and_colors = db
-        .get_app_icons_for_bundle_ids(&all_bundle_ids.into_iter().collect::<Vec<_>>())
+    let bundle_ids: Vec<String> = all_bundle_ids.into_iter().collect();
+    let mut app_icons_and_colors = db
+        .get_app_icons_for_bundle_ids(&bundle_ids)
         .await
         .map_err(|e| e.to_string())?;
 
-    // Split into separate maps for icons and colors
+    // Split into separate maps for icons and colors; every top app gets a color,
+    // falling back to one derived from its bundle ID
     let mut app_icons = HashMap::new();
     let mut app_colors = HashMap::new();
-    for (bundle_id, (icon, color)) in app_icons_and_colors {
+    for bundle_id in bundle_ids {
+        let (icon, color) = app_icons_and_colors
+            .remove(&bundle_id)
+            .unwrap_or((None, None));
         app_icons.insert(bundle_id.clone(), icon);
-        app_colors.insert(bundle_id, color);
+        let color = color_or_fallback(color, &bundle_id);
+        app_colors.insert(bundle_id, Some(color));
     }
 
     // Share the same maps across all summaries (efficient - no duplication)
@@ -171,14 +448,36 @@ pub async fn list_sessions(state: State<'_, AppState>) -> Result<Vec<SessionSumm
     Ok(summaries)
 }
 
+/// Kept for backward compatibility with StatsView.
+#[tauri::command]
+pub async fn list_sessions(
+    state: State<'_, AppState>,
+    exclude_system_ui: Option<bool>,
+) -> Result<Vec<SessionSummary>, String> {
+    let db = &state.db;
+    let exclude_system_ui = exclude_system_ui.unwrap_or_else(|| state.settings.exclude_system_ui());
+
+    // Get all sessions (completed + interrupted)
+    let sessions = db.list_sessions().await.map_err(|e| e.to_string())?;
+
+    build_session_summaries(
+        db,
+        sessions,
+        exclude_system_ui,
+        state.settings.scattered_interruptions_per_hour(),
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn list_sessions_paginated(
     state: State<'_, AppState>,
     limit: usize,
     offset: usize,
+    exclude_system_ui: Option<bool>,
 ) -> Result<Vec<SessionSummary>, String> {
-    use std::collections::{HashMap, HashSet};
     let db = &state.db;
+    let exclude_system_ui = exclude_system_ui.unwrap_or_else(|| state.settings.exclude_system_ui());
 
     // Get paginated sessions (completed + interrupted)
     let sessions = db
@@ -186,59 +485,173 @@ pub async fn list_sessions_paginated(
         .await
         .map_err(|e| e.to_string())?;
 
-    // For each session, get top 3 apps
-    let mut summaries = Vec::new();
-    let mut all_bundle_ids = HashSet::new();
+    build_session_summaries(
+        db,
+        sessions,
+        exclude_system_ui,
+        state.settings.scattered_interruptions_per_hour(),
+    )
+    .await
+}
 
-    for session in sessions {
-        let top_apps = db
-            .get_top_apps_for_session(&session.id, 3)
-            .await
-            .map_err(|e| e.to_string())?;
+/// Sessions whose status is any of `statuses` (e.g. only cancelled ones), newest first.
+#[tauri::command]
+pub async fn list_sessions_by_status(
+    state: State<'_, AppState>,
+    statuses: Vec<SessionStatus>,
+    limit: usize,
+    offset: usize,
+    exclude_system_ui: Option<bool>,
+) -> Result<Vec<SessionSummary>, String> {
+    let db = &state.db;
+    let exclude_system_ui = exclude_system_ui.unwrap_or_else(|| state.settings.exclude_system_ui());
 
-        // Collect all unique bundle IDs
-        for app in &top_apps {
-            all_bundle_ids.insert(app.bundle_id.clone());
-        }
+    let sessions = db
+        .list_sessions_by_status(statuses, limit, offset)
+        .await
+        .map_err(|e| e.to_string())?;
 
-        summaries.push(SessionSummary {
-            id: session.id,
-            started_at: session.started_at,
-            stopped_at: session.stopped_at,
-            status: session.status,
-            target_ms: session.target_ms,
-            active_ms: session.active_ms,
-            label_id: session.label_id,
-            top_apps,
-            app_icons: HashMap::new(),  // Will be populated below
-            app_colors: HashMap::new(), // Will be populated below
-        });
-    }
+    build_session_summaries(
+        db,
+        sessions,
+        exclude_system_ui,
+        state.settings.scattered_interruptions_per_hour(),
+    )
+    .await
+}
 
-    // Fetch all app icons and colors in one go
-    let app_icons_and_colors = db
-        .get_app_icons_for_bundle_ids(&all_bundle_ids.into_iter().collect::<Vec<_>>())
+/// Fetch a single session summary by id. Returns `None` for unknown ids.
+#[tauri::command]
+pub async fn get_session(
+    state: State<'_, AppState>,
+    session_id: String,
+    exclude_system_ui: Option<bool>,
+) -> Result<Option<SessionSummary>, String> {
+    let db = &state.db;
+    let exclude_system_ui = exclude_system_ui.unwrap_or_else(|| state.settings.exclude_system_ui());
+
+    let Some(session) = db.get_session(&session_id).await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+
+    let summaries = build_session_summaries(
+        db,
+        vec![session],
+        exclude_system_ui,
+        state.settings.scattered_interruptions_per_hour(),
+    )
+    .await?;
+    Ok(summaries.into_iter().next())
+}
+
+/// Per-app durations fetched for comparisons; effectively "all apps".
+const COMPARE_APP_LIMIT: usize = 500;
+
+/// One session's summary, focus stats, and full per-app durations for a comparison.
+async fn load_comparison_side(
+    db: &Database,
+    session_id: &str,
+    exclude_system_ui: bool,
+    scattered_threshold: f64,
+) -> Result<(SessionComparisonSide, Vec<TopApp>), String> {
+    let session = db
+        .get_session(session_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let summary = build_session_summaries(db, vec![session], exclude_system_ui, scattered_threshold)
+        .await?
+        .remove(0);
+    let focus = db
+        .get_session_focus_stats(session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let apps = db
+        .get_top_apps_for_session(session_id, COMPARE_APP_LIMIT, exclude_system_ui)
         .await
         .map_err(|e| e.to_string())?;
 
-    // Split into separate maps for icons and colors
-    let mut app_icons = HashMap::new();
-    let mut app_colors = HashMap::new();
-    for (bundle_id, (icon, color)) in app_icons_and_colors {
-        app_icons.insert(bundle_id.clone(), icon);
-        app_colors.insert(bundle_id, color);
-    }
+    Ok((SessionComparisonSide { summary, focus }, apps))
+}
 
-    // Share the same maps across all summaries (efficient - no duplication)
-    for summary in &mut summaries {
-        summary.app_icons = app_icons.clone();
-        summary.app_colors = app_colors.clone();
-    }
+/// Compare two sessions side by side. Deltas in the diff are B minus A.
+#[tauri::command]
+pub async fn compare_sessions(
+    state: State<'_, AppState>,
+    session_id_a: String,
+    session_id_b: String,
+    exclude_system_ui: Option<bool>,
+) -> Result<SessionComparison, String> {
+    let db = &state.db;
+    let exclude_system_ui = exclude_system_ui.unwrap_or_else(|| state.settings.exclude_system_ui());
 
-    Ok(summaries)
+    let scattered_threshold = state.settings.scattered_interruptions_per_hour();
+
+    let (a, apps_a) =
+        load_comparison_side(db, &session_id_a, exclude_system_ui, scattered_threshold).await?;
+    let (b, apps_b) =
+        load_comparison_side(db, &session_id_b, exclude_system_ui, scattered_threshold).await?;
+    let diff = compare::diff_sessions(&a, &b, &apps_a, &apps_b);
+
+    Ok(SessionComparison { a, b, diff })
+}
+
+/// Maintenance: segment finished sessions that have readings but no timeline, except
+/// one the timer is still finalizing. Returns how many sessions were segmented.
+#[tauri::command]
+pub async fn segment_unsegmented_sessions(state: State<'_, AppState>) -> Result<usize, String> {
+    let timer_session_id = state.timer.get_state().await.session_id;
+    segmentation::segment_unsegmented_sessions(&state.db, timer_session_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn delete_session(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
     state.db.delete_session(&session_id).await.map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::{segment, session, t0};
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn interruption_rate_drives_the_scattered_flag() {
+        let db = Database::new_in_memory().unwrap();
+        // Half an hour of active time with four interruptions: 8 per active hour
+        let mut record = session("s", t0());
+        record.active_ms = 30 * 60 * 1000;
+        db.insert_session(&record).await.unwrap();
+        let parent = segment("s", "com.a", t0(), 1800);
+        let interruptions: Vec<Interruption> = (0..4)
+            .map(|i| Interruption {
+                id: format!("i{i}"),
+                segment_id: parent.id.clone(),
+                bundle_id: "com.b".to_string(),
+                app_name: None,
+                timestamp: t0() + Duration::minutes(i * 5 + 1),
+                duration_secs: 10,
+                icon_data_url: None,
+                icon_color: None,
+            })
+            .collect();
+        db.insert_segments_and_interruptions("s", &[parent], &interruptions)
+            .await
+            .unwrap();
+
+        let rate = db.get_session_interruption_rate("s").await.unwrap();
+        assert!((rate - 8.0).abs() < 1e-9);
+        assert_eq!(interruptions_per_active_hour(4, 0), 0.0);
+
+        let calm = build_session_summaries(&db, vec![record.clone()], false, 12.0)
+            .await
+            .unwrap();
+        assert!(!calm[0].scattered);
+        let scattered = build_session_summaries(&db, vec![record], false, 6.0)
+            .await
+            .unwrap();
+        assert!(scattered[0].scattered);
+    }
+}
@@ -0,0 +1,56 @@
+//! A/B comparison of two sessions: side-by-side summaries plus a computed diff.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::db::models::{SessionComparisonSide, SessionDiff, SharedAppDelta, TopApp};
+
+/// Diff two sessions given their summaries/focus stats and full per-app durations.
+/// Every delta is B minus A.
+pub fn diff_sessions(
+    a: &SessionComparisonSide,
+    b: &SessionComparisonSide,
+    apps_a: &[TopApp],
+    apps_b: &[TopApp],
+) -> SessionDiff {
+    let by_bundle_b: HashMap<&str, &TopApp> = apps_b
+        .iter()
+        .map(|app| (app.bundle_id.as_str(), app))
+        .collect();
+
+    let bundles_a: HashSet<&str> = apps_a.iter().map(|app| app.bundle_id.as_str()).collect();
+
+    let mut apps_only_in_a = Vec::new();
+    let mut shared_apps = Vec::new();
+    for app_a in apps_a {
+        match by_bundle_b.get(app_a.bundle_id.as_str()) {
+            Some(app_b) => shared_apps.push(SharedAppDelta {
+                bundle_id: app_a.bundle_id.clone(),
+                app_name: app_a.app_name.clone().or_else(|| app_b.app_name.clone()),
+                duration_secs_a: app_a.duration_secs,
+                duration_secs_b: app_b.duration_secs,
+                delta_secs: app_b.duration_secs as i64 - app_a.duration_secs as i64,
+            }),
+            None => apps_only_in_a.push(app_a.clone()),
+        }
+    }
+
+    let mut apps_only_in_b: Vec<TopApp> = apps_b
+        .iter()
+        .filter(|app_b| !bundles_a.contains(app_b.bundle_id.as_str()))
+        .cloned()
+        .collect();
+
+    apps_only_in_a.sort_by(|x, y| y.duration_secs.cmp(&x.duration_secs));
+    apps_only_in_b.sort_by(|x, y| y.duration_secs.cmp(&x.duration_secs));
+    shared_apps.sort_by(|x, y| y.delta_secs.abs().cmp(&x.delta_secs.abs()));
+
+    SessionDiff {
+        active_ms_delta: b.summary.active_ms as i64 - a.summary.active_ms as i64,
+        focus_score_delta: b.focus.focus_score - a.focus.focus_score,
+        interruption_count_delta: b.focus.interruption_count as i64
+            - a.focus.interruption_count as i64,
+        apps_only_in_a,
+        apps_only_in_b,
+        shared_apps,
+    }
+}
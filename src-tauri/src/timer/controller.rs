@@ -4,16 +4,19 @@ use std::{
 };
 
 use anyhow::{anyhow, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use log::{error, info};
 use serde::Serialize;
 use tokio::{sync::Mutex, task::JoinHandle, time};
 use uuid::Uuid;
 
 use crate::{
-    db::{Database, Session, SessionInfo, SessionStatus},
+    db::{models::SessionEventType, Database, Session, SessionInfo, SessionStatus},
     metrics::MetricsCollector,
-    sensing::SensingController,
+    segmentation::segment_and_store,
+    sensing::{SensingController, SensingStatus},
+    settings::SettingsStore,
+    utils::time::local_offset_minutes,
 };
 
 #[cfg(target_os = "macos")]
@@ -23,12 +26,27 @@ use super::{TimerMode, TimerState, TimerStatus};
 
 use tauri::{AppHandle, Emitter};
 
+/// Input-free seconds after which `active_requires_interaction` stops accruing
+/// active time.
+#[cfg(target_os = "macos")]
+const INTERACTION_IDLE_THRESHOLD_SECS: f64 = 60.0;
+
 #[derive(Debug, Serialize, Clone)]
 pub struct TimerSnapshot {
     pub state: TimerState,
     pub remaining_ms: i64,
 }
 
+/// Returned by `start_timer` so callers get the new session id without unwrapping
+/// `TimerState::session_id`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StartTimerResult {
+    pub session_id: String,
+    pub started_at: DateTime<Utc>,
+    pub state: TimerState,
+}
+
 #[derive(Serialize, Clone)]
 struct TimerStateChangedEvent {
     state: TimerState,
@@ -42,6 +60,12 @@ struct TimerHeartbeatEvent {
     remaining_ms: i64,
 }
 
+#[derive(Serialize, Clone)]
+struct SessionAutoEndedEvent {
+    session_id: String,
+    reason: &'static str,
+}
+
 #[derive(Serialize, Clone)]
 struct SessionCompletedEvent {
     session_id: String,
@@ -58,10 +82,16 @@ pub struct TimerController {
     heartbeat_every_ticks: u32,
     sensing: Arc<Mutex<SensingController>>,
     metrics: MetricsCollector,
+    settings: Arc<SettingsStore>,
 }
 
 impl TimerController {
-    pub fn new(app_handle: AppHandle, db: Database, metrics: MetricsCollector) -> Self {
+    pub fn new(
+        app_handle: AppHandle,
+        db: Database,
+        metrics: MetricsCollector,
+        settings: Arc<SettingsStore>,
+    ) -> Self {
         let debug_mode = std::env::var("LEFOCUS_DEBUG")
             .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
             .unwrap_or(false);
@@ -75,6 +105,7 @@ impl TimerController {
             heartbeat_every_ticks: if debug_mode { 1 } else { 10 },
             sensing: Arc::new(Mutex::new(SensingController::new())),
             metrics,
+            settings,
         }
     }
 
@@ -84,6 +115,42 @@ impl TimerController {
         guard.clone()
     }
 
+    pub async fn sensing_status(&self) -> SensingStatus {
+        self.sensing.lock().await.status()
+    }
+
+    /// Pause screen sensing while the timer keeps running.
+    pub async fn suspend_sensing(&self) -> Result<()> {
+        self.sensing.lock().await.suspend()?;
+        self.log_current_session_event(SessionEventType::SensingSuspended)
+            .await;
+        Ok(())
+    }
+
+    pub async fn resume_sensing(&self) -> Result<()> {
+        self.sensing.lock().await.resume()?;
+        self.log_current_session_event(SessionEventType::SensingResumed)
+            .await;
+        Ok(())
+    }
+
+    /// Append to the session's lifecycle log. Failures are logged, not fatal.
+    async fn log_event(
+        &self,
+        session_id: &str,
+        event_type: SessionEventType,
+        detail: Option<String>,
+    ) {
+        log_session_event(&self.db, session_id, event_type, detail).await;
+    }
+
+    async fn log_current_session_event(&self, event_type: SessionEventType) {
+        let session_id = self.state.lock().await.session_id.clone();
+        if let Some(session_id) = session_id {
+            self.log_event(&session_id, event_type, None).await;
+        }
+    }
+
     pub async fn get_snapshot(&self) -> TimerSnapshot {
         let mut guard = self.state.lock().await;
         guard.sync_active_from_anchor();
@@ -93,7 +160,12 @@ impl TimerController {
         }
     }
 
-    pub async fn start_timer(&self, target_ms: u64, mode: Option<TimerMode>, label_id: Option<i64>) -> Result<TimerState> {
+    pub async fn start_timer(
+        &self,
+        target_ms: u64,
+        mode: Option<TimerMode>,
+        label_id: Option<i64>,
+    ) -> Result<StartTimerResult> {
         let mode = mode.unwrap_or(TimerMode::Countdown);
 
         // For stopwatch mode, use i64::MAX as target (essentially unlimited, but SQLite-safe)
@@ -118,55 +190,28 @@ impl TimerController {
             TimerMode::Stopwatch => i64::MAX as u64,
         };
 
-        {
-            let state = self.state.lock().await;
-            if state.status != TimerStatus::Idle {
-                return Err(anyhow!("timer already active"));
-            }
-        }
-
-        let session_id = Uuid::new_v4().to_string();
-        let started_at = Utc::now();
-
-        // Skip DB insert and sensing for Break mode
-        if mode != TimerMode::Break {
-            let session = Session {
-                id: session_id.clone(),
-                started_at,
-                stopped_at: None,
-                status: SessionStatus::Running,
-                target_ms: actual_target_ms,
-                active_ms: 0,
-                label_id,
-                created_at: started_at,
-                updated_at: started_at,
-            };
-
-            self.db.insert_session(&session).await?;
-        }
+        let (session_id, started_at) =
+            begin_new_session(&self.state, &self.db, mode, actual_target_ms, label_id).await?;
+        self.log_event(
+            &session_id,
+            SessionEventType::Started,
+            Some(mode.as_str().to_string()),
+        )
+        .await;
 
-        // Initialize state without the anchor yet
-        {
-            let mut state = self.state.lock().await;
-            state.begin_session(
-                session_id.clone(),
-                actual_target_ms,
-                mode,
-                started_at,
-                Instant::now(),
-            );
-        }
+        let is_break = mode == TimerMode::Break;
 
-        // Skip sensing start for Break mode
-        if mode != TimerMode::Break {
+        // Breaks only sense when the user opted in; otherwise they produce no segments
+        if !is_break || self.settings.sense_during_breaks() {
             self.sensing
                 .lock()
                 .await
                 .start_sensing(
-                    session_id,
+                    session_id.clone(),
                     self.db.clone(),
                     self.metrics.clone(),
                     self.app_handle.clone(),
+                    self.settings.clone(),
                 )
                 .await?;
         }
@@ -182,80 +227,152 @@ impl TimerController {
         }
 
         #[cfg(target_os = "macos")]
-        {
-            let start_uptime_ms = current_uptime_ms();
-            let island_target_ms = match mode {
-                TimerMode::Countdown => {
-                    let clamped = actual_target_ms.min(i64::MAX as u64);
-                    clamped as i64
-                }
-                TimerMode::Break => {
-                    let clamped = actual_target_ms.min(i64::MAX as u64);
-                    clamped as i64
-                }
-                TimerMode::Stopwatch => 0,
-            };
-            let mode_str = match mode {
-                TimerMode::Countdown => "countdown",
-                TimerMode::Break => "break",
-                TimerMode::Stopwatch => "stopwatch",
-            };
-
-            island_start(start_uptime_ms, island_target_ms, mode_str);
-        }
+        start_island(mode, actual_target_ms, 0);
 
         self.emit_state_changed().await?;
 
-        Ok(self.get_state().await)
+        Ok(StartTimerResult {
+            session_id,
+            started_at,
+            state: self.get_state().await,
+        })
     }
 
-    pub async fn end_timer(&self) -> Result<SessionInfo> {
-        let stopped_at = Utc::now();
+    /// Resume a session left `Running` by a crash, continuing from the active time of its
+    /// last heartbeat. Time the app was down is not counted.
+    pub async fn resume_session(&self, session: Session) -> Result<TimerState> {
+        let mode = if session.is_break {
+            TimerMode::Break
+        } else if session.target_ms >= i64::MAX as u64 {
+            TimerMode::Stopwatch
+        } else {
+            TimerMode::Countdown
+        };
 
-        let (session_snapshot, is_break_mode) = {
-            let mut state = self.state.lock().await;
-            if state.status == TimerStatus::Idle {
-                return Err(anyhow!("no active session to end"));
+        if mode != TimerMode::Stopwatch && session.active_ms >= session.target_ms {
+            return Err(anyhow!(
+                "session {} already reached its target",
+                session.id
+            ));
+        }
+
+        let mut state = self.state.lock().await;
+        state.ensure_idle()?;
+        state.begin_session(
+            session.id.clone(),
+            session.target_ms,
+            mode,
+            session.started_at,
+            Instant::now(),
+        );
+        state.active_ms_baseline = session.active_ms;
+        state.active_ms = session.active_ms;
+        drop(state);
+
+        let resumed: Result<()> = async {
+            if !session.is_break || self.settings.sense_during_breaks() {
+                self.sensing
+                    .lock()
+                    .await
+                    .start_sensing(
+                        session.id.clone(),
+                        self.db.clone(),
+                        self.metrics.clone(),
+                        self.app_handle.clone(),
+                        self.settings.clone(),
+                    )
+                    .await?;
             }
 
-            let is_break = state.mode == TimerMode::Break;
+            self.spawn_ticker().await;
 
-            // Allow manual end for both countdown and stopwatch modes
-            // Users should be able to end any timer early from the island UI
+            #[cfg(target_os = "macos")]
+            start_island(mode, session.target_ms, session.active_ms);
 
-            state.sync_active_from_anchor();
+            info!(
+                "Resumed session {} at {}ms active",
+                session.id, session.active_ms
+            );
+            self.log_event(
+                &session.id,
+                SessionEventType::Resumed,
+                Some(format!("{}ms active", session.active_ms)),
+            )
+            .await;
+            self.emit_state_changed().await
+        }
+        .await;
 
-            let session_id = state
-                .session_id
-                .clone()
-                .ok_or_else(|| anyhow!("missing session id"))?;
-            let started_at = state.started_at.unwrap_or_else(Utc::now);
-            let target_ms = state.target_ms;
-            let active_ms = state.current_active_ms().min(target_ms);
+        // Don't leave the timer claiming a session that nothing is tracking;
+        // recover_incomplete_session marks the row Interrupted when this fails
+        if let Err(err) = resumed {
+            if let Err(stop_err) = self.sensing.lock().await.stop_sensing().await {
+                error!("Failed to stop sensing after a failed resume: {stop_err:#}");
+            }
+            self.cancel_ticker().await;
+            self.state.lock().await.cancel();
 
-            state.stop();
-            state.cancel();
+            #[cfg(target_os = "macos")]
+            {
+                island_reset();
+            }
 
-            (
-                Session {
-                    id: session_id,
-                    started_at,
-                    stopped_at: Some(stopped_at),
-                    status: SessionStatus::Completed,
-                    target_ms,
-                    active_ms,
-                    label_id: None,
-                    created_at: started_at,
-                    updated_at: stopped_at,
-                },
-                is_break,
-            )
-        };
+            return Err(err);
+        }
+
+        Ok(self.get_state().await)
+    }
+
+    pub async fn end_timer(&self) -> Result<SessionInfo> {
+        self.end_session(None).await
+    }
+
+    /// End the current session. `auto_end_reason` is set when the app rather than
+    /// the user ends it, and is recorded as the session's single terminal event.
+    async fn end_session(&self, auto_end_reason: Option<&'static str>) -> Result<SessionInfo> {
+        let stopped_at = Utc::now();
+
+        // State returns to Idle only after persistence and segmentation below finish
+        let (session_snapshot, is_break_mode) =
+            claim_session_for_end(&mut *self.state.lock().await, stopped_at)?;
+
+        let finalized = self
+            .finalize_ended_session(&session_snapshot, is_break_mode, stopped_at, auto_end_reason)
+            .await;
+
+        // Back to Idle even if finalizing failed, so the timer can't get stuck
+        self.state.lock().await.cancel();
+        self.emit_state_changed().await?;
+        finalized?;
+
+        // Fetch the actual session from DB to get the correct label_id
+        // (session_snapshot has label_id: None because it's a snapshot from the timer state)
+        let session_from_db = self
+            .db
+            .get_session(&session_snapshot.id)
+            .await?
+            .ok_or_else(|| anyhow!("session {} not found after ending", session_snapshot.id))?;
+        let session_info = SessionInfo::from(session_from_db);
 
-        // Skip sensing stop for Break mode (it was never started)
+        // Skip session_completed event for Break mode (no results modal)
         if !is_break_mode {
-            self.sensing.lock().await.stop_sensing().await?;
+            self.emit_session_completed(&session_info).await?;
+            self.check_daily_goal().await;
         }
+
+        Ok(session_info)
+    }
+
+    /// Persist, segment, and post-process a session `end_timer` has claimed.
+    async fn finalize_ended_session(
+        &self,
+        session_snapshot: &Session,
+        is_break_mode: bool,
+        stopped_at: DateTime<Utc>,
+        auto_end_reason: Option<&'static str>,
+    ) -> Result<()> {
+        // No-op when sensing was skipped for a break
+        self.sensing.lock().await.stop_sensing().await?;
         self.cancel_ticker().await;
 
         #[cfg(target_os = "macos")]
@@ -263,127 +380,101 @@ impl TimerController {
             island_reset();
         }
 
-        // Skip DB updates and segmentation for Break mode
-        if is_break_mode {
-            // Emit state change before returning so frontend knows timer is back to idle
-            self.emit_state_changed().await?;
-
-            return Ok(SessionInfo {
-                id: session_snapshot.id,
-                started_at: session_snapshot.started_at,
-                stopped_at: session_snapshot.stopped_at,
-                status: SessionStatus::Completed,
-                target_ms: session_snapshot.target_ms,
-                active_ms: session_snapshot.active_ms,
-                label_id: None,
-            });
+        persist_ended_session(&self.db, session_snapshot, stopped_at, auto_end_reason).await?;
+
+        if !is_break_mode && self.settings.auto_label_enabled() {
+            self.apply_auto_label(&session_snapshot.id).await;
         }
 
-        self.db
-            .mark_session_status(
-                &session_snapshot.id,
-                SessionStatus::Completed,
-                session_snapshot.active_ms,
-                session_snapshot.stopped_at,
-                stopped_at,
-            )
-            .await?;
+        if !is_break_mode && self.settings.session_notifications_enabled() {
+            self.post_completion_notification(&session_snapshot.id, session_snapshot.active_ms)
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// Emit `daily-goal-reached` the first time today's focus time meets the goal.
+    /// Failures are logged, not fatal.
+    async fn check_daily_goal(&self) {
+        let goal_minutes = self.settings.daily_goal_minutes();
+        if goal_minutes == 0 {
+            return;
+        }
 
-        // Run segmentation synchronously so UI can render results immediately
+        let progress = match self
+            .db
+            .get_today_progress(local_offset_minutes(), goal_minutes)
+            .await
         {
-            use crate::segmentation::{segment_session, SegmentationConfig};
-
-            let session_id = session_snapshot.id.clone();
-
-            match self.db.get_context_readings_for_session(&session_id).await {
-                Ok(readings) => match segment_session(readings, &SegmentationConfig::default()) {
-                    Ok((segments, interruptions)) => {
-                        // Insert segments and interruptions atomically in a single transaction
-                        // This prevents race conditions where segments might be deleted before interruptions are inserted
-                        if let Err(e) = self
-                            .db
-                            .insert_segments_and_interruptions(
-                                &session_id,
-                                &segments,
-                                &interruptions,
-                            )
-                            .await
-                        {
-                            error!("Failed to insert segments and interruptions: {}", e);
-                            error!(
-                                "Segments count: {}, Interruptions count: {}",
-                                segments.len(),
-                                interruptions.len()
-                            );
-                            if !segments.is_empty() {
-                                error!(
-                                    "Segment IDs: {:?}",
-                                    segments.iter().map(|s| &s.id).collect::<Vec<_>>()
-                                );
-                            }
-                            if !interruptions.is_empty() {
-                                error!(
-                                    "Interruption segment_ids: {:?}",
-                                    interruptions
-                                        .iter()
-                                        .map(|i| &i.segment_id)
-                                        .collect::<Vec<_>>()
-                                );
-                            }
-                        } else {
-                            // Update context_readings with segment_ids
-                            let segment_tuples: Vec<(
-                                String,
-                                chrono::DateTime<chrono::Utc>,
-                                chrono::DateTime<chrono::Utc>,
-                            )> = segments
-                                .iter()
-                                .map(|s| (s.id.clone(), s.start_time, s.end_time))
-                                .collect();
-                            if let Err(e) = self
-                                .db
-                                .update_readings_with_segment_ids(&session_id, &segment_tuples)
-                                .await
-                            {
-                                error!("Failed to update readings with segment_ids: {}", e);
-                            } else {
-                                info!(
-                                    "Created {} segments and {} interruptions for session {}",
-                                    segments.len(),
-                                    interruptions.len(),
-                                    session_id
-                                );
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Segmentation failed: {}", e);
-                    }
-                },
-                Err(e) => {
-                    error!("Failed to load readings for segmentation: {}", e);
-                }
+            Ok(progress) => progress,
+            Err(e) => {
+                error!("Failed to compute daily goal progress: {}", e);
+                return;
+            }
+        };
+
+        if progress.percentage >= 100.0 && self.settings.mark_daily_goal_reached(progress.date) {
+            if let Err(err) = self.app_handle.emit("daily-goal-reached", &progress) {
+                error!("Failed to emit daily-goal-reached: {err}");
             }
         }
+    }
 
-        self.emit_state_changed().await?;
+    /// Label an unlabeled session from its dominant apps. Failures are logged, not fatal.
+    async fn apply_auto_label(&self, session_id: &str) {
+        let exclude_system_ui = self.settings.exclude_system_ui();
+        let top_apps = match self
+            .db
+            .get_top_apps_for_session(session_id, 10, exclude_system_ui)
+            .await
+        {
+            Ok(top_apps) => top_apps,
+            Err(e) => {
+                error!("Failed to load top apps for auto-labeling: {}", e);
+                return;
+            }
+        };
 
-        // Fetch the actual session from DB to get the correct label_id
-        // (session_snapshot has label_id: None because it's a snapshot from the timer state)
-        let session_from_db = self.db.get_session(&session_snapshot.id).await?;
-        let session_info = SessionInfo::from(session_from_db);
+        match self.db.apply_auto_label_rules(session_id, top_apps).await {
+            Ok(Some(label_id)) => {
+                info!("Auto-labeled session {} with label {}", session_id, label_id)
+            }
+            Ok(None) => {}
+            Err(e) => error!("Failed to apply auto-label rules: {}", e),
+        }
+    }
 
-        // Skip session_completed event for Break mode (no results modal)
-        if !is_break_mode {
-            self.emit_session_completed(&session_info).await?;
+    /// Post "Focus session complete — 50 minutes, 92% in VS Code" via the native bridge.
+    async fn post_completion_notification(&self, session_id: &str, active_ms: u64) {
+        let minutes = (active_ms + 30_000) / 60_000;
+        let mut body = if minutes == 1 {
+            "1 minute".to_string()
+        } else {
+            format!("{} minutes", minutes)
+        };
+
+        let exclude_system_ui = self.settings.exclude_system_ui();
+        match self
+            .db
+            .get_top_apps_for_session(session_id, 1, exclude_system_ui)
+            .await
+        {
+            Ok(top_apps) => {
+                if let Some(app) = top_apps.first() {
+                    let app_name = app.app_name.as_deref().unwrap_or(&app.bundle_id);
+                    body.push_str(&format!(", {:.0}% in {}", app.percentage, app_name));
+                }
+            }
+            Err(e) => error!("Failed to load top app for completion notification: {}", e),
         }
 
-        Ok(session_info)
+        crate::macos_bridge::post_notification("Focus session complete", &body);
     }
 
-    pub async fn cancel_timer(&self) -> Result<()> {
+    pub async fn cancel_timer(&self, reason: Option<String>) -> Result<()> {
         let cancelled_at = Utc::now();
-        let (session_id, active_ms, is_break_mode) = {
+        let (session_id, active_ms) = {
             let mut state = self.state.lock().await;
             if state.status == TimerStatus::Idle {
                 #[cfg(target_os = "macos")]
@@ -392,7 +483,9 @@ impl TimerController {
                 }
                 return Ok(());
             }
-            let is_break = state.mode == TimerMode::Break;
+            if state.status == TimerStatus::Finalizing {
+                return Err(anyhow!("session is already ending"));
+            }
             state.sync_active_from_anchor();
             let session_id = state
                 .session_id
@@ -400,13 +493,11 @@ impl TimerController {
                 .ok_or_else(|| anyhow!("no active session to cancel"))?;
             let active_ms = state.active_ms;
             state.cancel();
-            (session_id, active_ms, is_break)
+            (session_id, active_ms)
         };
 
-        // Skip sensing stop for Break mode (it was never started)
-        if !is_break_mode {
-            self.sensing.lock().await.stop_sensing().await?;
-        }
+        // No-op when sensing was skipped for a break
+        self.sensing.lock().await.stop_sensing().await?;
         self.cancel_ticker().await;
 
         #[cfg(target_os = "macos")]
@@ -414,18 +505,11 @@ impl TimerController {
             island_reset();
         }
 
-        // Skip DB update for Break mode
-        if !is_break_mode {
-            self.db
-                .mark_session_status(
-                    &session_id,
-                    SessionStatus::Cancelled,
-                    active_ms,
-                    Some(cancelled_at),
-                    cancelled_at,
-                )
-                .await?;
-        }
+        self.db
+            .mark_session_cancelled(&session_id, active_ms, cancelled_at, reason.clone())
+            .await?;
+        self.log_event(&session_id, SessionEventType::Cancelled, reason)
+            .await;
         self.emit_state_changed().await?;
         Ok(())
     }
@@ -442,6 +526,8 @@ impl TimerController {
         let tick_interval = self.tick_interval;
         let heartbeat_every = self.heartbeat_every_ticks;
         let sensing = self.sensing.clone();
+        let max_stopwatch_ms = self.settings.max_stopwatch_secs().saturating_mul(1000);
+        let controller = self.clone();
 
         let handle = tokio::spawn(async move {
             let mut interval = time::interval(tick_interval);
@@ -449,17 +535,35 @@ impl TimerController {
             loop {
                 interval.tick().await;
 
-                let (snapshot, remaining) = {
+                // With `active_requires_interaction`, idle ticks don't count as focus time
+                let idle = controller.settings.active_requires_interaction() && user_is_idle();
+
+                let (snapshot, remaining, first_idle_pause) = {
                     let mut guard = state.lock().await;
                     if guard.status != TimerStatus::Running {
                         break;
                     }
+                    let first_idle_pause = idle && !guard.idle_excluded;
+                    if idle {
+                        guard.pause_accrual();
+                    }
                     guard.sync_active_from_anchor();
                     let remaining = guard.remaining_ms();
                     let snapshot = guard.clone();
-                    (snapshot, remaining)
+                    (snapshot, remaining, first_idle_pause)
                 };
 
+                if first_idle_pause {
+                    if let Some(session_id) = snapshot.session_id.as_deref() {
+                        if let Err(e) = db.mark_session_idle_excluded(session_id).await {
+                            error!(
+                                "Failed to flag session {} as idle-excluded: {}",
+                                session_id, e
+                            );
+                        }
+                    }
+                }
+
                 #[cfg(target_os = "macos")]
                 {
                     island_sync(snapshot.remaining_ms());
@@ -469,42 +573,72 @@ impl TimerController {
                 if remaining <= 0
                     && (snapshot.mode == TimerMode::Countdown || snapshot.mode == TimerMode::Break)
                 {
-                    let final_snapshot = {
+                    let (final_snapshot, actual_active_ms) = {
                         let mut guard = state.lock().await;
                         guard.sync_active_from_anchor();
                         guard.stop();
+                        let actual_active_ms = guard.unclamped_active_ms();
                         guard.active_ms = guard.active_ms.min(guard.target_ms);
-                        guard.clone()
+                        (guard.clone(), actual_active_ms)
                     };
 
-                    // Stop sensing immediately (skip for Break mode)
-                    if final_snapshot.mode != TimerMode::Break {
-                        if let Err(e) = sensing.lock().await.stop_sensing().await {
-                            error!("Failed to stop sensing on timer completion: {}", e);
-                        }
+                    // Stop sensing immediately (no-op for breaks that didn't sense)
+                    if let Err(e) = sensing.lock().await.stop_sensing().await {
+                        error!("Failed to stop sensing on timer completion: {}", e);
                     }
 
                     emit_timer_state(&app_handle, final_snapshot.clone());
 
-                    // Skip DB update for Break mode
-                    if final_snapshot.mode != TimerMode::Break {
-                        if let Some(session_id) = final_snapshot.session_id.clone() {
-                            let db_clone = db.clone();
-                            tokio::spawn(async move {
-                                let _ = db_clone
-                                    .update_session_progress(
-                                        &session_id,
-                                        final_snapshot.active_ms,
-                                        Utc::now(),
-                                    )
-                                    .await;
-                            });
-                        }
+                    if let Some(session_id) = final_snapshot.session_id.clone() {
+                        let db_clone = db.clone();
+                        let controller = controller.clone();
+                        tokio::spawn(async move {
+                            let _ = db_clone
+                                .update_session_progress(
+                                    &session_id,
+                                    final_snapshot.active_ms,
+                                    actual_active_ms,
+                                    Utc::now(),
+                                )
+                                .await;
+                            controller
+                                .log_event(&session_id, SessionEventType::TargetReached, None)
+                                .await;
+                        });
                     }
 
                     break;
                 }
 
+                // End runaway stopwatches. end_timer aborts this ticker, so run it
+                // on its own task and stop ticking here.
+                if snapshot.stopwatch_cap_reached(max_stopwatch_ms) {
+                    let controller = controller.clone();
+                    tokio::spawn(async move {
+                        match controller.end_session(Some("max_duration")).await {
+                            Ok(session) => {
+                                info!(
+                                    "Stopwatch session {} reached the {}ms cap; ended",
+                                    session.id, max_stopwatch_ms
+                                );
+                                let payload = SessionAutoEndedEvent {
+                                    session_id: session.id,
+                                    reason: "max_duration",
+                                };
+                                if let Err(e) =
+                                    controller.app_handle.emit("session-auto-ended", payload)
+                                {
+                                    error!("Failed to emit session-auto-ended: {}", e);
+                                }
+                            }
+                            Err(e) => {
+                                error!("Failed to auto-end stopwatch at max duration: {}", e)
+                            }
+                        }
+                    });
+                    break;
+                }
+
                 ticks = ticks.wrapping_add(1);
 
                 if let Some(session_id) = snapshot.session_id.clone() {
@@ -526,6 +660,7 @@ impl TimerController {
                                 .update_session_progress(
                                     &session_id_clone,
                                     snapshot_clone.active_ms,
+                                    snapshot_clone.active_ms,
                                     now,
                                 )
                                 .await;
@@ -565,6 +700,166 @@ impl TimerController {
     }
 }
 
+/// Append to a session's lifecycle log. Failures are logged, not fatal.
+async fn log_session_event(
+    db: &Database,
+    session_id: &str,
+    event_type: SessionEventType,
+    detail: Option<String>,
+) {
+    if let Err(e) = db.log_session_event(session_id, event_type, detail).await {
+        error!(
+            "Failed to log {} event for session {}: {}",
+            event_type.as_str(),
+            session_id,
+            e
+        );
+    }
+}
+
+/// Insert a `Running` session row and start `state` on it. The state lock is held
+/// from the idle check through `begin_session`, so concurrent starts (frontend
+/// commands and island callbacks) can't both pass the check or both insert a row.
+async fn begin_new_session(
+    state: &Mutex<TimerState>,
+    db: &Database,
+    mode: TimerMode,
+    target_ms: u64,
+    label_id: Option<i64>,
+) -> Result<(String, DateTime<Utc>)> {
+    let mut state = state.lock().await;
+    state.ensure_idle()?;
+
+    let session_id = Uuid::new_v4().to_string();
+    let started_at = Utc::now();
+    let session = Session {
+        id: session_id.clone(),
+        started_at,
+        stopped_at: None,
+        status: SessionStatus::Running,
+        target_ms,
+        active_ms: 0,
+        actual_active_ms: 0,
+        label_id,
+        cancel_reason: None,
+        is_break: mode == TimerMode::Break,
+        created_at: started_at,
+        updated_at: started_at,
+    };
+    db.insert_session(&session).await?;
+
+    // `start_timer` resets the anchor once sensing is up
+    state.begin_session(
+        session_id.clone(),
+        target_ms,
+        mode,
+        started_at,
+        Instant::now(),
+    );
+    Ok((session_id, started_at))
+}
+
+/// Claim the current session for ending: stop the clock and hold it in `Finalizing`,
+/// so concurrent ends and starts are rejected until the caller resets the state.
+/// Returns the completed session to persist and whether it was a break.
+fn claim_session_for_end(
+    state: &mut TimerState,
+    stopped_at: DateTime<Utc>,
+) -> Result<(Session, bool)> {
+    // Any mode can be ended early, e.g. from the island UI
+    state.ensure_endable()?;
+    state.sync_active_from_anchor();
+
+    let session_id = state
+        .session_id
+        .clone()
+        .ok_or_else(|| anyhow!("missing session id"))?;
+    let is_break = state.mode == TimerMode::Break;
+    let started_at = state.started_at.unwrap_or_else(Utc::now);
+    let target_ms = state.target_ms;
+    let actual_active_ms = state.unclamped_active_ms();
+    let active_ms = state.current_active_ms().min(target_ms);
+    state.finalize();
+
+    let session = Session {
+        id: session_id,
+        started_at,
+        stopped_at: Some(stopped_at),
+        status: SessionStatus::Completed,
+        target_ms,
+        active_ms,
+        actual_active_ms,
+        label_id: None,
+        cancel_reason: None,
+        is_break,
+        created_at: started_at,
+        updated_at: stopped_at,
+    };
+    Ok((session, is_break))
+}
+
+/// Mark a claimed session `Completed`, record its terminal event and segment it.
+/// Segmentation runs synchronously so the UI can render results immediately; a
+/// failure there is logged, not returned.
+async fn persist_ended_session(
+    db: &Database,
+    session: &Session,
+    stopped_at: DateTime<Utc>,
+    auto_end_reason: Option<&'static str>,
+) -> Result<()> {
+    db.mark_session_status(
+        &session.id,
+        SessionStatus::Completed,
+        session.active_ms,
+        session.actual_active_ms,
+        session.stopped_at,
+        stopped_at,
+    )
+    .await?;
+    let (event_type, detail) = match auto_end_reason {
+        Some(reason) => (SessionEventType::AutoEnded, reason.to_string()),
+        None => (
+            SessionEventType::Ended,
+            format!("{}ms active", session.active_ms),
+        ),
+    };
+    log_session_event(db, &session.id, event_type, Some(detail)).await;
+
+    if let Err(e) = segment_and_store(db, &session.id, session.stopped_at).await {
+        error!("Segmentation failed for session {}: {:#}", session.id, e);
+    }
+    Ok(())
+}
+
+/// Whether the user has gone long enough without input that active time should
+/// stop accruing under `active_requires_interaction`.
+#[cfg(target_os = "macos")]
+fn user_is_idle() -> bool {
+    crate::macos_bridge::get_idle_seconds() >= INTERACTION_IDLE_THRESHOLD_SECS
+}
+
+#[cfg(not(target_os = "macos"))]
+fn user_is_idle() -> bool {
+    false
+}
+
+/// Start the island for a session that has already been active for `active_ms`.
+#[cfg(target_os = "macos")]
+fn start_island(mode: TimerMode, target_ms: u64, active_ms: u64) {
+    let start_uptime_ms = current_uptime_ms() - active_ms.min(i64::MAX as u64) as i64;
+    let island_target_ms = match mode {
+        TimerMode::Countdown | TimerMode::Break => target_ms.min(i64::MAX as u64) as i64,
+        TimerMode::Stopwatch => 0,
+    };
+    let mode_str = match mode {
+        TimerMode::Countdown => "countdown",
+        TimerMode::Break => "break",
+        TimerMode::Stopwatch => "stopwatch",
+    };
+
+    island_start(start_uptime_ms, island_target_ms, mode_str);
+}
+
 fn emit_timer_state(app_handle: &AppHandle, state: TimerState) {
     let payload = TimerStateChangedEvent {
         remaining_ms: state.remaining_ms(),
@@ -573,3 +868,81 @@ fn emit_timer_state(app_handle: &AppHandle, state: TimerState) {
 
     let _ = app_handle.emit("timer-state-changed", payload);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::reading;
+
+    #[tokio::test]
+    async fn concurrent_starts_create_exactly_one_session() {
+        let db = Database::new_in_memory().unwrap();
+        let state = Mutex::new(TimerState::new());
+
+        let (first, second) = tokio::join!(
+            begin_new_session(&state, &db, TimerMode::Countdown, 60_000, None),
+            begin_new_session(&state, &db, TimerMode::Countdown, 60_000, None),
+        );
+
+        let results = [first, second];
+        let (session_id, _) = results.iter().find_map(|r| r.as_ref().ok()).unwrap();
+        let err = results.iter().find_map(|r| r.as_ref().err()).unwrap();
+        assert_eq!(err.to_string(), "timer already active");
+
+        let sessions = db.list_sessions().await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(&sessions[0].id, session_id);
+        assert_eq!(sessions[0].status, SessionStatus::Running);
+        let state = state.lock().await;
+        assert_eq!(state.status, TimerStatus::Running);
+        assert_eq!(state.session_id.as_ref(), Some(session_id));
+    }
+
+    #[tokio::test]
+    async fn concurrent_ends_persist_and_segment_once() {
+        let db = Database::new_in_memory().unwrap();
+        let state = Mutex::new(TimerState::new());
+        let (session_id, started_at) =
+            begin_new_session(&state, &db, TimerMode::Countdown, 3_600_000, None)
+                .await
+                .unwrap();
+        let readings: Vec<_> = (0..3)
+            .map(|i| {
+                let at = started_at + chrono::Duration::seconds(i * 5);
+                reading(&session_id, at, "com.a")
+            })
+            .collect();
+        db.insert_context_readings_batch(&readings).await.unwrap();
+
+        // end_session's steps, minus the sensing, ticker and island teardown
+        let stopped_at = started_at + chrono::Duration::seconds(60);
+        let (state, db) = (&state, &db);
+        let end = || async move {
+            let (session, _) = claim_session_for_end(&mut *state.lock().await, stopped_at)?;
+            let persisted = persist_ended_session(db, &session, stopped_at, None).await;
+            state.lock().await.cancel();
+            persisted
+        };
+        let (first, second) = tokio::join!(end(), end());
+
+        let results = [first, second];
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        let err = results.iter().find_map(|r| r.as_ref().err()).unwrap();
+        assert_eq!(err.to_string(), "session is already ending");
+
+        let segments = db
+            .get_segments_for_session(&session_id, None)
+            .await
+            .unwrap();
+        assert_eq!(segments.len(), 1);
+        let events = db.get_session_events(&session_id).await.unwrap();
+        let ended = events
+            .iter()
+            .filter(|e| e.event_type == SessionEventType::Ended)
+            .count();
+        assert_eq!(ended, 1);
+        let stored = db.get_session(&session_id).await.unwrap().unwrap();
+        assert_eq!(stored.status, SessionStatus::Completed);
+        assert_eq!(state.lock().await.status, TimerStatus::Idle);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod commands;
+pub mod compare;
 pub mod controller;
+pub mod recovery;
 pub mod state;
 
-pub use controller::{TimerController, TimerSnapshot};
+pub use controller::{StartTimerResult, TimerController, TimerSnapshot};
+pub use recovery::{recover_incomplete_session, segment_interrupted_session, StartupRecovery};
 pub use state::{TimerMode, TimerState, TimerStatus};
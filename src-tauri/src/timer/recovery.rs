@@ -0,0 +1,232 @@
+use std::future::Future;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+use serde::Serialize;
+
+use crate::{
+    db::{
+        models::{Session, SessionEventType},
+        Database,
+    },
+    segmentation::segment_and_store,
+};
+
+use super::TimerController;
+
+/// What startup did with a session the previous run left `Running`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupRecovery {
+    pub session_id: String,
+    /// `true` if the timer picked the session back up, `false` if it was interrupted
+    pub resumed: bool,
+    /// Seconds between the session's last heartbeat and this startup
+    pub offline_secs: i64,
+    pub grace_secs: u64,
+    /// When the session last checkpointed; an interrupted session ends here
+    #[serde(skip)]
+    pub last_heartbeat: DateTime<Utc>,
+}
+
+impl StartupRecovery {
+    pub fn event_name(&self) -> &'static str {
+        if self.resumed {
+            "session-recovered"
+        } else {
+            "session-interrupted-on-startup"
+        }
+    }
+}
+
+/// Finalize or resume the session a crash left running.
+///
+/// Sessions whose last heartbeat is within `grace_secs` are resumed; older ones (or ones
+/// that fail to resume) are marked `Interrupted`. Segmenting those is left to
+/// `segment_interrupted_session`, so startup doesn't wait on it.
+pub async fn recover_incomplete_session(
+    db: &Database,
+    timer: &TimerController,
+    grace_secs: u64,
+) -> Result<Option<StartupRecovery>> {
+    recover_with(db, grace_secs, Utc::now(), |session| {
+        timer.resume_session(session)
+    })
+    .await
+}
+
+/// `recover_incomplete_session` as of `now`, with the timer's resume step passed in.
+async fn recover_with<F, Fut, T>(
+    db: &Database,
+    grace_secs: u64,
+    now: DateTime<Utc>,
+    resume: F,
+) -> Result<Option<StartupRecovery>>
+where
+    F: FnOnce(Session) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let Some(session) = db.get_incomplete_session().await? else {
+        return Ok(None);
+    };
+
+    let offline_secs = (now - session.updated_at).num_seconds().max(0);
+    let session_id = session.id.clone();
+    let last_heartbeat = session.updated_at;
+
+    let resumed = if offline_secs as u64 <= grace_secs {
+        match resume(session).await {
+            Ok(_) => {
+                info!(
+                    "Resumed session {} after {}s offline",
+                    session_id, offline_secs
+                );
+                true
+            }
+            Err(err) => {
+                error!("Failed to resume session {}: {err:#}", session_id);
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    if !resumed {
+        warn!(
+            "Recovered incomplete session {} ({}s offline, grace {}s); marking as Interrupted",
+            session_id, offline_secs, grace_secs
+        );
+        db.mark_session_interrupted(&session_id, now).await?;
+        let detail = format!("{}s offline, grace {}s", offline_secs, grace_secs);
+        if let Err(err) = db
+            .log_session_event(&session_id, SessionEventType::Interrupted, Some(detail))
+            .await
+        {
+            error!(
+                "Failed to log interrupted event for session {}: {err:#}",
+                session_id
+            );
+        }
+    }
+
+    Ok(Some(StartupRecovery {
+        session_id,
+        resumed,
+        offline_secs,
+        grace_secs,
+        last_heartbeat,
+    }))
+}
+
+/// Segment a session that `recover_incomplete_session` marked `Interrupted` from the
+/// readings it captured, so it still gets a timeline. No-op for resumed sessions.
+pub async fn segment_interrupted_session(db: &Database, recovery: &StartupRecovery) {
+    if recovery.resumed {
+        return;
+    }
+
+    // The session ended at its last heartbeat, not at this startup
+    if let Err(err) =
+        segment_and_store(db, &recovery.session_id, Some(recovery.last_heartbeat)).await
+    {
+        error!(
+            "Failed to segment interrupted session {}: {err:#}",
+            recovery.session_id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use anyhow::anyhow;
+    use chrono::Duration;
+
+    use super::*;
+    use crate::db::{
+        models::SessionStatus,
+        test_support::{session, t0},
+    };
+
+    const GRACE_SECS: u64 = 120;
+
+    /// A `Running` session whose last heartbeat was at `t0()`.
+    async fn running_session(db: &Database) {
+        let mut running = session("s1", t0() - Duration::minutes(30));
+        running.status = SessionStatus::Running;
+        running.stopped_at = None;
+        running.updated_at = t0();
+        db.insert_session(&running).await.unwrap();
+    }
+
+    async fn status(db: &Database) -> SessionStatus {
+        db.get_session("s1").await.unwrap().unwrap().status
+    }
+
+    #[tokio::test]
+    async fn resumes_a_session_within_the_grace_window() {
+        let db = Database::new_in_memory().unwrap();
+        running_session(&db).await;
+
+        let now = t0() + Duration::seconds(GRACE_SECS as i64);
+        let recovery = recover_with(&db, GRACE_SECS, now, |_| async { Ok(()) })
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(recovery.resumed);
+        assert_eq!(recovery.offline_secs, GRACE_SECS as i64);
+        assert_eq!(status(&db).await, SessionStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn interrupts_a_session_outside_the_grace_window_without_resuming() {
+        let db = Database::new_in_memory().unwrap();
+        running_session(&db).await;
+        let resumes = AtomicUsize::new(0);
+
+        let now = t0() + Duration::seconds(GRACE_SECS as i64 + 1);
+        let recovery = recover_with(&db, GRACE_SECS, now, |_| async {
+            resumes.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(!recovery.resumed);
+        assert_eq!(resumes.load(Ordering::SeqCst), 0);
+        assert_eq!(recovery.last_heartbeat, t0());
+        assert_eq!(status(&db).await, SessionStatus::Interrupted);
+    }
+
+    #[tokio::test]
+    async fn interrupts_a_session_that_fails_to_resume() {
+        let db = Database::new_in_memory().unwrap();
+        running_session(&db).await;
+
+        let recovery = recover_with(&db, GRACE_SECS, t0(), |_| async {
+            Err::<(), _>(anyhow!("sensing failed to start"))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(!recovery.resumed);
+        assert_eq!(status(&db).await, SessionStatus::Interrupted);
+    }
+
+    #[tokio::test]
+    async fn does_nothing_without_a_running_session() {
+        let db = Database::new_in_memory().unwrap();
+
+        let recovery = recover_with(&db, GRACE_SECS, t0(), |_| async { Ok(()) })
+            .await
+            .unwrap();
+
+        assert!(recovery.is_none());
+    }
+}
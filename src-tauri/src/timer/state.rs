@@ -1,3 +1,4 @@
+use anyhow::{bail, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::cmp;
@@ -9,6 +10,9 @@ pub enum TimerStatus {
     Idle,
     Running,
     Stopped,
+    /// `end_timer` has claimed the session and is persisting and segmenting it;
+    /// returns to `Idle` once that finishes.
+    Finalizing,
 }
 
 impl Default for TimerStatus {
@@ -25,6 +29,16 @@ pub enum TimerMode {
     Break,
 }
 
+impl TimerMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimerMode::Countdown => "Countdown",
+            TimerMode::Stopwatch => "Stopwatch",
+            TimerMode::Break => "Break",
+        }
+    }
+}
+
 impl Default for TimerMode {
     fn default() -> Self {
         TimerMode::Countdown
@@ -46,6 +60,9 @@ pub struct TimerState {
     pub active_ms_baseline: u64,
     #[serde(skip)]
     pub running_anchor: Option<Instant>,
+    /// `pause_accrual` has left idle time out of this session's active time.
+    #[serde(skip)]
+    pub idle_excluded: bool,
 }
 
 impl Default for TimerState {
@@ -59,6 +76,7 @@ impl Default for TimerState {
             started_at: None,
             active_ms_baseline: 0,
             running_anchor: None,
+            idle_excluded: false,
         }
     }
 }
@@ -70,7 +88,7 @@ impl TimerState {
 
     pub fn remaining_ms(&self) -> i64 {
         match (self.status, self.mode) {
-            (TimerStatus::Idle | TimerStatus::Stopped, _) => 0,
+            (TimerStatus::Idle | TimerStatus::Stopped | TimerStatus::Finalizing, _) => 0,
             (TimerStatus::Running, TimerMode::Countdown) => {
                 let remaining = self.target_ms as i64 - self.current_active_ms() as i64;
                 cmp::max(remaining, 0)
@@ -96,6 +114,17 @@ impl TimerState {
         }
     }
 
+    /// Active time without the countdown clamp. The auto-stop path clamps `active_ms`
+    /// after `stop()` has already copied the unclamped value into the baseline.
+    pub fn unclamped_active_ms(&self) -> u64 {
+        match self.status {
+            TimerStatus::Running => self.current_active_ms(),
+            TimerStatus::Idle | TimerStatus::Stopped | TimerStatus::Finalizing => {
+                cmp::max(self.active_ms, self.active_ms_baseline)
+            }
+        }
+    }
+
     pub fn sync_active_from_anchor(&mut self) {
         if let (TimerStatus::Running, Some(anchor)) = (self.status, self.running_anchor) {
             self.active_ms = self
@@ -104,6 +133,45 @@ impl TimerState {
         }
     }
 
+    /// Discard the time since active time was last synced: the baseline takes the
+    /// last synced `active_ms` and `running_anchor` restarts now, so
+    /// `current_active_ms` resumes from there. Called each tick the user is idle;
+    /// any sync between ticks (e.g. a state emit) still counts up to that point.
+    pub fn pause_accrual(&mut self) {
+        if self.status == TimerStatus::Running {
+            self.active_ms_baseline = self.active_ms;
+            self.running_anchor = Some(Instant::now());
+            self.idle_excluded = true;
+        }
+    }
+
+    /// Fail unless the timer is idle. Callers hold the state lock from this check
+    /// through `begin_session`, so concurrent starts can't both pass it.
+    pub fn ensure_idle(&self) -> Result<()> {
+        if self.status != TimerStatus::Idle {
+            bail!("timer already active");
+        }
+        Ok(())
+    }
+
+    /// Fail unless there is a session to end that no other caller has claimed.
+    /// Callers hold the state lock from this check through `finalize`.
+    pub fn ensure_endable(&self) -> Result<()> {
+        match self.status {
+            TimerStatus::Idle => bail!("no active session to end"),
+            TimerStatus::Finalizing => bail!("session is already ending"),
+            TimerStatus::Running | TimerStatus::Stopped => Ok(()),
+        }
+    }
+
+    /// Whether a running stopwatch has accrued `max_stopwatch_ms` and should be
+    /// auto-ended. Countdowns and breaks stop at their own target instead.
+    pub fn stopwatch_cap_reached(&self, max_stopwatch_ms: u64) -> bool {
+        self.status == TimerStatus::Running
+            && self.mode == TimerMode::Stopwatch
+            && self.current_active_ms() >= max_stopwatch_ms
+    }
+
     pub fn begin_session(
         &mut self,
         session_id: String,
@@ -121,6 +189,7 @@ impl TimerState {
             started_at: Some(start_at),
             active_ms_baseline: 0,
             running_anchor: Some(now),
+            idle_excluded: false,
         };
     }
 
@@ -131,7 +200,40 @@ impl TimerState {
         self.active_ms_baseline = self.active_ms;
     }
 
+    /// Stop and hold the session in `Finalizing` so concurrent ends and starts
+    /// are rejected until `cancel` resets the state.
+    pub fn finalize(&mut self) {
+        self.stop();
+        self.status = TimerStatus::Finalizing;
+    }
+
     pub fn cancel(&mut self) {
         *self = Self::default();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stopwatch_cap_is_reached_once_accrued() {
+        const CAP_MS: u64 = 3_600_000;
+        let two_hours_ago = Instant::now()
+            .checked_sub(std::time::Duration::from_secs(7_200))
+            .unwrap();
+        let begin = |mode: TimerMode, anchor: Instant| {
+            let mut state = TimerState::new();
+            state.begin_session("session".to_string(), 0, mode, Utc::now(), anchor);
+            state
+        };
+
+        assert!(!begin(TimerMode::Stopwatch, Instant::now()).stopwatch_cap_reached(CAP_MS));
+        let mut overrun = begin(TimerMode::Stopwatch, two_hours_ago);
+        assert!(overrun.stopwatch_cap_reached(CAP_MS));
+        // Only running stopwatches are capped
+        assert!(!begin(TimerMode::Countdown, two_hours_ago).stopwatch_cap_reached(CAP_MS));
+        overrun.finalize();
+        assert!(!overrun.stopwatch_cap_reached(CAP_MS));
+    }
+}
@@ -0,0 +1,95 @@
+//! App colors: a Rust port of the Swift bridge's dominant-color extraction (used when
+//! the bridge can't re-fetch an icon), plus deterministic fallbacks for apps with no
+//! stored color.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::GenericImageView;
+
+/// Dominant color of a `data:image/png;base64,...` icon as `#RRGGBB`.
+///
+/// Mirrors `AppIconProvider.extractDominantColor`: skips transparent, near-black and
+/// near-white pixels, prefers the most common colorful pixel, and falls back to the
+/// most common remaining pixel when the icon is grayscale.
+pub fn compute_dominant_color(icon_data_url: &str) -> Option<String> {
+    let encoded = icon_data_url
+        .split_once("base64,")
+        .map_or(icon_data_url, |(_, data)| data);
+    let bytes = STANDARD.decode(encoded.trim()).ok()?;
+    let img = image::load_from_memory(&bytes).ok()?;
+
+    let mut colorful: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut all: HashMap<[u8; 3], usize> = HashMap::new();
+
+    for (_, _, pixel) in img.pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a < 128 {
+            continue;
+        }
+
+        let (ri, gi, bi) = (i32::from(r), i32::from(g), i32::from(b));
+        let brightness = (ri + gi + bi) / 3;
+        if !(20..=235).contains(&brightness) {
+            continue;
+        }
+
+        *all.entry([r, g, b]).or_insert(0) += 1;
+
+        let max_diff = (ri - gi).abs().max((gi - bi).abs()).max((ri - bi).abs());
+        if max_diff >= 15 {
+            *colorful.entry([r, g, b]).or_insert(0) += 1;
+        }
+    }
+
+    let most_common = |counts: &HashMap<[u8; 3], usize>| {
+        counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(rgb, _)| *rgb)
+    };
+
+    most_common(&colorful)
+        .or_else(|| most_common(&all))
+        .map(|[r, g, b]| format!("#{:02X}{:02X}{:02X}", r, g, b))
+}
+
+/// Saturation/lightness of fallback colors, matching the muted app palette in the UI.
+const FALLBACK_SATURATION: f32 = 0.25;
+const FALLBACK_LIGHTNESS: f32 = 0.5;
+
+/// True for `#RRGGBB` hex colors, the format stored in `apps.icon_color`.
+pub fn validate_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Deterministic muted color for an app without a stored color. The hue is an FNV-1a
+/// hash of the bundle ID, so it is stable across runs and builds.
+pub fn fallback_color_for_bundle(bundle_id: &str) -> String {
+    let hash = bundle_id.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    let hue = (hash % 360) as f32;
+
+    let chroma = (1.0 - (2.0 * FALLBACK_LIGHTNESS - 1.0).abs()) * FALLBACK_SATURATION;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = FALLBACK_LIGHTNESS - chroma / 2.0;
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let to_byte = |v: f32| ((v + m) * 255.0).round() as u8;
+
+    format!("#{:02X}{:02X}{:02X}", to_byte(r), to_byte(g), to_byte(b))
+}
+
+/// The stored color if it is valid, otherwise the bundle's fallback color.
+pub fn color_or_fallback(color: Option<String>, bundle_id: &str) -> String {
+    color
+        .filter(|c| validate_color(c))
+        .unwrap_or_else(|| fallback_color_for_bundle(bundle_id))
+}
@@ -1,25 +1,139 @@
-//! Conditional logging macros that check a module-level `ENABLE_LOGS` flag.
+//! Conditional logging macros gated by a module tag or a module-level `ENABLE_LOGS` flag,
+//! plus the app logger, which also keeps recent lines in memory for diagnostics.
 //!
 //! Usage:
 //! ```rust
-//! // In your module, define the flag first:
-//! const ENABLE_LOGS: bool = true;
+//! // The macros are exported at the crate root:
+//! use lefocus_lib::log_info;
 //!
-//! // Then use the macros (they're exported at the crate root):
-//! use lefocus_lib::{log_info, log_warn, log_error};
+//! // Tagged: toggled at runtime via `set_enabled_modules` (the `set_log_modules` command)
+//! const LOG_TAG: &str = "loop_worker";
+//! log_info!(tag: LOG_TAG, "This will log while loop_worker is enabled");
 //!
+//! // Untagged: the module must define the flag, checked at compile time
+//! const ENABLE_LOGS: bool = true;
 //! log_info!("This will log if ENABLE_LOGS is true");
 //! ```
 
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Mutex, OnceLock, RwLock};
+
+/// Log lines kept in memory for `recent_logs`.
+const RECENT_LOG_CAPACITY: usize = 1000;
+
+static RECENT_LOGS: OnceLock<Mutex<VecDeque<(Level, String)>>> = OnceLock::new();
+
+fn recent_logs_buffer() -> &'static Mutex<VecDeque<(Level, String)>> {
+    RECENT_LOGS.get_or_init(|| Mutex::new(VecDeque::with_capacity(RECENT_LOG_CAPACITY)))
+}
+
+/// Writes through env_logger (stderr) and keeps the last `RECENT_LOG_CAPACITY` lines,
+/// so packaged builds can show recent logs without a terminal.
+struct TeeLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for TeeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+
+        let line = format!(
+            "{} {:<5} {}: {}",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        let mut buffer = recent_logs_buffer()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if buffer.len() == RECENT_LOG_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back((record.level(), line));
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the app logger: env_logger at `default_level` (overridable via `RUST_LOG`),
+/// teed into the in-memory buffer read by `recent_logs`.
+pub fn init(default_level: LevelFilter) -> Result<(), SetLoggerError> {
+    let inner = env_logger::Builder::from_default_env()
+        .filter_level(default_level)
+        .build();
+    let max_level = inner.filter();
+    log::set_boxed_logger(Box::new(TeeLogger { inner }))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// Buffered log lines at or above `level_filter` (e.g. `Warn` keeps warnings and
+/// errors), oldest first.
+pub fn recent_logs(level_filter: LevelFilter) -> Vec<String> {
+    let buffer = recent_logs_buffer()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    buffer
+        .iter()
+        .filter(|(level, _)| *level <= level_filter)
+        .map(|(_, line)| line.clone())
+        .collect()
+}
+
+/// `None` means every tag is enabled (the default until the set is configured).
+static ENABLED_MODULES: OnceLock<RwLock<Option<HashSet<String>>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Option<HashSet<String>>> {
+    ENABLED_MODULES.get_or_init(|| RwLock::new(None))
+}
+
+/// Restrict tagged logging to `modules`, or re-enable every tag with `None`.
+pub fn set_enabled_modules(modules: Option<Vec<String>>) {
+    let mut guard = registry().write().unwrap_or_else(|e| e.into_inner());
+    *guard = modules.map(|m| m.into_iter().collect());
+}
+
+/// Currently enabled tags, or `None` if every tag is enabled.
+pub fn enabled_modules() -> Option<Vec<String>> {
+    let guard = registry().read().unwrap_or_else(|e| e.into_inner());
+    guard.as_ref().map(|set| {
+        let mut modules: Vec<String> = set.iter().cloned().collect();
+        modules.sort();
+        modules
+    })
+}
+
+/// Whether `tag` is currently enabled; re-exported at the crate root for the tagged
+/// macro arms, which expand outside this private module.
+pub fn module_enabled(tag: &str) -> bool {
+    let guard = registry().read().unwrap_or_else(|e| e.into_inner());
+    match guard.as_ref() {
+        Some(set) => set.contains(tag),
+        None => true,
+    }
+}
+
 /// Macro for conditional info logging.
-/// Checks the `ENABLE_LOGS` const in the calling module.
-///
-/// Each module that uses this macro must define:
-/// ```rust
-/// const ENABLE_LOGS: bool = true; // or false
-/// ```
+/// With `tag: ...` checks the runtime registry; otherwise checks the `ENABLE_LOGS`
+/// const in the calling module.
 #[macro_export]
 macro_rules! log_info {
+    (tag: $tag:expr, $($arg:tt)+) => {
+        if $crate::module_enabled($tag) {
+            log::info!($($arg)+);
+        }
+    };
     ($($arg:tt)*) => {
         if ENABLE_LOGS {
             log::info!($($arg)*);
@@ -28,14 +142,15 @@ macro_rules! log_info {
 }
 
 /// Macro for conditional warn logging.
-/// Checks the `ENABLE_LOGS` const in the calling module.
-///
-/// Each module that uses this macro must define:
-/// ```rust
-/// const ENABLE_LOGS: bool = true; // or false
-/// ```
+/// With `tag: ...` checks the runtime registry; otherwise checks the `ENABLE_LOGS`
+/// const in the calling module.
 #[macro_export]
 macro_rules! log_warn {
+    (tag: $tag:expr, $($arg:tt)+) => {
+        if $crate::module_enabled($tag) {
+            log::warn!($($arg)+);
+        }
+    };
     ($($arg:tt)*) => {
         if ENABLE_LOGS {
             log::warn!($($arg)*);
@@ -44,14 +159,15 @@ macro_rules! log_warn {
 }
 
 /// Macro for conditional error logging.
-/// Checks the `ENABLE_LOGS` const in the calling module.
-///
-/// Each module that uses this macro must define:
-/// ```rust
-/// const ENABLE_LOGS: bool = true; // or false
-/// ```
+/// With `tag: ...` checks the runtime registry; otherwise checks the `ENABLE_LOGS`
+/// const in the calling module.
 #[macro_export]
 macro_rules! log_error {
+    (tag: $tag:expr, $($arg:tt)+) => {
+        if $crate::module_enabled($tag) {
+            log::error!($($arg)+);
+        }
+    };
     ($($arg:tt)*) => {
         if ENABLE_LOGS {
             log::error!($($arg)*);
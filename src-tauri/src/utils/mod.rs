@@ -1 +1,3 @@
+pub mod color;
 pub mod logging;
+pub mod time;
@@ -0,0 +1,7 @@
+use chrono::Local;
+
+/// The machine's current UTC offset in minutes east of UTC, for callers that
+/// don't pass the frontend's timezone.
+pub fn local_offset_minutes() -> i32 {
+    Local::now().offset().local_minus_utc() / 60
+}
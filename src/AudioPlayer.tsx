@@ -2,7 +2,24 @@ import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import "./App.css";
 
-type SoundType = "Binaural" | "BrownNoise" | "Rain";
+type SoundType = "Binaural" | "BrownNoise" | "Rain" | "Ocean" | "Campfire";
+
+/** Mirrors the Rust `AudioError` payload: `{ code, message }`. */
+interface AudioError {
+  code: "no_output_device" | "thread_unavailable" | "channel";
+  message: string;
+}
+
+/** Mirrors the Rust `AudioStartResult`; `audioStarted` is false without an output device. */
+interface AudioStartResult {
+  audioStarted: boolean;
+  message: string;
+}
+
+function formatAudioError(error: unknown): string {
+  const audioError = error as Partial<AudioError> | null;
+  return audioError?.message ?? String(error);
+}
 
 function AudioPlayer() {
   const [soundType, setSoundType] = useState<SoundType>("Binaural");
@@ -15,19 +32,22 @@ function AudioPlayer() {
 
   async function startAudio() {
     try {
-      const result = await invoke<string>("start_audio", {
+      const result = await invoke<AudioStartResult>("start_audio", {
         soundType,
         leftFreq: soundType === "Binaural" ? leftFreq : null,
         rightFreq: soundType === "Binaural" ? rightFreq : null,
       });
-      setMessage(result);
+      setMessage(result.message);
+      if (!result.audioStarted) {
+        return;
+      }
       setIsPlaying(true);
       setIsPaused(false);
 
       // Set initial volume
-      await invoke("set_volume", { volume });
+      setVolume(await invoke<number>("set_volume", { volume }));
     } catch (error) {
-      setMessage(`Error: ${error}`);
+      setMessage(`Error: ${formatAudioError(error)}`);
     }
   }
 
@@ -38,7 +58,7 @@ function AudioPlayer() {
       setIsPlaying(false);
       setIsPaused(false);
     } catch (error) {
-      setMessage(`Error: ${error}`);
+      setMessage(`Error: ${formatAudioError(error)}`);
     }
   }
 
@@ -48,7 +68,7 @@ function AudioPlayer() {
       setIsPaused(paused);
       setMessage(paused ? "Paused" : "Playing");
     } catch (error) {
-      setMessage(`Error: ${error}`);
+      setMessage(`Error: ${formatAudioError(error)}`);
     }
   }
 
@@ -56,9 +76,9 @@ function AudioPlayer() {
     setVolume(newVolume);
     if (isPlaying) {
       try {
-        await invoke("set_volume", { volume: newVolume });
+        setVolume(await invoke<number>("set_volume", { volume: newVolume }));
       } catch (error) {
-        setMessage(`Error: ${error}`);
+        setMessage(`Error: ${formatAudioError(error)}`);
       }
     }
   }
@@ -78,6 +98,8 @@ function AudioPlayer() {
             <option value="Binaural">Binaural Beats</option>
             <option value="Rain">Rain Sounds</option>
             <option value="BrownNoise">Brown Noise</option>
+            <option value="Ocean">Ocean Waves</option>
+            <option value="Campfire">Campfire</option>
           </select>
         </div>
 
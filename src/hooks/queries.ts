@@ -11,9 +11,26 @@
 
 import { useQuery, useMutation, useQueryClient, useQueries, useInfiniteQuery } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
-import type { SessionSummary, SessionInfo } from "@/types/timer";
-import type { Segment, Interruption, WindowTitleWithDuration } from "@/types/segment";
-import type { Label, LabelInput } from "@/types/label";
+import type {
+  SessionSummary,
+  SessionInfo,
+  DailyProgress,
+  DailyFocus,
+  ActiveMsDiscrepancy,
+  SessionEvent,
+  SessionComparison,
+  SessionStatus,
+} from "@/types/timer";
+import type {
+  Segment,
+  Interruption,
+  CaptureGap,
+  TimelineReading,
+  SegmentHighlight,
+  WindowTitleWithDuration,
+} from "@/types/segment";
+import type { Label, LabelDefaults, LabelInput } from "@/types/label";
+import type { KnownApp, AppIconUpdate } from "@/types/app";
 
 // ============================================================================
 // QUERY HOOKS (Data Fetching)
@@ -38,6 +55,23 @@ export function useSessionsList() {
   });
 }
 
+/**
+ * Fetch a page of sessions whose status is any of `statuses` (e.g. for status chips)
+ */
+export function useSessionsByStatus(statuses: SessionStatus[], limit = 30, offset = 0) {
+  return useQuery({
+    queryKey: ['sessions', 'byStatus', [...statuses].sort(), limit, offset],
+    queryFn: async () => {
+      return await invoke<SessionSummary[]>("list_sessions_by_status", {
+        statuses,
+        limit,
+        offset,
+      });
+    },
+    staleTime: 60_000,
+  });
+}
+
 /**
  * Fetch paginated list of sessions with infinite scroll
  * Uses useInfiniteQuery for pagination with page size of 30
@@ -73,13 +107,16 @@ export function useSessionsListInfinite() {
 /**
  * Fetch segments for a specific session
  * Replaces: useSegments hook
+ * Pass minConfidence to hide segments below that confidence
  */
-export function useSegments(sessionId: string | null) {
+export function useSegments(sessionId: string | null, minConfidence?: number) {
   return useQuery({
-    queryKey: ['segments', sessionId],
+    queryKey: minConfidence === undefined
+      ? ['segments', sessionId]
+      : ['segments', sessionId, minConfidence],
     queryFn: async () => {
       console.log(`[useSegments] Fetching segments for session: ${sessionId}`);
-      const result = await invoke<Segment[]>("get_segments_for_session", { sessionId });
+      const result = await invoke<Segment[]>("get_segments_for_session", { sessionId, minConfidence });
       console.log(`[useSegments] Fetched ${result.length} segments for session: ${sessionId}`);
       return result;
     },
@@ -106,6 +143,90 @@ export function useInterruptions(segmentId: string | null) {
   });
 }
 
+/**
+ * Fetch every interruption in a session (all segments) for a session-level timeline
+ */
+export function useSessionInterruptions(sessionId: string | null) {
+  return useQuery({
+    queryKey: ['sessionInterruptions', sessionId],
+    queryFn: async () => {
+      return await invoke<Interruption[]>("get_interruptions_for_session", { sessionId });
+    },
+    enabled: !!sessionId,
+    staleTime: 300_000, // Consider fresh for 5 minutes (interruptions rarely change)
+  });
+}
+
+/**
+ * Fetch stretches of a session where sensing missed captures ("no data" regions)
+ */
+export function useCaptureGaps(sessionId: string | null) {
+  return useQuery({
+    queryKey: ['captureGaps', sessionId],
+    queryFn: async () => {
+      return await invoke<CaptureGap[]>("get_capture_gaps", { sessionId });
+    },
+    enabled: !!sessionId,
+    staleTime: 300_000, // Readings don't change once a session has ended
+  });
+}
+
+/**
+ * Seconds of a session hidden by useSegments at the given minConfidence
+ */
+export function useLowConfidenceDuration(sessionId: string | null, minConfidence: number) {
+  return useQuery({
+    queryKey: ['lowConfidenceDuration', sessionId, minConfidence],
+    queryFn: async () => {
+      return await invoke<number>("get_low_confidence_duration", { sessionId, minConfidence });
+    },
+    enabled: !!sessionId,
+    staleTime: 30_000,
+  });
+}
+
+/**
+ * Compare two sessions side by side (deltas are B minus A)
+ */
+export function useSessionComparison(sessionIdA: string | null, sessionIdB: string | null) {
+  return useQuery({
+    queryKey: ['sessions', 'compare', sessionIdA, sessionIdB],
+    queryFn: async () => {
+      return await invoke<SessionComparison>("compare_sessions", { sessionIdA, sessionIdB });
+    },
+    enabled: sessionIdA !== null && sessionIdB !== null,
+  });
+}
+
+/**
+ * Today's focus time against the daily goal, in the browser's timezone
+ */
+export function useTodayProgress() {
+  return useQuery({
+    queryKey: ['todayProgress'],
+    queryFn: async () => {
+      const tzOffsetMinutes = -new Date().getTimezoneOffset();
+      return await invoke<DailyProgress>("get_today_progress", { tzOffsetMinutes });
+    },
+    staleTime: 60_000,
+  });
+}
+
+/**
+ * Focus time per local day from `start` to `end` (inclusive, YYYY-MM-DD), in the
+ * browser's timezone, with zero entries for empty days
+ */
+export function useFocusByDay(start: string, end: string) {
+  return useQuery({
+    queryKey: ['focusByDay', start, end],
+    queryFn: async () => {
+      const tzOffsetMinutes = -new Date().getTimezoneOffset();
+      return await invoke<DailyFocus[]>("get_focus_by_day", { start, end, tzOffsetMinutes });
+    },
+    staleTime: 60_000,
+  });
+}
+
 /**
  * Fetch window titles for a specific segment with durations
  */
@@ -124,6 +245,97 @@ export function useWindowTitles(segmentId: string | null) {
   });
 }
 
+/**
+ * Fetch a segment's readings in order, with visual (pHash) and OCR text change
+ */
+export function useSegmentTimeline(segmentId: string | null) {
+  return useQuery({
+    queryKey: ['segmentTimeline', segmentId],
+    queryFn: async () => {
+      return await invoke<TimelineReading[]>("get_segment_timeline", { segmentId });
+    },
+    enabled: !!segmentId,
+    staleTime: 300_000,
+  });
+}
+
+/**
+ * Longest stable segments across history ("deep work highlights")
+ */
+export function useLongestSegments(options: {
+  limit?: number;
+  minConfidence?: number;
+  start?: string; // ISO 8601 datetime
+  end?: string; // ISO 8601 datetime
+} = {}) {
+  const { limit, minConfidence, start, end } = options;
+  return useQuery({
+    queryKey: ['segments', 'longest', limit, minConfidence, start, end],
+    queryFn: async () => {
+      return await invoke<SegmentHighlight[]>("get_longest_segments", {
+        limit,
+        minConfidence,
+        start,
+        end,
+      });
+    },
+    staleTime: 60_000,
+  });
+}
+
+/**
+ * Lifecycle events of a session (started, ended, cancelled, ...), oldest first
+ */
+export function useSessionEvents(sessionId: string | null) {
+  return useQuery({
+    queryKey: ['sessionEvents', sessionId],
+    queryFn: async () => {
+      return await invoke<SessionEvent[]>("get_session_events", { sessionId });
+    },
+    enabled: !!sessionId,
+    staleTime: 30_000,
+  });
+}
+
+/**
+ * Fetch the full OCR text of a single reading, on demand
+export function useReadingOcrText(readingId: number | null) {
+  return useQuery({
+    queryKey: ['readingOcrText', readingId],
+    queryFn: async () => {
+      return await invoke<string | null>("get_reading_ocr_text", { readingId });
+    },
+    enabled: readingId !== null,
+    staleTime: Infinity, // A reading's OCR text never changes
+  });
+}
+
+/**
+ * Fetch the tags on one segment
+ */
+export function useSegmentTags(segmentId: string | null) {
+  return useQuery({
+    queryKey: ['segmentTags', segmentId],
+    queryFn: async () => {
+      return await invoke<string[]>("get_segment_tags", { segmentId });
+    },
+    enabled: segmentId !== null,
+  });
+}
+
+/**
+ * Fetch every segment carrying a tag, newest first
+ */
+export function useSegmentsByTag(tag: string | null) {
+  return useQuery({
+    queryKey: ['segments', 'byTag', tag],
+    queryFn: async () => {
+      return await invoke<Segment[]>("get_segments_by_tag", { tag });
+    },
+    enabled: tag !== null && tag.trim() !== '',
+  });
+}
+
 /**
  * Fetch segments for multiple sessions in parallel
  * Automatically deduplicates requests and caches results
@@ -199,6 +411,43 @@ export function useAppDetails(
   });
 }
 
+/**
+ * Fetch an app's window titles across its whole history, longest first
+ */
+export function useAppAllTimeTitles(bundleId: string | null, limit?: number) {
+  return useQuery({
+    queryKey: ['appAllTimeTitles', bundleId, limit],
+    queryFn: async () => {
+      if (!bundleId) return null;
+
+      const result = await invoke<AppDetailsResponse>("get_app_all_time_titles", {
+        bundleId,
+        limit: limit ?? null,
+      });
+
+      return result.window_titles.map(([title, durationSecs]) => ({
+        title,
+        durationSecs,
+      })) as WindowTitleWithDuration[];
+    },
+    enabled: !!bundleId,
+    staleTime: 60_000, // Cache for 1 minute
+  });
+}
+
+/**
+ * Fetch every app the user has ever used, most used first (for app pickers)
+ */
+export function useKnownApps() {
+  return useQuery({
+    queryKey: ['knownApps'],
+    queryFn: async () => {
+      return await invoke<KnownApp[]>("list_known_apps");
+    },
+    staleTime: 60_000, // Cache for 1 minute
+  });
+}
+
 // ============================================================================
 // MUTATION HOOKS (State Changes)
 // ============================================================================
@@ -275,6 +524,19 @@ export function useCreateLabelMutation() {
   });
 }
 
+/**
+ * Timer defaults to pre-fill when a label is selected
+ */
+export function useLabelDefaults(labelId: number | null) {
+  return useQuery({
+    queryKey: ['labelDefaults', labelId],
+    queryFn: async () => {
+      return await invoke<LabelDefaults>("get_label_defaults", { labelId });
+    },
+    enabled: labelId !== null,
+  });
+}
+
 /**
  * Update an existing label
  * Automatically updates the label in the cache
@@ -283,14 +545,24 @@ export function useUpdateLabelMutation() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ labelId, name, color }: { labelId: number; name?: string; color?: string }) =>
-      invoke<Label>("update_label", { labelId, name, color }),
+    mutationFn: ({
+      labelId,
+      name,
+      color,
+      defaults,
+    }: {
+      labelId: number;
+      name?: string;
+      color?: string;
+      defaults?: LabelDefaults; // Replaces all three defaults; null fields clear them
+    }) => invoke<Label>("update_label", { labelId, name, color, defaults }),
     onSuccess: (updatedLabel) => {
       // Update the label in the cache
       queryClient.setQueryData<Label[]>(['labels'], (old) => {
         if (!old) return old;
         return old.map(label => label.id === updatedLabel.id ? updatedLabel : label);
       });
+      queryClient.invalidateQueries({ queryKey: ['labelDefaults', updatedLabel.id] });
     },
   });
 }
@@ -323,7 +595,7 @@ export function useUpdateSessionLabelMutation() {
 
   return useMutation({
     mutationFn: ({ sessionId, labelId }: { sessionId: string; labelId: number | null }) =>
-      invoke<void>("update_session_label", { sessionId, labelId }),
+      invoke<SessionSummary>("update_session_label", { sessionId, labelId }),
     onSuccess: (_, { sessionId, labelId }) => {
       // Optimistically update the sessions list cache
       queryClient.setQueryData<SessionSummary[]>(['sessions'], (old) => {
@@ -372,3 +644,134 @@ export function useDeleteSessionMutation() {
     },
   });
 }
+
+/**
+ * Re-attribute a segment to a different app
+ * Invalidates segment and session queries so top apps pick up the change
+ */
+export function useCorrectSegmentAppMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { segmentId: string; bundleId: string; appName?: string | null }) =>
+      invoke<void>("correct_segment_app", args),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+      queryClient.invalidateQueries({ queryKey: ['sessions'] });
+    },
+  });
+}
+
+/**
+ * Segment finished sessions that have readings but no timeline (e.g. ones interrupted by a crash)
+ */
+export function useSegmentUnsegmentedSessionsMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: () => invoke<number>("segment_unsegmented_sessions"),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+      queryClient.invalidateQueries({ queryKey: ['sessions'] });
+    },
+  });
+}
+
+/**
+ * Merge two adjacent segments of a session into one
+ */
+export function useMergeSegmentsMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { segmentIdA: string; segmentIdB: string }) =>
+      invoke<Segment>("merge_segments", args),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+      queryClient.invalidateQueries({ queryKey: ['sessions'] });
+    },
+  });
+}
+
+/**
+ * Split a segment in two at an ISO 8601 timestamp inside it
+ */
+export function useSplitSegmentMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { segmentId: string; at: string }) =>
+      invoke<Segment[]>("split_segment", args),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+      queryClient.invalidateQueries({ queryKey: ['sessions'] });
+    },
+  });
+}
+
+/**
+ * Add a tag to a segment
+ */
+export function useAddSegmentTagMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { segmentId: string; tag: string }) =>
+      invoke<string[]>("add_segment_tag", args),
+    onSuccess: (_tags, { segmentId }) => {
+      queryClient.invalidateQueries({ queryKey: ['segmentTags', segmentId] });
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+    },
+  });
+}
+
+/**
+ * Remove a tag from a segment
+ */
+export function useRemoveSegmentTagMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (args: { segmentId: string; tag: string }) =>
+      invoke<string[]>("remove_segment_tag", args),
+    onSuccess: (_tags, { segmentId }) => {
+      queryClient.invalidateQueries({ queryKey: ['segmentTags', segmentId] });
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+    },
+  });
+}
+
+/**
+ * Re-fetch one app's icon and color, replacing what is stored
+ */
+export function useRefreshAppIconMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (bundleId: string) => invoke<AppIconUpdate>("refresh_app_icon", { bundleId }),
+    onSuccess: () => {
+      // Icons and colors are embedded in session, segment and app query results
+      queryClient.invalidateQueries({ queryKey: ['sessions'] });
+      queryClient.invalidateQueries({ queryKey: ['segments'] });
+      queryClient.invalidateQueries({ queryKey: ['knownApps'] });
+    },
+  });
+}
+
+/**
+ * Audit completed sessions' active time against their segments; with `repair`,
+ * mismatched sessions are updated to the segment total
+ */
+export function useAuditActiveMsMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (repair: boolean) => invoke<ActiveMsDiscrepancy[]>("audit_active_ms", { repair }),
+    onSuccess: (_discrepancies, repair) => {
+      if (!repair) return;
+      queryClient.invalidateQueries({ queryKey: ['sessions'] });
+      queryClient.invalidateQueries({ queryKey: ['todayProgress'] });
+      queryClient.invalidateQueries({ queryKey: ['focusByDay'] });
+    },
+  });
+}
@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import {
   ISLAND_SOUND_OPTIONS,
+  IslandSoundId,
   IslandSoundSettings,
   IslandSoundSettingsWire,
   fromWire,
@@ -76,7 +77,7 @@ export function useIslandSoundSettings() {
     [applySettings]
   );
 
-  const previewSound = useCallback(async (soundId: string) => {
+  const previewSound = useCallback(async (soundId: IslandSoundId) => {
     await invoke("preview_island_chime", { sound_id: soundId, soundId });
   }, []);
 
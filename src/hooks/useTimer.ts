@@ -1,17 +1,22 @@
 import { useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useTimerSnapshot } from "./useTimerSnapshot";
-import type { SessionInfo, TimerMode } from "@/types/timer";
+import type { SessionInfo, StartTimerResult, TimerMode } from "@/types/timer";
 
 export function useTimer() {
   const { timerState, error, setError } = useTimerSnapshot();
 
-  const startTimer = useCallback(async (durationMs: number, mode: TimerMode, labelId?: number | null) => {
+  const startTimer = useCallback(async (
+    durationMs: number,
+    mode: TimerMode,
+    labelId?: number | null
+  ): Promise<StartTimerResult | null> => {
     try {
       setError("");
-      await invoke("start_timer", { targetMs: durationMs, mode, labelId });
+      return await invoke<StartTimerResult>("start_timer", { targetMs: durationMs, mode, labelId });
     } catch (err) {
       setError(`Failed to start timer: ${err}`);
+      return null;
     }
   }, []);
 
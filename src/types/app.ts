@@ -0,0 +1,15 @@
+export interface KnownApp {
+  bundleId: string;
+  appName: string | null;
+  iconDataUrl: string | null; // App icon (base64 PNG data URL)
+  iconColor: string; // Stored dominant color, or a fallback derived from bundleId
+  totalSecs: number; // Segment time across all sessions
+  sessionCount: number;
+}
+
+/** Result of `refresh_app_icon` and payload of `app-icon-updated`. */
+export interface AppIconUpdate {
+  bundleId: string;
+  iconDataUrl: string; // App icon (base64 PNG data URL)
+  iconColor: string | null; // Dominant color (hex like "#AABBCC"), if one was extracted
+}
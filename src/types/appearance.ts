@@ -0,0 +1,5 @@
+export type Theme = "System" | "Light" | "Dark";
+
+export interface AppearanceSettings {
+  theme: Theme;
+}
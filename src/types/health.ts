@@ -0,0 +1,48 @@
+import type { SensingStatus } from "./sensing";
+
+/** Mirrors the Rust `SchemaInfo`. */
+export interface SchemaInfo {
+  currentVersion: number;
+  expectedVersion: number;
+  upToDate: boolean;
+  readOnly: boolean;
+}
+
+export interface StartupRecovery {
+  sessionId: string;
+  resumed: boolean;
+  offlineSecs: number;
+  graceSecs: number;
+}
+
+/** `level_filter` accepted by `get_recent_logs`; each level includes the ones above it. */
+export type LogLevelFilter = "off" | "error" | "warn" | "info" | "debug" | "trace";
+
+/** Payload of `get_system_health`. */
+export interface SystemHealth {
+  schema: SchemaInfo | null;
+  databaseOk: boolean;
+  databaseDetail: string; // "ok", the first integrity problem, or the probe error
+  screenRecordingPermission: boolean;
+  accessibilityPermission: boolean;
+  audioDeviceAvailable: boolean;
+  audioDefaultDevice: string | null;
+  audioError: string | null;
+  sensing: SensingStatus;
+  startupRecovery: StartupRecovery | null;
+}
+
+/** Payload of `get_build_info`. */
+export interface BuildInfo {
+  version: string;
+  commit: string; // Short git hash, or "unknown"
+  buildTime: string | null; // ISO 8601 datetime
+  schemaVersion: number;
+}
+
+/** Result of `get_database_info` */
+export interface DatabaseInfo {
+  path: string;
+  sizeBytes: number;
+  walSizeBytes: number; // 0 when the -wal sidecar doesn't exist
+}
@@ -1,15 +1,23 @@
+/** Chime ids the backend accepts (`list_island_sounds`) */
+export type IslandSoundId =
+  | "island_default"
+  | "island_soft"
+  | "island_elevator"
+  | "island_404"
+  | "island_runaway";
+
 export interface IslandSoundSettings {
   enabled: boolean;
-  soundId: string;
+  soundId: IslandSoundId;
 }
 
 export interface IslandSoundSettingsWire {
   enabled: boolean;
-  sound_id: string;
+  sound_id: IslandSoundId;
 }
 
 export interface IslandSoundOption {
-  id: string;
+  id: IslandSoundId;
   label: string;
   description?: string;
 }
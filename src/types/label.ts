@@ -1,5 +1,9 @@
 // Label type definitions matching Rust backend
 
+import type { TimerMode } from "./timer";
+
+export type LabelSoundType = "Binaural" | "BrownNoise" | "Rain" | "Ocean" | "Campfire";
+
 export interface Label {
   id: number;
   name: string;
@@ -8,9 +12,37 @@ export interface Label {
   createdAt: string; // ISO 8601 datetime
   updatedAt: string; // ISO 8601 datetime
   deletedAt: string | null; // ISO 8601 datetime
+  defaultTargetMs: number | null;
+  defaultMode: TimerMode | null;
+  defaultSound: LabelSoundType | null;
 }
 
 export interface LabelInput {
   name: string;
   color: string;
+  defaultTargetMs?: number | null;
+  defaultMode?: TimerMode | null;
+  defaultSound?: LabelSoundType | null;
+}
+
+/** Timer settings to pre-fill when a label is selected; null leaves the form as is */
+export interface LabelDefaults {
+  targetMs: number | null;
+  mode: TimerMode | null;
+  sound: LabelSoundType | null;
+}
+
+export interface AutoLabelRule {
+  id: number;
+  bundleId: string;
+  labelId: number;
+  minPercentage: number; // 0-100
+  createdAt: string; // ISO 8601 datetime
+  updatedAt: string; // ISO 8601 datetime
+}
+
+export interface AutoLabelRuleInput {
+  bundleId: string;
+  labelId: number;
+  minPercentage: number;
 }
@@ -6,12 +6,23 @@ export interface CaptureMetrics {
   phash_ms: number;
   ocr_ms: number | null;
   ocr_skipped_reason: string | null;
+  skipped_reason: string | null;
   db_write_ms: number;
   total_ms: number;
   cpu_percent: number;
   memory_mb: number;
 }
 
+/** Result of `estimate_sensing_impact`: projected cost of sensing at the current interval. */
+export interface SensingImpact {
+  capture_interval_secs: number;
+  captures_per_hour: number;
+  avg_cpu_percent: number; // Process CPU sampled at recent captures
+  avg_capture_ms: number;
+  projected_cpu_seconds_per_hour: number;
+  sample_count: number; // Recent non-skipped captures behind the averages
+}
+
 export interface SystemMetrics {
   cpu_percent: number;
   memory_mb: number;
@@ -23,4 +34,21 @@ export interface MetricsSnapshot {
   capture_count: number;
   ocr_count: number;
   ocr_skip_count: number;
+  capture_skip_count: number;
+}
+
+export interface SessionMetricsSummary {
+  capture_count: number;
+  skipped_count: number;
+  avg_total_ms: number;
+  p50_total_ms: number;
+  p95_total_ms: number;
+  max_total_ms: number;
+  ocr_rate: number; // 0-1, share of non-skipped captures that ran OCR
+}
+
+/** Capture metrics persisted for a past session (`get_session_metrics`). */
+export interface SessionMetrics {
+  captures: CaptureMetrics[];
+  summary: SessionMetricsSummary;
 }
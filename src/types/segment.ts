@@ -1,5 +1,7 @@
 // Segment type definitions matching Rust backend
 
+export type SegmentType = "stable" | "transitioning" | "distracted";
+
 export interface Segment {
   id: string;
   sessionId: string;
@@ -9,16 +11,20 @@ export interface Segment {
   bundleId: string;
   appName: string | null;
   windowTitle: string | null;
+  segmentType: SegmentType;
   confidence: number;
   durationScore: number | null;
   stabilityScore: number | null;
   visualClarityScore: number | null;
   ocrQualityScore: number | null;
+  readingActivityScore: number | null;
   readingCount: number;
   uniquePhashCount: number | null;
+  avgOcrWordCount: number | null; // Mean OCR words per reading that ran OCR ("text density")
   segmentSummary: string | null;
   iconDataUrl?: string | null; // App icon from apps table (base64 PNG data URL)
   iconColor?: string | null; // App icon dominant color from apps table (hex like "#AABBCC")
+  tags: string[]; // Free-form tags, alphabetical
 }
 
 export interface Interruption {
@@ -37,6 +43,22 @@ export interface WindowTitleWithDuration {
   durationSecs: number;
 }
 
+/** One reading in a segment's timeline (`get_segment_timeline`). */
+export interface TimelineReading {
+  id: number;
+  timestamp: string; // ISO 8601 datetime
+  windowTitle: string;
+  phash: string | null;
+  ocrWordCount: number | null;
+  ocrTextDelta: number | null; // 0..1 share of words changed since the previous reading
+}
+
+export interface CaptureGap {
+  start: string; // ISO 8601 datetime of the reading before the gap
+  end: string; // ISO 8601 datetime of the reading after the gap
+  gapSecs: number;
+}
+
 export interface AppDuration {
   bundleId: string;
   appName: string | null;
@@ -52,3 +74,10 @@ export interface SegmentStats {
   interruptionCount: number;
   topApps: AppDuration[];
 }
+
+/** A long segment with its session's context (`get_longest_segments`). */
+export interface SegmentHighlight {
+  segment: Segment;
+  sessionStartedAt: string; // ISO 8601 datetime
+  sessionLabelId: number | null;
+}
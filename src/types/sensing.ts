@@ -0,0 +1,38 @@
+export type SensingState = "Idle" | "Running" | "Degraded" | "Suspended" | "Stopped";
+
+export interface SensingStatus {
+  state: SensingState;
+  sessionId: string | null;
+  lastCaptureAt: string | null;
+  captureIntervalSecs: number;
+  ocrEnabled: boolean;
+}
+
+export interface SensingConfig {
+  jitter_percent: number; // 0-50; randomizes each capture interval by up to ±this percent
+  max_ocr_chars: number; // OCR text per reading is truncated past this length
+  redact_ocr: boolean; // Strip emails, URLs, and long numbers from OCR text before storage
+  system_ui_debounce_captures: number; // 0-12; system UI readings are recorded only after persisting this many captures
+}
+
+/** Payload of `distraction-detected`, fired once per stretch on a distraction-listed app. */
+export interface DistractionDetectedEvent {
+  sessionId: string;
+  bundleId: string;
+  appName: string;
+  detectedAt: string; // ISO 8601 datetime
+}
+
+/** Payload of `sensing-stalled`, fired when a capture is skipped behind a stuck metadata call. */
+export interface SensingStalledEvent {
+  sessionId: string;
+  stalledForMs: number; // How long the previous capture's metadata call has been running
+  consecutiveStalls: number;
+  cacheReset: boolean; // This stall triggered a reset of the Swift sensing cache
+}
+
+/** Payload of `sensing-suspended` and `sensing-resumed`. */
+export interface SensingSuspensionEvent {
+  sessionId: string | null;
+  at: string; // ISO 8601 datetime
+}
@@ -1,10 +1,10 @@
 // Timer type definitions matching Rust backend
 
-export type TimerStatus = "idle" | "running" | "stopped";
+export type TimerStatus = "idle" | "running" | "stopped" | "finalizing";
 
 export type TimerMode = "countdown" | "stopwatch" | "break";
 
-export type SessionStatus = "active" | "completed" | "interrupted";
+export type SessionStatus = "running" | "completed" | "cancelled" | "interrupted";
 
 export interface TimerState {
   status: TimerStatus;
@@ -20,13 +20,20 @@ export interface TimerSnapshot {
   remaining_ms: number;
 }
 
+export interface StartTimerResult {
+  sessionId: string;
+  startedAt: string; // ISO 8601 datetime
+  state: TimerState;
+}
+
 export interface SessionInfo {
   id: string;
   startedAt: string; // ISO 8601 datetime
   stoppedAt: string | null; // ISO 8601 datetime
   status: SessionStatus;
   targetMs: number;
-  activeMs: number;
+  activeMs: number; // Clamped to targetMs for countdowns
+  actualActiveMs: number; // Unclamped active time, including overrun
   labelId: number | null;
 }
 
@@ -48,4 +55,86 @@ export interface SessionSummary {
   topApps: TopApp[];
   appIcons: Record<string, string | null>; // bundleId -> icon data URL (base64 PNG)
   appColors: Record<string, string | null>; // bundleId -> icon color (hex like "#AABBCC")
+  scattered: boolean; // Interruptions per active hour exceed the configured threshold
+}
+
+/** Segment-derived focus figures for one session */
+export interface SessionFocusStats {
+  focusScore: number; // Percent (0-100) of segmented time in stable segments
+  interruptionCount: number;
+}
+
+export interface SessionComparisonSide {
+  summary: SessionSummary;
+  focus: SessionFocusStats;
+}
+
+export interface SharedAppDelta {
+  bundleId: string;
+  appName: string | null;
+  durationSecsA: number;
+  durationSecsB: number;
+  deltaSecs: number; // B minus A
+}
+
+/** Every delta is B minus A */
+export interface SessionDiff {
+  activeMsDelta: number;
+  focusScoreDelta: number;
+  interruptionCountDelta: number;
+  appsOnlyInA: TopApp[];
+  appsOnlyInB: TopApp[];
+  sharedApps: SharedAppDelta[]; // Largest absolute change first
+}
+
+/** Result of `compare_sessions` */
+export interface SessionComparison {
+  a: SessionComparisonSide;
+  b: SessionComparisonSide;
+  diff: SessionDiff;
+}
+
+/** Today's focus time against the daily goal (`get_today_progress`, `daily-goal-reached`). */
+export interface DailyProgress {
+  date: string; // Local calendar date, YYYY-MM-DD
+  focusedMinutes: number;
+  goalMinutes: number; // 0 when no goal is set
+  percentage: number; // Can exceed 100
+}
+
+/** One day of `get_focus_by_day`; days without sessions are included as zeros. */
+export interface DailyFocus {
+  localDate: string; // Local calendar date, YYYY-MM-DD
+  totalActiveMs: number;
+  sessionCount: number;
+}
+
+export type SessionEventType =
+  | "started"
+  | "resumed" // Picked back up by startup recovery after a crash
+  | "target_reached" // A countdown or break ran down to zero
+  | "ended"
+  | "auto_ended" // Ended by the app, e.g. the stopwatch duration cap
+  | "cancelled"
+  | "interrupted" // Left running by a crash and marked Interrupted on startup
+  | "sensing_suspended"
+  | "sensing_resumed";
+
+/** One entry of a session's lifecycle log (`get_session_events`). */
+export interface SessionEvent {
+  id: number;
+  sessionId: string;
+  eventType: SessionEventType;
+  detail: string | null; // e.g. the cancel reason or the mode a session started in
+  at: string; // ISO 8601 datetime
+}
+
+/** One entry of `audit_active_ms`: a completed session whose `activeMs` disagrees with its segments. */
+export interface ActiveMsDiscrepancy {
+  sessionId: string;
+  startedAt: string; // ISO 8601 datetime
+  storedActiveMs: number;
+  segmentActiveMs: number; // Summed segment durations, clamped to the session target
+  segmentCount: number;
+  repaired: boolean; // activeMs was rewritten to segmentActiveMs
 }